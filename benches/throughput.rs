@@ -2,7 +2,7 @@ use std::hint::black_box;
 
 use criterion::{Criterion, Throughput, criterion_group, criterion_main};
 
-use yamd::{deserialize, lexer::Lexer, op, to_yamd};
+use yamd::{deserialize, lexer::Lexer, op, to_borrowed_yamd, to_yamd};
 
 // cancat of all YAMD documents from https://github.com/Lurk/barhamon/tree/main/content on
 // 2024-12-25
@@ -64,6 +64,19 @@ fn ast(c: &mut Criterion) {
     group.finish();
 }
 
+fn borrowed_ast(c: &mut Criterion) {
+    let mut group = c.benchmark_group("borrowed_ast");
+    group.measurement_time(std::time::Duration::from_secs(10));
+    for (name, input) in datasets() {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        let ops = op::parse(input);
+        group.bench_function(name, |b| {
+            b.iter(|| to_borrowed_yamd(black_box(&ops), black_box(input)))
+        });
+    }
+    group.finish();
+}
+
 fn roundtrip(c: &mut Criterion) {
     let mut group = c.benchmark_group("roundtrip");
     group.measurement_time(std::time::Duration::from_secs(10));
@@ -76,5 +89,5 @@ fn roundtrip(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, lexer, parser, ast, roundtrip);
+criterion_group!(benches, lexer, parser, ast, borrowed_ast, roundtrip);
 criterion_main!(benches);
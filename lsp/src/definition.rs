@@ -0,0 +1,132 @@
+use serde_json::{json, Value};
+use yamd::{
+    nodes::{Anchor, HeadingNodes, List, ParagraphNodes, YamdNodes},
+    op::SpanNode,
+};
+
+use crate::document::Document;
+
+/// Returns `Location` of the heading an in-document `#anchor` under `offset` points to.
+pub fn definition(uri: &str, document: &Document, offset: usize) -> Value {
+    let Some(target) = anchors(document)
+        .into_iter()
+        .find(|(_, span)| span.span.range.contains(&offset))
+        .and_then(|(anchor, _)| anchor.url.strip_prefix('#'))
+    else {
+        return Value::Null;
+    };
+    document
+        .headings()
+        .into_iter()
+        .zip(document.yamd.heading_ids())
+        .find(|(_, (_, id))| id == target)
+        .map(|((_, span), _)| json!({ "uri": uri, "range": document.lines().range(&span.span.range) }))
+        .unwrap_or(Value::Null)
+}
+
+fn anchors(document: &Document) -> Vec<(&Anchor, &SpanNode)> {
+    let mut anchors = vec![];
+    for (node, span, _) in document.blocks() {
+        match node {
+            YamdNodes::Paragraph(paragraph) => {
+                paragraph_anchors(&paragraph.body, &span.children, &mut anchors)
+            }
+            YamdNodes::Heading(heading) => {
+                for (node, span) in heading.body.iter().zip(&span.children) {
+                    if let HeadingNodes::Anchor(anchor) = node {
+                        anchors.push((anchor, span));
+                    }
+                }
+            }
+            YamdNodes::List(list) => list_anchors(list, span, &mut anchors),
+            YamdNodes::Highlight(highlight) => {
+                for (paragraph, span) in highlight.body.iter().zip(&span.children) {
+                    paragraph_anchors(&paragraph.body, &span.children, &mut anchors);
+                }
+            }
+            YamdNodes::Table(table) => {
+                let rows = std::iter::once(&table.header).chain(&table.body);
+                for (row, span) in rows.zip(&span.children) {
+                    for (cell, span) in row.cells.iter().zip(&span.children) {
+                        paragraph_anchors(&cell.body, &span.children, &mut anchors);
+                    }
+                }
+            }
+            YamdNodes::FootnoteDefinition(definition) => {
+                paragraph_anchors(&definition.body, &span.children, &mut anchors)
+            }
+            _ => {}
+        }
+    }
+    anchors
+}
+
+fn paragraph_anchors<'a>(
+    nodes: &'a [ParagraphNodes],
+    spans: &'a [SpanNode],
+    anchors: &mut Vec<(&'a Anchor, &'a SpanNode)>,
+) {
+    for (node, span) in nodes.iter().zip(spans) {
+        if let ParagraphNodes::Anchor(anchor) = node {
+            anchors.push((anchor, span));
+        }
+    }
+}
+
+fn list_anchors<'a>(
+    list: &'a List,
+    span: &'a SpanNode,
+    anchors: &mut Vec<(&'a Anchor, &'a SpanNode)>,
+) {
+    for (item, item_span) in list.body.iter().zip(&span.children) {
+        paragraph_anchors(&item.text, &item_span.children, anchors);
+        if let (Some(nested), Some(nested_span)) = (&item.nested_list, item_span.children.last()) {
+            list_anchors(nested, nested_span, anchors);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::definition;
+    use crate::document::Document;
+
+    #[test]
+    fn anchor_to_heading() {
+        let document = Document::new(
+            "# Intro\n\n{% more\n## Set up\n%}\n\n- see [setup](#set-up)".to_string(),
+        );
+        let offset = document.text.find("setup]").unwrap();
+        assert_eq!(
+            definition("file:///a.yamd", &document, offset),
+            json!({
+                "uri": "file:///a.yamd",
+                "range": {
+                    "start": {"line": 3, "character": 0},
+                    "end": {"line": 3, "character": 9}
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn anchor_to_duplicate_heading() {
+        let document = Document::new("# a\n\n# a\n\n[b](#a-1)".to_string());
+        let offset = document.text.find("b]").unwrap();
+        assert_eq!(
+            definition("file:///a.yamd", &document, offset)["range"]["start"]["line"],
+            2
+        );
+    }
+
+    #[test]
+    fn no_target() {
+        let document = Document::new("# a\n\n[b](#c) [d](https://e) f".to_string());
+        assert_eq!(definition("file:///a.yamd", &document, 1), Value::Null);
+        assert_eq!(definition("file:///a.yamd", &document, 6), Value::Null);
+        assert_eq!(definition("file:///a.yamd", &document, 13), Value::Null);
+        assert_eq!(definition("file:///a.yamd", &document, 25), Value::Null);
+    }
+}
@@ -0,0 +1,71 @@
+use serde_json::{json, Value};
+use yamd::lint::{lint, Severity};
+
+use crate::document::Document;
+
+/// Returns `textDocument/publishDiagnostics` params for the document.
+pub fn publish(uri: &str, document: Option<&Document>) -> Value {
+    let diagnostics: Vec<Value> = document
+        .map(|document| {
+            let lines = document.lines();
+            lint(&document.text)
+                .into_iter()
+                .map(|diagnostic| {
+                    let mut message = diagnostic.message;
+                    if let Some(suggestion) = diagnostic.suggestion {
+                        message.push_str(&format!("\nsuggestion: {suggestion}"));
+                    }
+                    json!({
+                        "range": lines.range(&diagnostic.span.range),
+                        "severity": match diagnostic.severity {
+                            Severity::Error => 1,
+                            Severity::Warning => 2,
+                            Severity::Info => 3,
+                        },
+                        "code": diagnostic.rule.name(),
+                        "source": "yamd",
+                        "message": message,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    json!({ "uri": uri, "diagnostics": diagnostics })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::publish;
+    use crate::document::Document;
+
+    #[test]
+    fn lint_diagnostics() {
+        let document = Document::new("# a\n\n[🤔]()".to_string());
+        assert_eq!(
+            publish("file:///a.yamd", Some(&document)),
+            json!({
+                "uri": "file:///a.yamd",
+                "diagnostics": [{
+                    "range": {
+                        "start": {"line": 2, "character": 0},
+                        "end": {"line": 2, "character": 6}
+                    },
+                    "severity": 1,
+                    "code": "empty-anchor-url",
+                    "source": "yamd",
+                    "message": "anchor `🤔` has empty url\nsuggestion: [🤔](url)"
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn closed_document() {
+        assert_eq!(
+            publish("file:///a.yamd", None),
+            json!({"uri": "file:///a.yamd", "diagnostics": []})
+        );
+    }
+}
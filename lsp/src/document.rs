@@ -0,0 +1,75 @@
+use yamd::{
+    deserialize_with_spans,
+    nodes::{Heading, Yamd, YamdNodes},
+    op::{SpanNode, Spans},
+};
+
+use crate::position::LineIndex;
+
+/// Open text document with its AST and spans.
+pub struct Document {
+    pub text: String,
+    pub yamd: Yamd,
+    pub spans: Spans,
+}
+
+impl Document {
+    pub fn new(text: String) -> Self {
+        let (yamd, spans) = deserialize_with_spans(&text);
+        Self { text, yamd, spans }
+    }
+
+    pub fn lines(&self) -> LineIndex<'_> {
+        LineIndex::new(&self.text)
+    }
+
+    /// Returns every block with its span in document order, including blocks nested in
+    /// collapsibles, together with the nesting depth.
+    pub fn blocks(&self) -> Vec<(&YamdNodes, &SpanNode, usize)> {
+        fn collect<'n>(
+            nodes: &'n [YamdNodes],
+            spans: &'n [SpanNode],
+            depth: usize,
+            out: &mut Vec<(&'n YamdNodes, &'n SpanNode, usize)>,
+        ) {
+            for (node, span) in nodes.iter().zip(spans) {
+                out.push((node, span, depth));
+                if let YamdNodes::Collapsible(collapsible) = node {
+                    collect(&collapsible.body, &span.children, depth + 1, out);
+                }
+            }
+        }
+        let mut out = Vec::new();
+        collect(&self.yamd.body, &self.spans.body, 0, &mut out);
+        out
+    }
+
+    /// Returns headings with their spans in document order.
+    pub fn headings(&self) -> Vec<(&Heading, &SpanNode)> {
+        self.blocks()
+            .into_iter()
+            .filter_map(|(node, span, _)| match node {
+                YamdNodes::Heading(heading) => Some((heading, span)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Document;
+
+    #[test]
+    fn blocks_include_collapsible_body() {
+        let document = Document::new("# a\n\n{% t\n## [b](c) d\n%}".to_string());
+        let depths: Vec<usize> = document.blocks().iter().map(|(_, _, d)| *d).collect();
+        assert_eq!(depths, vec![0, 0, 1]);
+        let headings: Vec<String> = document
+            .headings()
+            .iter()
+            .map(|(heading, _)| heading.text())
+            .collect();
+        assert_eq!(headings, vec!["a", "b d"]);
+    }
+}
@@ -0,0 +1,55 @@
+use serde_json::{json, Value};
+use yamd::nodes::YamdNodes;
+
+use crate::document::Document;
+
+/// Returns `FoldingRange`s of collapsibles, highlights, code blocks, lists and tables that span
+/// more than one line.
+pub fn folding_ranges(document: &Document) -> Value {
+    let lines = document.lines();
+    document
+        .blocks()
+        .into_iter()
+        .filter(|(node, _, _)| {
+            matches!(
+                node,
+                YamdNodes::Collapsible(_)
+                    | YamdNodes::Highlight(_)
+                    | YamdNodes::Code(_)
+                    | YamdNodes::DisplayMath(_)
+                    | YamdNodes::List(_)
+                    | YamdNodes::Table(_)
+            )
+        })
+        .filter_map(|(_, span, _)| {
+            let start = span.span.start.row;
+            let end = lines.last_line(&span.span.range);
+            (end > start).then(|| json!({ "startLine": start, "endLine": end }))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::folding_ranges;
+    use crate::document::Document;
+
+    #[test]
+    fn multi_line_blocks() {
+        let document = Document::new(
+            "{% t\n```rust\nlet a;\n```\n\n- a\n- b\n%}\n\n!! i\ntext\n!!\n\n- single\n\n# h"
+                .to_string(),
+        );
+        assert_eq!(
+            folding_ranges(&document),
+            json!([
+                {"startLine": 0, "endLine": 7},
+                {"startLine": 1, "endLine": 3},
+                {"startLine": 5, "endLine": 6},
+                {"startLine": 9, "endLine": 11}
+            ])
+        );
+    }
+}
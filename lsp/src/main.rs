@@ -0,0 +1,39 @@
+//! Language server for YAMD documents.
+//!
+//! Speaks [LSP](https://microsoft.github.io/language-server-protocol/) over stdio and supports:
+//!
+//! - diagnostics from [yamd::lint]
+//! - document symbols for headings
+//! - folding ranges for collapsibles, highlights, code blocks and lists
+//! - go to definition for `#anchor` links to headings in the same document
+//!
+//! Documents are synced in full on every change.
+
+mod definition;
+mod diagnostics;
+mod document;
+mod folding;
+mod position;
+mod server;
+mod symbols;
+mod transport;
+
+use std::io;
+
+use server::Server;
+
+fn main() -> io::Result<()> {
+    let mut input = io::stdin().lock();
+    let mut output = io::stdout().lock();
+    let mut server = Server::default();
+    while let Some(message) = transport::read(&mut input)? {
+        for outgoing in server.handle(message) {
+            transport::write(&mut output, &outgoing)?;
+        }
+        if let Some(code) = server.exit_code() {
+            std::process::exit(code);
+        }
+    }
+    // Client went away without `exit`.
+    std::process::exit(1);
+}
@@ -0,0 +1,82 @@
+use std::ops::Range;
+
+use serde_json::{json, Value};
+
+/// Converts between byte offsets and LSP positions, which count columns in UTF-16 code units.
+pub struct LineIndex<'a> {
+    text: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(text: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self { text, line_starts }
+    }
+
+    pub fn position(&self, offset: usize) -> Value {
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let character = self.text[self.line_starts[line]..offset]
+            .encode_utf16()
+            .count();
+        json!({ "line": line, "character": character })
+    }
+
+    pub fn range(&self, range: &Range<usize>) -> Value {
+        json!({ "start": self.position(range.start), "end": self.position(range.end) })
+    }
+
+    /// Returns byte offset of LSP position, clamped to the text.
+    pub fn offset(&self, position: &Value) -> usize {
+        let line = position["line"].as_u64().unwrap_or_default() as usize;
+        let character = position["character"].as_u64().unwrap_or_default() as usize;
+        let Some(&start) = self.line_starts.get(line) else {
+            return self.text.len();
+        };
+        let mut units = 0;
+        for (i, c) in self.text[start..].char_indices() {
+            if units >= character || c == '\n' {
+                return start + i;
+            }
+            units += c.len_utf16();
+        }
+        self.text.len()
+    }
+
+    /// Returns zero based line of the last character in `range`.
+    pub fn last_line(&self, range: &Range<usize>) -> usize {
+        let end = if range.end > range.start && self.text[..range.end].ends_with('\n') {
+            range.end - 1
+        } else {
+            range.end
+        };
+        self.line_starts.partition_point(|&start| start <= end) - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::LineIndex;
+
+    #[test]
+    fn utf16_columns() {
+        let index = LineIndex::new("a\n🤔b\nc");
+        assert_eq!(index.position(2), json!({"line": 1, "character": 0}));
+        assert_eq!(index.position(6), json!({"line": 1, "character": 2}));
+        assert_eq!(index.offset(&json!({"line": 1, "character": 2})), 6);
+        assert_eq!(index.offset(&json!({"line": 1, "character": 9})), 7);
+        assert_eq!(index.offset(&json!({"line": 7, "character": 0})), 9);
+    }
+
+    #[test]
+    fn last_line() {
+        let index = LineIndex::new("a\nb\nc");
+        assert_eq!(index.last_line(&(0..4)), 1);
+        assert_eq!(index.last_line(&(0..5)), 2);
+        assert_eq!(index.last_line(&(0..0)), 0);
+    }
+}
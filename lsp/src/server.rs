@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+
+use crate::{
+    definition::definition, diagnostics, document::Document, folding::folding_ranges,
+    symbols::document_symbols,
+};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// State of the language server. Takes incoming messages and returns outgoing ones.
+#[derive(Default)]
+pub struct Server {
+    documents: HashMap<String, Document>,
+    shutdown: bool,
+    exit: Option<i32>,
+}
+
+impl Server {
+    /// Process exit code, once `exit` notification is received.
+    pub fn exit_code(&self) -> Option<i32> {
+        self.exit
+    }
+
+    /// Handles one incoming message, returns responses and notifications to send.
+    pub fn handle(&mut self, message: Value) -> Vec<Value> {
+        let Some(method) = message["method"].as_str() else {
+            return if message.is_object() {
+                // Responses to server requests, the server does not send any.
+                vec![]
+            } else {
+                vec![error(Value::Null, PARSE_ERROR, "invalid JSON")]
+            };
+        };
+        let params = &message["params"];
+        match message.get("id").cloned() {
+            Some(id) => {
+                if self.shutdown && method != "exit" {
+                    return vec![error(id, INVALID_REQUEST, "server is shut down")];
+                }
+                match self.request(method, params) {
+                    Ok(result) => vec![json!({ "jsonrpc": "2.0", "id": id, "result": result })],
+                    Err((code, message)) => vec![error(id, code, &message)],
+                }
+            }
+            None => self.notification(method, params),
+        }
+    }
+
+    fn request(&mut self, method: &str, params: &Value) -> Result<Value, (i64, String)> {
+        match method {
+            "initialize" => Ok(json!({
+                "capabilities": {
+                    "textDocumentSync": 1,
+                    "documentSymbolProvider": true,
+                    "foldingRangeProvider": true,
+                    "definitionProvider": true,
+                },
+                "serverInfo": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            })),
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Value::Null)
+            }
+            "textDocument/documentSymbol" => Ok(document_symbols(self.document(params)?)),
+            "textDocument/foldingRange" => Ok(folding_ranges(self.document(params)?)),
+            "textDocument/definition" => {
+                let document = self.document(params)?;
+                let offset = document.lines().offset(&params["position"]);
+                Ok(definition(uri(params)?, document, offset))
+            }
+            _ => Err((METHOD_NOT_FOUND, format!("unknown method: {method}"))),
+        }
+    }
+
+    fn notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let Ok(uri) = uri(params) else {
+            if method == "exit" {
+                self.exit = Some(if self.shutdown { 0 } else { 1 });
+            }
+            return vec![];
+        };
+        let text = match method {
+            "textDocument/didOpen" => params["textDocument"]["text"].as_str(),
+            // Full sync: the last change holds the whole text.
+            "textDocument/didChange" => params["contentChanges"]
+                .as_array()
+                .and_then(|changes| changes.last())
+                .and_then(|change| change["text"].as_str()),
+            "textDocument/didClose" => {
+                self.documents.remove(uri);
+                return vec![publish_diagnostics(uri, None)];
+            }
+            _ => return vec![],
+        };
+        let Some(text) = text else {
+            return vec![];
+        };
+        let document = Document::new(text.to_string());
+        let diagnostics = publish_diagnostics(uri, Some(&document));
+        self.documents.insert(uri.to_string(), document);
+        vec![diagnostics]
+    }
+
+    fn document(&self, params: &Value) -> Result<&Document, (i64, String)> {
+        let uri = uri(params)?;
+        self.documents
+            .get(uri)
+            .ok_or_else(|| (INVALID_PARAMS, format!("unknown document: {uri}")))
+    }
+}
+
+fn uri(params: &Value) -> Result<&str, (i64, String)> {
+    params["textDocument"]["uri"]
+        .as_str()
+        .ok_or_else(|| (INVALID_PARAMS, "missing textDocument.uri".to_string()))
+}
+
+fn publish_diagnostics(uri: &str, document: Option<&Document>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": diagnostics::publish(uri, document),
+    })
+}
+
+fn error(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::Server;
+
+    fn open(server: &mut Server, text: &str) -> Vec<Value> {
+        server.handle(json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {"textDocument": {"uri": "file:///a.yamd", "languageId": "yamd", "version": 1, "text": text}}
+        }))
+    }
+
+    #[test]
+    fn change_replaces_text() {
+        let mut server = Server::default();
+        let sent = open(&mut server, "[a]()");
+        assert_eq!(
+            sent[0]["params"]["diagnostics"].as_array().unwrap().len(),
+            1
+        );
+        let sent = server.handle(json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didChange",
+            "params": {
+                "textDocument": {"uri": "file:///a.yamd", "version": 2},
+                "contentChanges": [{"text": "# a"}]
+            }
+        }));
+        assert_eq!(sent[0]["params"]["diagnostics"], json!([]));
+        let sent = server.handle(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "textDocument/documentSymbol",
+            "params": {"textDocument": {"uri": "file:///a.yamd"}}
+        }));
+        assert_eq!(sent[0]["result"][0]["name"], "a");
+    }
+
+    #[test]
+    fn unknown_document_and_method() {
+        let mut server = Server::default();
+        let sent = server.handle(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "textDocument/foldingRange",
+            "params": {"textDocument": {"uri": "file:///b.yamd"}}
+        }));
+        assert_eq!(sent[0]["error"]["code"], -32602);
+        let sent =
+            server.handle(json!({"jsonrpc": "2.0", "id": 2, "method": "textDocument/hover"}));
+        assert_eq!(sent[0]["error"]["code"], -32601);
+        let sent = server
+            .handle(json!({"jsonrpc": "2.0", "method": "$/cancelRequest", "params": {"id": 2}}));
+        assert!(sent.is_empty());
+    }
+
+    #[test]
+    fn close_clears_diagnostics() {
+        let mut server = Server::default();
+        open(&mut server, "[a]()");
+        let sent = server.handle(json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didClose",
+            "params": {"textDocument": {"uri": "file:///a.yamd"}}
+        }));
+        assert_eq!(sent[0]["params"]["diagnostics"], json!([]));
+        assert!(server.documents.is_empty());
+    }
+
+    #[test]
+    fn exit_code() {
+        let mut server = Server::default();
+        server.handle(json!({"jsonrpc": "2.0", "method": "exit"}));
+        assert_eq!(server.exit_code(), Some(1));
+
+        let mut server = Server::default();
+        server.handle(json!({"jsonrpc": "2.0", "id": 1, "method": "shutdown"}));
+        let sent = server.handle(json!({"jsonrpc": "2.0", "id": 2, "method": "initialize"}));
+        assert_eq!(sent[0]["error"]["code"], -32600);
+        server.handle(json!({"jsonrpc": "2.0", "method": "exit"}));
+        assert_eq!(server.exit_code(), Some(0));
+    }
+}
@@ -0,0 +1,91 @@
+use serde_json::{json, Value};
+
+use crate::document::Document;
+
+/// `SymbolKind.String`, the kind Markdown language servers use for headings.
+const HEADING_KIND: u8 = 15;
+
+struct Symbol {
+    level: u8,
+    value: Value,
+    children: Vec<Symbol>,
+}
+
+impl Symbol {
+    fn into_value(self) -> Value {
+        let mut value = self.value;
+        value["children"] = self.children.into_iter().map(Symbol::into_value).collect();
+        value
+    }
+}
+
+/// Returns `DocumentSymbol` hierarchy of headings, where a heading contains the deeper headings
+/// that follow it.
+pub fn document_symbols(document: &Document) -> Value {
+    let lines = document.lines();
+    let mut roots: Vec<Symbol> = vec![];
+    for (heading, span) in document.headings() {
+        let range = lines.range(&span.span.range);
+        let symbol = Symbol {
+            level: heading.level,
+            value: json!({
+                "name": heading.text(),
+                "detail": "#".repeat(heading.level as usize),
+                "kind": HEADING_KIND,
+                "range": range,
+                "selectionRange": range,
+            }),
+            children: vec![],
+        };
+        let mut siblings = &mut roots;
+        while siblings
+            .last()
+            .is_some_and(|last| last.level < symbol.level)
+        {
+            siblings = &mut siblings.last_mut().expect("checked above").children;
+        }
+        siblings.push(symbol);
+    }
+    roots.into_iter().map(Symbol::into_value).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::document_symbols;
+    use crate::document::Document;
+
+    fn outline(value: &serde_json::Value) -> Vec<String> {
+        value
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|symbol| {
+                let children = outline(&symbol["children"]);
+                if children.is_empty() {
+                    symbol["name"].as_str().unwrap().to_string()
+                } else {
+                    format!("{} {:?}", symbol["name"].as_str().unwrap(), children)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn nested_by_level() {
+        let document = Document::new("# a\n\n## b\n\n### c\n\n## d\n\n# e\n\n### f".to_string());
+        assert_eq!(
+            outline(&document_symbols(&document)),
+            vec![r#"a ["b [\"c\"]", "d"]"#, r#"e ["f"]"#]
+        );
+    }
+
+    #[test]
+    fn range() {
+        let document = Document::new("text\n\n## [b](c)".to_string());
+        let symbols = document_symbols(&document);
+        assert_eq!(symbols[0]["name"], "b");
+        assert_eq!(symbols[0]["detail"], "##");
+        assert_eq!(symbols[0]["range"]["start"]["line"], 2);
+        assert_eq!(symbols[0]["range"]["end"]["character"], 9);
+    }
+}
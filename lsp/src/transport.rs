@@ -0,0 +1,72 @@
+use std::io::{self, BufRead, Write};
+
+use serde_json::Value;
+
+/// Reads one message framed with `Content-Length` header. Returns `None` at the end of input.
+pub fn read(input: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            if length.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = Some(value.trim().parse::<usize>().map_err(|e| {
+                    io::Error::new(io::ErrorKind::InvalidData, format!("Content-Length: {e}"))
+                })?);
+            }
+        }
+    }
+    let mut body = vec![0; length.unwrap_or_default()];
+    input.read_exact(&mut body)?;
+    Ok(Some(serde_json::from_slice(&body).unwrap_or(Value::Null)))
+}
+
+/// Writes `message` framed with `Content-Length` header.
+pub fn write(output: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    output.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{read, write};
+
+    #[test]
+    fn round_trip() {
+        let mut buffer = vec![];
+        write(&mut buffer, &json!({"id": 1, "method": "🤔"})).unwrap();
+        write(&mut buffer, &json!({"id": 2})).unwrap();
+        let mut input = buffer.as_slice();
+        assert_eq!(
+            read(&mut input).unwrap(),
+            Some(json!({"id": 1, "method": "🤔"}))
+        );
+        assert_eq!(read(&mut input).unwrap(), Some(json!({"id": 2})));
+        assert_eq!(read(&mut input).unwrap(), None);
+    }
+
+    #[test]
+    fn extra_headers() {
+        let mut input =
+            "Content-Type: application/vscode-jsonrpc\r\ncontent-length: 2\r\n\r\n{}".as_bytes();
+        assert_eq!(read(&mut input).unwrap(), Some(json!({})));
+    }
+
+    #[test]
+    fn invalid_json_is_null() {
+        let mut input = "Content-Length: 1\r\n\r\n{".as_bytes();
+        assert_eq!(read(&mut input).unwrap(), Some(serde_json::Value::Null));
+    }
+}
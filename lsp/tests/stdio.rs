@@ -0,0 +1,140 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use serde_json::{json, Value};
+
+fn frame(message: Value) -> String {
+    let body = message.to_string();
+    format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+}
+
+fn unframe(mut output: &str) -> Vec<Value> {
+    let mut messages = vec![];
+    while let Some((headers, rest)) = output.split_once("\r\n\r\n") {
+        let length: usize = headers
+            .strip_prefix("Content-Length: ")
+            .unwrap()
+            .parse()
+            .unwrap();
+        messages.push(serde_json::from_str(&rest[..length]).unwrap());
+        output = &rest[length..];
+    }
+    messages
+}
+
+fn run(messages: Vec<Value>) -> (Vec<Value>, Option<i32>) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_yamd-lsp"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let input: String = messages.into_iter().map(frame).collect();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    (
+        unframe(&String::from_utf8(output.stdout).unwrap()),
+        output.status.code(),
+    )
+}
+
+fn request(id: u64, method: &str, params: Value) -> Value {
+    json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params})
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({"jsonrpc": "2.0", "method": method, "params": params})
+}
+
+#[test]
+fn session() {
+    let uri = "file:///doc.yamd";
+    let document = json!({"uri": uri});
+    let (messages, code) = run(vec![
+        request(1, "initialize", json!({"capabilities": {}})),
+        notification("initialized", json!({})),
+        notification(
+            "textDocument/didOpen",
+            json!({"textDocument": {
+                "uri": uri,
+                "languageId": "yamd",
+                "version": 1,
+                "text": "# Intro\n\n### Deep\n\n{% more\n- see [intro](#intro)\n- b\n%}"
+            }}),
+        ),
+        request(
+            2,
+            "textDocument/documentSymbol",
+            json!({"textDocument": document}),
+        ),
+        request(
+            3,
+            "textDocument/foldingRange",
+            json!({"textDocument": document}),
+        ),
+        request(
+            4,
+            "textDocument/definition",
+            json!({"textDocument": document, "position": {"line": 5, "character": 8}}),
+        ),
+        request(5, "shutdown", Value::Null),
+        notification("exit", Value::Null),
+    ]);
+    assert_eq!(code, Some(0));
+    assert_eq!(messages.len(), 6);
+
+    assert_eq!(messages[0]["id"], 1);
+    assert_eq!(
+        messages[0]["result"]["capabilities"],
+        json!({
+            "textDocumentSync": 1,
+            "documentSymbolProvider": true,
+            "foldingRangeProvider": true,
+            "definitionProvider": true
+        })
+    );
+
+    assert_eq!(messages[1]["method"], "textDocument/publishDiagnostics");
+    let diagnostics = messages[1]["params"]["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["code"], "heading-level-skip");
+    assert_eq!(
+        diagnostics[0]["range"]["start"],
+        json!({"line": 2, "character": 0})
+    );
+
+    assert_eq!(messages[2]["id"], 2);
+    assert_eq!(messages[2]["result"][0]["name"], "Intro");
+    assert_eq!(messages[2]["result"][0]["children"][0]["name"], "Deep");
+
+    assert_eq!(messages[3]["id"], 3);
+    assert_eq!(
+        messages[3]["result"],
+        json!([{"startLine": 4, "endLine": 7}, {"startLine": 5, "endLine": 6}])
+    );
+
+    assert_eq!(messages[4]["id"], 4);
+    assert_eq!(messages[4]["result"]["uri"], uri);
+    assert_eq!(
+        messages[4]["result"]["range"]["start"],
+        json!({"line": 0, "character": 0})
+    );
+
+    assert_eq!(
+        messages[5],
+        json!({"jsonrpc": "2.0", "id": 5, "result": null})
+    );
+}
+
+#[test]
+fn exit_without_shutdown() {
+    let (messages, code) = run(vec![notification("exit", Value::Null)]);
+    assert_eq!(messages, Vec::<Value>::new());
+    assert_eq!(code, Some(1));
+}
@@ -0,0 +1,824 @@
+use crate::nodes::{
+    Anchor, Bold, BoldNodes, Code, CodeSpan, Collapsible, Custom, Embed, FootnoteDefinition,
+    Heading, HeadingNodes, Highlight, Image, List, ListTypes, Paragraph, ParagraphNodes, Table,
+    TableCell, Yamd, YamdNodes,
+};
+
+/// How [Embed] is written, CommonMark has no equivalent.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum EmbedFallback {
+    /// `[kind](url)`, where url is the first positional [argument](Embed::arguments).
+    #[default]
+    Link,
+    /// `<iframe class="kind" src="url"></iframe>`, same as the HTML renderer.
+    Iframe,
+    /// Embed is omitted.
+    Drop,
+    /// Embed is written as returned string.
+    Custom(fn(&Embed) -> String),
+}
+
+/// Options of [to_commonmark_with].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExportOptions {
+    embed: EmbedFallback,
+}
+
+impl ExportOptions {
+    /// Sets how [Embed] is written.
+    pub fn embed(mut self, embed: EmbedFallback) -> Self {
+        self.embed = embed;
+        self
+    }
+}
+
+/// Serializes [Yamd] to GitHub flavoured Markdown with [default](ExportOptions::default) options.
+///
+/// | YAMD                             | CommonMark                                       |
+/// |----------------------------------|--------------------------------------------------|
+/// | [List] of [ListTypes::Ordered]   | `1.` list                                        |
+/// | [Highlight]                      | alert (`> [!NOTE]`) if icon is an alert type, otherwise blockquote without icon; title in bold |
+/// | [Collapsible]                    | `<details>` with `<summary>`                     |
+/// | [Italic](crate::nodes::Italic)   | `_italic_`, or `*italic*` inside of a word       |
+/// | [Emphasis](crate::nodes::Emphasis) | `*emphasis*`                                   |
+/// | [Embed]                          | see [EmbedFallback]                              |
+/// | [Custom]                         | `<div class="name">body</div>`, or the [nodes](Custom::nodes) inside of the `<div>` |
+///
+/// Text is escaped following CommonMark rules, which depend on the position in the line and the
+/// surrounding characters, so the output is read back as the same text.
+///
+/// # Example
+/// ```
+/// use yamd::{commonmark::to_commonmark, deserialize};
+///
+/// let yamd = deserialize("!! Heads up\n! warning\nsnake_case *and* 1. not a list\n!!\n\n+ one\n+ two");
+/// assert_eq!(
+///     to_commonmark(&yamd),
+///     "> [!WARNING]\n> **Heads up**\n>\n> snake_case *and* 1. not a list\n\n1. one\n2. two\n"
+/// );
+/// ```
+pub fn to_commonmark(yamd: &Yamd) -> String {
+    to_commonmark_with(yamd, &ExportOptions::default())
+}
+
+/// Serializes [Yamd] to GitHub flavoured Markdown. See [to_commonmark] for the mapping.
+///
+/// # Example
+/// ```
+/// use yamd::{
+///     commonmark::{EmbedFallback, ExportOptions, to_commonmark_with},
+///     deserialize,
+/// };
+///
+/// let yamd = deserialize("{{youtube|https://youtu.be/dQw4w9WgXcQ}}");
+/// let options = ExportOptions::default()
+///     .embed(EmbedFallback::Custom(|embed| format!("<{}>", embed.args)));
+/// assert_eq!(
+///     to_commonmark_with(&yamd, &options),
+///     "<https://youtu.be/dQw4w9WgXcQ>\n"
+/// );
+/// ```
+pub fn to_commonmark_with(yamd: &Yamd, options: &ExportOptions) -> String {
+    let mut blocks = vec![];
+    if let Some(metadata) = &yamd.metadata {
+        let delimiter = yamd.metadata_delimiter;
+        blocks.push(format!("{delimiter}\n{metadata}\n{delimiter}"));
+    }
+    blocks.extend(
+        yamd.body
+            .iter()
+            .map(|node| block(node, options))
+            .filter(|block| !block.is_empty()),
+    );
+    let mut out = blocks.join("\n\n");
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out
+}
+
+fn block(node: &YamdNodes, options: &ExportOptions) -> String {
+    let mut out = String::new();
+    match node {
+        YamdNodes::Paragraph(paragraph) => {
+            paragraph_nodes(&paragraph.body, &mut out);
+            escape_footnote_definition(&mut out);
+        }
+        YamdNodes::Heading(heading) => write_heading(heading, &mut out),
+        YamdNodes::Image(image) => write_image(image, &mut out),
+        YamdNodes::Images(images) => {
+            for (i, image) in images.body.iter().enumerate() {
+                if i > 0 {
+                    out.push('\n');
+                }
+                write_image(image, &mut out);
+            }
+        }
+        YamdNodes::Code(code) => write_code(code, &mut out),
+        YamdNodes::List(list) => write_list(list, &mut out),
+        YamdNodes::Highlight(highlight) => write_highlight(highlight, &mut out),
+        YamdNodes::ThematicBreak(_) => out.push_str("---"),
+        YamdNodes::Embed(embed) => write_embed(embed, options, &mut out),
+        YamdNodes::Custom(custom) => write_custom(custom, options, &mut out),
+        YamdNodes::Collapsible(collapsible) => write_collapsible(collapsible, options, &mut out),
+        YamdNodes::Table(table) => write_table(table, &mut out),
+        YamdNodes::FootnoteDefinition(definition) => {
+            write_footnote_definition(definition, &mut out)
+        }
+        YamdNodes::DisplayMath(math) => {
+            out.push_str("$$\n");
+            out.push_str(&math.body);
+            out.push_str("\n$$");
+        }
+    }
+    out
+}
+
+fn write_footnote_definition(definition: &FootnoteDefinition, out: &mut String) {
+    out.push_str("[^");
+    out.push_str(&definition.label);
+    out.push_str("]: ");
+    paragraph_nodes(&definition.body, out);
+}
+
+/// A paragraph that starts with a footnote reference followed by `:` is a footnote definition.
+fn escape_footnote_definition(out: &mut String) {
+    let colon = out
+        .strip_prefix("[^")
+        .and_then(|rest| rest.find(']'))
+        .map(|bracket| bracket + 3);
+    if let Some(colon) = colon.filter(|colon| out[*colon..].starts_with(':')) {
+        out.insert(colon, '\\');
+    }
+}
+
+fn write_heading(heading: &Heading, out: &mut String) {
+    out.push_str(&"#".repeat(heading.level as usize));
+    out.push(' ');
+    for node in &heading.body {
+        match node {
+            // ATX heading is a single line, and `#` may close it.
+            HeadingNodes::Text(text) => {
+                for (i, part) in text.replace('\n', " ").split('#').enumerate() {
+                    if i > 0 {
+                        out.push_str("\\#");
+                    }
+                    escape(part, out);
+                }
+            }
+            HeadingNodes::Anchor(anchor) => write_anchor(anchor, out),
+            HeadingNodes::InlineMath(math) => write_inline_math(&math.0.replace('\n', " "), out),
+        }
+    }
+}
+
+fn write_image(image: &Image, out: &mut String) {
+    out.push_str("![");
+    escape(&image.alt, out);
+    out.push_str("](");
+    destination(&image.src, out);
+    out.push(')');
+}
+
+fn write_code(code: &Code, out: &mut String) {
+    let fence_char = if code.lang.contains('`') { '~' } else { '`' };
+    let fence = fence_char
+        .to_string()
+        .repeat(longest_run(&code.code, fence_char).max(2) + 1);
+    out.push_str(&fence);
+    out.push_str(&code.lang.replace('\n', " "));
+    out.push('\n');
+    out.push_str(&code.code);
+    out.push('\n');
+    out.push_str(&fence);
+}
+
+fn write_list(list: &List, out: &mut String) {
+    for (i, item) in list.body.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        let marker = match list.list_type {
+            ListTypes::Unordered => "-".to_string(),
+            ListTypes::Ordered => format!("{}.", i + 1),
+        };
+        let indent = " ".repeat(marker.len() + 1);
+        let mut text = String::new();
+        paragraph_nodes(&item.text, &mut text);
+        out.push_str(&marker);
+        match item.checked {
+            Some(true) => out.push_str(" [x]"),
+            Some(false) => out.push_str(" [ ]"),
+            None => {}
+        }
+        if !text.is_empty() {
+            out.push(' ');
+            out.push_str(&text.replace('\n', &format!("\n{indent}")));
+        }
+        if let Some(nested_list) = &item.nested_list {
+            let mut nested = String::new();
+            write_list(nested_list, &mut nested);
+            for line in nested.lines() {
+                out.push('\n');
+                out.push_str(&indent);
+                out.push_str(line);
+            }
+        }
+    }
+}
+
+fn write_highlight(highlight: &Highlight, out: &mut String) {
+    let mut paragraphs = vec![];
+    let alert = highlight
+        .icon
+        .as_deref()
+        .map(str::to_ascii_uppercase)
+        .filter(|icon| ["NOTE", "TIP", "IMPORTANT", "WARNING", "CAUTION"].contains(&icon.as_str()));
+    let mut header = vec![];
+    if let Some(alert) = alert {
+        header.push(format!("[!{alert}]"));
+    }
+    if let Some(title) = &highlight.title {
+        let mut bold = String::new();
+        write_bold(&Bold::new(vec![BoldNodes::Text(title.clone())]), &mut bold);
+        header.push(bold);
+    }
+    if !header.is_empty() {
+        paragraphs.push(header.join("\n"));
+    }
+    paragraphs.extend(highlight.body.iter().map(|paragraph: &Paragraph| {
+        let mut text = String::new();
+        paragraph_nodes(&paragraph.body, &mut text);
+        text
+    }));
+    let quoted = paragraphs.join("\n\n");
+    for (i, line) in quoted.split('\n').enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        out.push('>');
+        if !line.is_empty() {
+            out.push(' ');
+            out.push_str(line);
+        }
+    }
+}
+
+fn write_embed(embed: &Embed, options: &ExportOptions, out: &mut String) {
+    let args = embed.arguments();
+    let url = args.positional.first().map_or("", |url| url.as_ref());
+    match options.embed {
+        EmbedFallback::Link => write_anchor(&Anchor::new(embed.kind.as_str(), url), out),
+        EmbedFallback::Iframe => {
+            out.push_str("<iframe class=\"");
+            escape_html(&embed.kind, out);
+            out.push_str("\" src=\"");
+            escape_html(url, out);
+            out.push_str("\"></iframe>");
+        }
+        EmbedFallback::Drop => {}
+        EmbedFallback::Custom(write) => out.push_str(&write(embed)),
+    }
+}
+
+fn write_custom(custom: &Custom, options: &ExportOptions, out: &mut String) {
+    out.push_str("<div class=\"");
+    escape_html(&custom.name, out);
+    out.push_str("\">");
+    if custom.nodes.is_empty() {
+        escape_html(&custom.body, out);
+    } else {
+        // blank lines end the HTML block, so the nodes are read as Markdown
+        out.push_str("\n\n");
+        out.push_str(&to_commonmark_with(
+            &Yamd::new(None, custom.nodes.clone()),
+            options,
+        ));
+        out.push('\n');
+    }
+    out.push_str("</div>");
+}
+
+fn write_collapsible(collapsible: &Collapsible, options: &ExportOptions, out: &mut String) {
+    out.push_str("<details>\n<summary>");
+    escape_html(&collapsible.title, out);
+    out.push_str("</summary>\n");
+    let body = to_commonmark_with(&Yamd::new(None, collapsible.body.clone()), options);
+    if !body.is_empty() {
+        out.push('\n');
+        out.push_str(&body);
+        out.push('\n');
+    }
+    out.push_str("</details>");
+}
+
+fn write_table(table: &Table, out: &mut String) {
+    write_row(&table.header.cells, out);
+    out.push_str("\n|");
+    for column in 0..table.header.cells.len() {
+        out.push(' ');
+        out.push_str(&table.alignment(column).to_string());
+        out.push_str(" |");
+    }
+    for row in &table.body {
+        out.push('\n');
+        write_row(&row.cells, out);
+    }
+}
+
+/// Writes a table row. A row is a single line, and `|` separates cells even inside of code spans.
+fn write_row(cells: &[TableCell], out: &mut String) {
+    out.push('|');
+    for cell in cells {
+        let mut body = String::new();
+        paragraph_nodes(&cell.body, &mut body);
+        out.push(' ');
+        let mut backslashes = 0;
+        for c in body.chars() {
+            match c {
+                '|' if backslashes % 2 == 0 => out.push_str("\\|"),
+                '\n' => out.push(' '),
+                c => out.push(c),
+            }
+            backslashes = if c == '\\' { backslashes + 1 } else { 0 };
+        }
+        out.push_str(" |");
+    }
+}
+
+fn paragraph_nodes(nodes: &[ParagraphNodes], out: &mut String) {
+    for (i, node) in nodes.iter().enumerate() {
+        match node {
+            ParagraphNodes::Anchor(anchor) => write_anchor(anchor, out),
+            ParagraphNodes::Bold(bold) => write_bold(bold, out),
+            ParagraphNodes::Italic(italic) => {
+                // `_` does not open or close emphasis inside of a word.
+                let next = nodes.get(i + 1).and_then(first_char);
+                let intraword = out.chars().next_back().is_some_and(char::is_alphanumeric)
+                    || next.is_some_and(char::is_alphanumeric);
+                wrap(&italic.0, if intraword { "*" } else { "_" }, out);
+            }
+            ParagraphNodes::Strikethrough(strikethrough) => wrap(&strikethrough.0, "~~", out),
+            ParagraphNodes::Text(text) => escape(text, out),
+            ParagraphNodes::CodeSpan(code_span) => write_code_span(code_span, out),
+            ParagraphNodes::Emphasis(emphasis) => wrap(&emphasis.0, "*", out),
+            ParagraphNodes::FootnoteReference(reference) => {
+                out.push_str("[^");
+                out.push_str(&reference.label);
+                out.push(']');
+            }
+            ParagraphNodes::InlineMath(math) => write_inline_math(&math.0, out),
+        }
+    }
+}
+
+fn first_char(node: &ParagraphNodes) -> Option<char> {
+    match node {
+        ParagraphNodes::Text(text) => text.chars().next(),
+        _ => None,
+    }
+}
+
+fn write_anchor(anchor: &Anchor, out: &mut String) {
+    out.push('[');
+    if anchor.text.starts_with('^') {
+        out.push('\\');
+    }
+    escape(&anchor.text, out);
+    out.push_str("](");
+    destination(&anchor.url, out);
+    out.push(')');
+}
+
+fn write_bold(bold: &Bold, out: &mut String) {
+    let mut body = String::new();
+    for node in &bold.body {
+        match node {
+            BoldNodes::Italic(italic) => wrap(&italic.0, "*", &mut body),
+            BoldNodes::Strikethrough(strikethrough) => wrap(&strikethrough.0, "~~", &mut body),
+            BoldNodes::Text(text) => escape_inline(text, &mut body),
+        }
+    }
+    wrap_escaped(&body, "**", out);
+}
+
+fn write_code_span(code_span: &CodeSpan, out: &mut String) {
+    let fence = "`".repeat(longest_run(&code_span.0, '`') + 1);
+    let pad = code_span.0.starts_with('`')
+        || code_span.0.ends_with('`')
+        || (code_span.0.starts_with(' ')
+            && code_span.0.ends_with(' ')
+            && !code_span.0.trim().is_empty());
+    out.push_str(&fence);
+    if pad {
+        out.push(' ');
+    }
+    out.push_str(&code_span.0);
+    if pad {
+        out.push(' ');
+    }
+    out.push_str(&fence);
+}
+
+/// Math body is verbatim, as in YAMD.
+fn write_inline_math(body: &str, out: &mut String) {
+    out.push('$');
+    out.push_str(body);
+    out.push('$');
+}
+
+/// Wraps text in delimiter run `marker`.
+fn wrap(text: &str, marker: &str, out: &mut String) {
+    let mut escaped = String::new();
+    escape_inline(text, &mut escaped);
+    wrap_escaped(&escaped, marker, out);
+}
+
+/// Delimiter run followed or preceded by whitespace does not open or close emphasis, so
+/// surrounding whitespace is moved outside of it.
+fn wrap_escaped(escaped: &str, marker: &str, out: &mut String) {
+    let trimmed = escaped.trim_matches([' ', '\t']);
+    if trimmed.is_empty() {
+        out.push_str(escaped);
+        return;
+    }
+    let leading = &escaped[..escaped.len() - escaped.trim_start_matches([' ', '\t']).len()];
+    let trailing = &escaped[escaped.trim_end_matches([' ', '\t']).len()..];
+    out.push_str(leading);
+    out.push_str(marker);
+    out.push_str(trimmed);
+    out.push_str(marker);
+    out.push_str(trailing);
+}
+
+fn destination(url: &str, out: &mut String) {
+    let needs_brackets = url.is_empty()
+        || url
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '(' | ')' | '<' | '>'));
+    if needs_brackets {
+        out.push('<');
+        for c in url.chars() {
+            match c {
+                '<' | '>' | '\\' => {
+                    out.push('\\');
+                    out.push(c);
+                }
+                '\n' => out.push_str("%0A"),
+                c => out.push(c),
+            }
+        }
+        out.push('>');
+    } else {
+        out.push_str(&url.replace('\\', "\\\\"));
+    }
+}
+
+/// Escapes text of an inline node written to a separate buffer, which never starts a line.
+fn escape_inline(text: &str, out: &mut String) {
+    escape_with(text, false, out);
+}
+
+/// Escapes text so CommonMark reads it back as the same text. Whether a character needs escaping
+/// depends on what precedes and follows it, including the position in the line, so `out` must
+/// contain everything written before `text` in the same block.
+fn escape(text: &str, out: &mut String) {
+    escape_with(text, out.is_empty() || out.ends_with('\n'), out);
+}
+
+fn escape_with(text: &str, mut line_start: bool, out: &mut String) {
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        if line_start {
+            line_start = false;
+            let written = escape_line_start(&chars[i..], out);
+            if written > 0 {
+                i += written;
+                continue;
+            }
+        }
+        let c = chars[i];
+        let previous = out.chars().next_back();
+        let next = chars.get(i + 1).copied();
+        match c {
+            '\n' => {
+                // Two trailing spaces make a hard break.
+                while out.ends_with(' ') {
+                    out.pop();
+                }
+                // A blank line would end the paragraph.
+                if out.is_empty() || out.ends_with('\n') {
+                    out.push('\\');
+                }
+                out.push('\n');
+                line_start = true;
+            }
+            '*' | '`' | '[' | ']' | '<' | '~' | '$' => {
+                out.push('\\');
+                out.push(c);
+            }
+            // `_` inside of a word does not open or close emphasis.
+            '_' if !(previous.is_some_and(char::is_alphanumeric)
+                && next.is_some_and(char::is_alphanumeric)) =>
+            {
+                out.push_str("\\_");
+            }
+            // Backslash escapes only punctuation and line endings.
+            '\\' if next.is_none_or(|n| n.is_ascii_punctuation() || n == '\n') => {
+                out.push_str("\\\\");
+            }
+            '&' if next.is_some_and(|n| n.is_ascii_alphanumeric() || n == '#') => {
+                out.push_str("\\&");
+            }
+            c => out.push(c),
+        }
+        i += 1;
+    }
+}
+
+/// Escapes block markers at the start of a line. Returns the number of characters written.
+fn escape_line_start(line: &[char], out: &mut String) -> usize {
+    match line[0] {
+        // Leading whitespace is not content in CommonMark, and four spaces start code.
+        ' ' => {
+            out.push_str("&#32;");
+            1
+        }
+        '\t' => {
+            out.push_str("&#9;");
+            1
+        }
+        // Headings, blockquotes, list items, thematic breaks, setext underlines and table
+        // delimiter rows.
+        c @ ('#' | '>' | '-' | '+' | '=' | '|' | ':') => {
+            out.push('\\');
+            out.push(c);
+            1
+        }
+        // Ordered list items.
+        '0'..='9' => {
+            let digits = line.iter().take_while(|c| c.is_ascii_digit()).count();
+            match line.get(digits) {
+                Some(delimiter @ ('.' | ')')) => {
+                    out.extend(&line[..digits]);
+                    out.push('\\');
+                    out.push(*delimiter);
+                    digits + 1
+                }
+                _ => 0,
+            }
+        }
+        _ => 0,
+    }
+}
+
+fn escape_html(text: &str, out: &mut String) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+}
+
+fn longest_run(text: &str, c: char) -> usize {
+    text.split(|other| other != c)
+        .map(|run| run.chars().count())
+        .max()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        commonmark::{
+            EmbedFallback, ExportOptions, from_commonmark, to_commonmark, to_commonmark_with,
+        },
+        deserialize,
+        nodes::{
+            Bold, BoldNodes, Code, CodeSpan, Custom, Emphasis, Heading, Italic, Paragraph,
+            ParagraphNodes, Strikethrough, Table, TableAlignment, TableCell, TableRow, Yamd,
+        },
+    };
+
+    fn paragraph(nodes: Vec<ParagraphNodes>) -> Yamd {
+        Yamd::new(None, vec![Paragraph::new(nodes).into()])
+    }
+
+    #[test]
+    fn blocks() {
+        let yamd = deserialize(
+            "---\ntitle: a\n---\n\n## h [a](b c)\n\n![a](b)\n![c](d)\n\n```rust\nlet a;\n```\n\n-----\n\n- a\n - b\n  + c\n  + d\n- e",
+        );
+        assert_eq!(
+            to_commonmark(&yamd),
+            "---\ntitle: a\n---\n\n## h [a](<b c>)\n\n![a](b)\n![c](d)\n\n```rust\nlet a;\n```\n\n---\n\n- a\n  - b\n    1. c\n    2. d\n- e\n"
+        );
+    }
+
+    #[test]
+    fn highlight() {
+        assert_eq!(
+            to_commonmark(&deserialize("!! title\n! lightbulb\na\nb\n\nc\n!!")),
+            "> **title**\n>\n> a\n> b\n>\n> c\n"
+        );
+        assert_eq!(
+            to_commonmark(&deserialize("!!\n! Note\na\n!!")),
+            "> [!NOTE]\n>\n> a\n"
+        );
+    }
+
+    #[test]
+    fn collapsible() {
+        assert_eq!(
+            to_commonmark(&deserialize("{% a <b>\n# c\n\n{% d\ne\n%}\n%}")),
+            "<details>\n<summary>a &lt;b&gt;</summary>\n\n# c\n\n<details>\n<summary>d</summary>\n\ne\n\n</details>\n\n</details>\n"
+        );
+    }
+
+    #[test]
+    fn custom() {
+        let custom = Custom::new("note", ":::", "\n# a\n", ":::");
+        let yamd = Yamd::new(None, vec![custom.clone().into()]);
+        assert_eq!(to_commonmark(&yamd), "<div class=\"note\">\n# a\n</div>\n");
+        let yamd = Yamd::new(
+            None,
+            vec![
+                custom
+                    .with_nodes(vec![Heading::new(1, vec!["a".to_string().into()]).into()])
+                    .into(),
+            ],
+        );
+        assert_eq!(
+            to_commonmark(&yamd),
+            "<div class=\"note\">\n\n# a\n\n</div>\n"
+        );
+    }
+
+    #[test]
+    fn embed() {
+        let yamd = deserialize("{{youtube|https://youtu.be/1|start=30}}\n\na");
+        assert_eq!(to_commonmark(&yamd), "[youtube](https://youtu.be/1)\n\na\n");
+        assert_eq!(
+            to_commonmark_with(
+                &yamd,
+                &ExportOptions::default().embed(EmbedFallback::Iframe)
+            ),
+            "<iframe class=\"youtube\" src=\"https://youtu.be/1\"></iframe>\n\na\n"
+        );
+        assert_eq!(
+            to_commonmark_with(&yamd, &ExportOptions::default().embed(EmbedFallback::Drop)),
+            "a\n"
+        );
+    }
+
+    #[test]
+    fn emphasis_markers() {
+        let yamd = paragraph(vec![
+            String::from("a").into(),
+            Italic::new("b").into(),
+            String::from(" ").into(),
+            Italic::new(" c ").into(),
+            String::from(" ").into(),
+            Emphasis::new("d").into(),
+            Bold::new(vec![
+                BoldNodes::Text(" e ".to_string()),
+                Italic::new("f").into(),
+            ])
+            .into(),
+            Strikethrough::new("g").into(),
+        ]);
+        assert_eq!(to_commonmark(&yamd), "a*b*  _c_  *d* **e *f***~~g~~\n");
+    }
+
+    #[test]
+    fn code() {
+        assert_eq!(
+            to_commonmark(&Yamd::new(
+                None,
+                vec![
+                    Code::new("", "````\n```").into(),
+                    Code::new("a`b", "~").into()
+                ]
+            )),
+            "`````\n````\n```\n`````\n\n~~~a`b\n~\n~~~\n"
+        );
+        assert_eq!(
+            to_commonmark(&paragraph(vec![
+                CodeSpan::new("a``b").into(),
+                CodeSpan::new("`c").into()
+            ])),
+            "```a``b````` `c ``\n"
+        );
+    }
+
+    #[test]
+    fn task_list() {
+        let yamd = deserialize("- [x] a\n - [ ] b\n- \\[x] c");
+        let markdown = to_commonmark(&yamd);
+        assert_eq!(markdown, "- [x] a\n  - [ ] b\n- \\[x\\] c\n");
+        assert_eq!(from_commonmark(&markdown).0, yamd);
+    }
+
+    #[test]
+    fn footnotes() {
+        let yamd = deserialize("[^1]\\: a [\\^b](c)\n\n[^1]: **d**");
+        let markdown = to_commonmark(&yamd);
+        assert_eq!(markdown, "[^1]\\: a [\\^b](c)\n\n[^1]: **d**\n");
+        assert_eq!(from_commonmark(&markdown).0, yamd);
+    }
+
+    #[test]
+    fn table() {
+        let yamd = deserialize("| a | b\\|c |\n| --: |\n| `d\\|e` |");
+        let markdown = to_commonmark(&yamd);
+        assert_eq!(markdown, "| a | b\\|c |\n| --: | --- |\n| `d\\|e` |\n");
+        assert_eq!(
+            from_commonmark(&markdown).0,
+            Yamd::new(
+                None,
+                vec![
+                    Table::new(
+                        TableRow::new(vec![
+                            TableCell::new(vec![String::from("a").into()]),
+                            TableCell::new(vec![String::from("b|c").into()]),
+                        ]),
+                        vec![TableAlignment::Right, TableAlignment::None],
+                        vec![TableRow::new(vec![
+                            TableCell::new(vec![CodeSpan::new("d|e").into()]),
+                            TableCell::new(vec![]),
+                        ])],
+                    )
+                    .into()
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn text_round_trips() {
+        for text in [
+            "# not a heading",
+            "- not a list",
+            "+ not a list",
+            "10. not a list",
+            "3) not a list",
+            "> not a quote",
+            "    not code",
+            "a\n---",
+            "a\n===",
+            "| a |\n|---|",
+            "snake_case and _emphasis_ and *strong* and ~~strike~~",
+            "`code` [link](url) ![image](src) <b>html</b> <https://autolink>",
+            "&amp; &#35; a & b",
+            "back\\slash \\* and trailing \\",
+            "a  \nb",
+            "1 + 1 = 2: a | b - c",
+            "$5 and $a$",
+        ] {
+            let yamd = paragraph(vec![String::from(text).into()]);
+            let markdown = to_commonmark(&yamd);
+            let (converted, approximations) = from_commonmark(&markdown);
+            assert_eq!(approximations, vec![], "{text:?} -> {markdown:?}");
+            let expected = text.replace("  \n", "\n");
+            assert_eq!(
+                converted,
+                paragraph(vec![expected.into()]),
+                "{text:?} -> {markdown:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn heading_round_trips() {
+        let yamd = deserialize("## a \\# b \\#");
+        let markdown = to_commonmark(&yamd);
+        assert_eq!(markdown, "## a \\# b \\#\n");
+        assert_eq!(from_commonmark(&markdown).0, yamd);
+    }
+
+    #[test]
+    fn math_round_trips() {
+        let yamd = deserialize("# $a$\n\n$\\frac{1}{2}$ b\n\n$$\nx \\\\\ny\n$$");
+        let markdown = to_commonmark(&yamd);
+        assert_eq!(markdown, "# $a$\n\n$\\frac{1}{2}$ b\n\n$$\nx \\\\\ny\n$$\n");
+        assert_eq!(from_commonmark(&markdown), (yamd, vec![]));
+    }
+
+    #[test]
+    fn metadata_round_trips() {
+        for input in ["---\ntitle: a\n---\n\nb", "+++\ntitle = 'a'\n+++\n\nb"] {
+            let yamd = deserialize(input);
+            let markdown = to_commonmark(&yamd);
+            assert_eq!(markdown, format!("{input}\n"));
+            assert_eq!(from_commonmark(&markdown), (yamd, vec![]));
+        }
+    }
+}
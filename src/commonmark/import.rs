@@ -0,0 +1,1100 @@
+use std::ops::Range;
+
+use pulldown_cmark::{
+    Alignment, BlockQuoteKind, CodeBlockKind, Event, LinkType, MetadataBlockKind, OffsetIter,
+    Options, Parser, Tag, TagEnd,
+};
+
+use crate::{
+    commonmark::{Approximation, Construct},
+    nodes::{
+        Anchor, Bold, BoldNodes, Code, CodeSpan, DisplayMath, Emphasis, FootnoteDefinition,
+        FootnoteReference, Heading, HeadingNodes, Highlight, Image, Images, InlineMath, Italic,
+        List, ListItem, ListTypes, MetadataDelimiter, Paragraph, ParagraphNodes, Strikethrough,
+        Table, TableAlignment, TableCell, TableRow, ThematicBreak, Yamd, YamdNodes,
+        is_display_math, is_footnote_label, is_inline_math,
+    },
+};
+
+/// Converts CommonMark `input` to [Yamd]. Returns every construct that was approximated or dropped,
+/// in the order they were encountered.
+///
+/// # Example
+/// ```
+/// use yamd::commonmark::{Construct, from_commonmark};
+///
+/// let (yamd, approximations) = from_commonmark("see [docs][1]\n\n[1]: https://docs.rs");
+/// assert_eq!(yamd.to_string(), "see [docs](https://docs.rs)");
+/// assert_eq!(approximations[0].construct, Construct::ReferenceLink);
+/// assert_eq!(approximations[0].range, 4..13);
+/// ```
+pub fn from_commonmark(input: &str) -> (Yamd, Vec<Approximation>) {
+    let options = Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_GFM
+        | Options::ENABLE_MATH
+        | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS
+        | Options::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS;
+    let mut converter = Converter {
+        source: input,
+        events: Parser::new_ext(input, options).into_offset_iter(),
+        approximations: vec![],
+        task: None,
+    };
+    let yamd = converter.document();
+    (yamd, converter.approximations)
+}
+
+/// Inline content before it is placed into a node. Images and display math are blocks in YAMD, so
+/// whether they stay blocks depends on the rest of the paragraph.
+enum Inline {
+    Node(ParagraphNodes),
+    Image(Image),
+    DisplayMath(String),
+}
+
+struct Converter<'a> {
+    source: &'a str,
+    events: OffsetIter<'a>,
+    approximations: Vec<Approximation>,
+    /// Task list marker of the list item being converted.
+    task: Option<bool>,
+}
+
+impl Converter<'_> {
+    fn report(&mut self, construct: Construct, range: Range<usize>) {
+        self.approximations.push(Approximation { construct, range });
+    }
+
+    fn document(&mut self) -> Yamd {
+        let mut metadata = None;
+        let mut metadata_delimiter = MetadataDelimiter::default();
+        let mut body = vec![];
+        while let Some((event, range)) = self.events.next() {
+            if let Event::Start(Tag::MetadataBlock(kind)) = event {
+                if kind == MetadataBlockKind::PlusesStyle {
+                    metadata_delimiter = MetadataDelimiter::Pluses;
+                }
+                metadata = Some(self.text().trim_end_matches('\n').to_string());
+            } else {
+                self.block(event, range, &mut body);
+            }
+        }
+        Yamd {
+            metadata_delimiter,
+            ..Yamd::new(metadata, body)
+        }
+    }
+
+    /// Converts blocks until the end of the current container.
+    fn blocks(&mut self) -> Vec<YamdNodes> {
+        let mut out = vec![];
+        while let Some((event, range)) = self.events.next() {
+            if let Event::End(_) = event {
+                break;
+            }
+            self.block(event, range, &mut out);
+        }
+        out
+    }
+
+    fn block(&mut self, event: Event, range: Range<usize>, out: &mut Vec<YamdNodes>) {
+        match event {
+            Event::Start(Tag::Paragraph) => {
+                let inlines = self.inlines();
+                out.push(self.paragraph_or_block(inlines, range));
+            }
+            Event::Start(Tag::Heading { level, .. }) => {
+                if !self.source[range.clone()].trim_start().starts_with('#') {
+                    self.report(Construct::SetextHeading, range.clone());
+                }
+                let inlines = self.inlines();
+                let body = self.heading_nodes(inlines, range);
+                out.push(Heading::new(level as u8, body).into());
+            }
+            Event::Start(Tag::BlockQuote(kind)) => {
+                if kind.is_none() {
+                    self.report(Construct::BlockQuote, range.clone());
+                }
+                let blocks = self.blocks();
+                let body = self.paragraphs(blocks, range);
+                out.push(Highlight::new(None::<String>, kind.map(icon), body).into());
+            }
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let lang = match kind {
+                    CodeBlockKind::Fenced(lang) => lang.to_string(),
+                    CodeBlockKind::Indented => {
+                        self.report(Construct::IndentedCode, range);
+                        String::new()
+                    }
+                };
+                out.push(Code::new(lang, self.text()).into());
+            }
+            Event::Start(Tag::List(start)) => out.push(self.list(start, 0, range).into()),
+            Event::Start(Tag::Table(alignments)) => {
+                out.push(self.table(alignments, range).into());
+            }
+            Event::Start(Tag::FootnoteDefinition(label)) => {
+                let blocks = self.blocks();
+                if blocks.len() > 1 {
+                    self.report(Construct::BlockInContainer, range.clone());
+                }
+                let mut body = flatten(blocks).body;
+                if is_footnote_label(&label) {
+                    out.push(FootnoteDefinition::new(label.to_string(), body).into());
+                } else {
+                    self.report(Construct::Footnote, range);
+                    let prefix = format!("[^{label}]: ");
+                    match body.first_mut() {
+                        Some(ParagraphNodes::Text(text)) => text.insert_str(0, &prefix),
+                        _ => body.insert(0, prefix.into()),
+                    }
+                    out.push(Paragraph::new(body).into());
+                }
+            }
+            Event::Start(Tag::HtmlBlock) => {
+                self.report(Construct::Html, range);
+                self.skip();
+            }
+            Event::Start(_) => self.skip(),
+            Event::Rule => out.push(ThematicBreak::new().into()),
+            Event::Html(_) => self.report(Construct::Html, range),
+            Event::End(_) => {}
+            event => {
+                let mut inlines = vec![];
+                self.inline(event, range.clone(), &mut inlines);
+                out.push(self.paragraph_or_block(inlines, range));
+            }
+        }
+    }
+
+    fn list(&mut self, start: Option<u64>, level: usize, range: Range<usize>) -> List {
+        if start.is_some_and(|start| start != 1) {
+            self.report(Construct::OrderedListStart, range.clone());
+        }
+        let list_type = if start.is_some() {
+            ListTypes::Ordered
+        } else {
+            ListTypes::Unordered
+        };
+        let mut loose = false;
+        let mut body = vec![];
+        while let Some((event, range)) = self.events.next() {
+            match event {
+                Event::Start(Tag::Item) => body.push(self.list_item(level, range, &mut loose)),
+                Event::End(_) => break,
+                _ => {}
+            }
+        }
+        if loose {
+            self.report(Construct::LooseList, range);
+        }
+        List::new(list_type, level, body)
+    }
+
+    fn list_item(&mut self, level: usize, range: Range<usize>, loose: &mut bool) -> ListItem {
+        let mut inlines = vec![];
+        let mut nested_list: Option<List> = None;
+        let mut checked = None;
+        while let Some((event, event_range)) = self.events.next() {
+            match event {
+                Event::End(_) => break,
+                Event::Start(Tag::List(start)) => {
+                    checked = checked.or(self.task.take());
+                    let list = self.list(start, level + 1, event_range.clone());
+                    match &mut nested_list {
+                        Some(nested_list) => {
+                            self.report(Construct::BlockInContainer, event_range);
+                            nested_list.body.extend(list.body);
+                        }
+                        None => nested_list = Some(list),
+                    }
+                }
+                Event::Start(Tag::Paragraph) => {
+                    *loose = true;
+                    let paragraph = self.inlines();
+                    push_line_break(&mut inlines);
+                    extend(&mut inlines, paragraph);
+                }
+                event if is_inline(&event) => self.inline(event, event_range, &mut inlines),
+                event => {
+                    let mut blocks = vec![];
+                    self.block(event, event_range.clone(), &mut blocks);
+                    if blocks.is_empty() {
+                        continue;
+                    }
+                    self.report(Construct::BlockInContainer, event_range);
+                    for block in blocks {
+                        push_line_break(&mut inlines);
+                        extend(
+                            &mut inlines,
+                            flatten([block]).body.into_iter().map(Inline::Node),
+                        );
+                    }
+                }
+            }
+        }
+        let checked = checked.or(self.task.take());
+        ListItem {
+            checked,
+            text: self.paragraph_nodes(inlines, range),
+            nested_list,
+        }
+    }
+
+    fn table(&mut self, alignments: Vec<Alignment>, range: Range<usize>) -> Table {
+        let mut rows: Vec<TableRow> = vec![];
+        let mut cells: Vec<TableCell> = vec![];
+        while let Some((event, _)) = self.events.next() {
+            match event {
+                Event::Start(Tag::TableCell) => {
+                    let inlines = self.inlines();
+                    cells.push(TableCell::new(self.paragraph_nodes(inlines, range.clone())));
+                }
+                Event::End(TagEnd::TableHead | TagEnd::TableRow) => {
+                    rows.push(TableRow::new(std::mem::take(&mut cells)));
+                }
+                Event::End(TagEnd::Table) => break,
+                _ => {}
+            }
+        }
+        let mut rows = rows.into_iter();
+        let header = rows.next().unwrap_or_else(|| TableRow::new(vec![]));
+        let alignments = alignments.into_iter().map(alignment).collect();
+        Table::new(header, alignments, rows.collect())
+    }
+
+    /// Converts inline content until the end of the current container.
+    fn inlines(&mut self) -> Vec<Inline> {
+        let mut out = vec![];
+        while let Some((event, range)) = self.events.next() {
+            if let Event::End(_) = event {
+                break;
+            }
+            self.inline(event, range, &mut out);
+        }
+        out
+    }
+
+    fn inline(&mut self, event: Event, range: Range<usize>, out: &mut Vec<Inline>) {
+        match event {
+            Event::Text(text) => push_text(out, &text),
+            Event::InlineMath(math) if is_inline_math(&math) => {
+                out.push(Inline::Node(InlineMath::new(math.to_string()).into()));
+            }
+            Event::InlineMath(math) => {
+                self.report(Construct::Math, range);
+                push_text(out, &format!("${math}$"));
+            }
+            Event::DisplayMath(math) => {
+                out.push(Inline::DisplayMath(math.trim_matches('\n').to_string()));
+            }
+            Event::Code(code) => out.push(Inline::Node(CodeSpan::new(code.to_string()).into())),
+            Event::SoftBreak => push_text(out, "\n"),
+            Event::HardBreak => {
+                self.report(Construct::HardBreak, range);
+                push_text(out, "\n");
+            }
+            Event::Html(_) | Event::InlineHtml(_) => self.report(Construct::Html, range),
+            Event::FootnoteReference(label) if is_footnote_label(&label) => {
+                out.push(Inline::Node(
+                    FootnoteReference::new(label.to_string()).into(),
+                ));
+            }
+            Event::FootnoteReference(label) => {
+                self.report(Construct::Footnote, range);
+                push_text(out, &format!("[^{label}]"));
+            }
+            Event::TaskListMarker(checked) => self.task = Some(checked),
+            Event::Start(Tag::Emphasis) => {
+                let underscore = self.source[range.clone()].starts_with('_');
+                let children = self.inlines();
+                let text = self.plain(children, range);
+                out.push(Inline::Node(if underscore {
+                    Italic::new(text).into()
+                } else {
+                    Emphasis::new(text).into()
+                }));
+            }
+            Event::Start(Tag::Strong) => {
+                let children = self.inlines();
+                let body = self.bold_nodes(children, range);
+                out.push(Inline::Node(Bold::new(body).into()));
+            }
+            Event::Start(Tag::Strikethrough) => {
+                let children = self.inlines();
+                let text = self.plain(children, range);
+                out.push(Inline::Node(Strikethrough::new(text).into()));
+            }
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                ..
+            }) => {
+                self.link(link_type, &title, range.clone());
+                let url = match link_type {
+                    LinkType::Email => format!("mailto:{dest_url}"),
+                    _ => dest_url.to_string(),
+                };
+                let children = self.inlines();
+                let text = self.plain(children, range);
+                out.push(Inline::Node(Anchor::new(text, url).into()));
+            }
+            Event::Start(Tag::Image {
+                link_type,
+                dest_url,
+                title,
+                ..
+            }) => {
+                self.link(link_type, &title, range.clone());
+                let children = self.inlines();
+                let alt = self.plain(children, range);
+                out.push(Inline::Image(Image::new(alt, dest_url.to_string())));
+            }
+            Event::Start(_) => {
+                let children = self.inlines();
+                out.extend(children);
+            }
+            Event::End(_) | Event::Rule => {}
+        }
+    }
+
+    fn link(&mut self, link_type: LinkType, title: &str, range: Range<usize>) {
+        if matches!(
+            link_type,
+            LinkType::Reference
+                | LinkType::ReferenceUnknown
+                | LinkType::Collapsed
+                | LinkType::CollapsedUnknown
+                | LinkType::Shortcut
+                | LinkType::ShortcutUnknown
+        ) {
+            self.report(Construct::ReferenceLink, range.clone());
+        }
+        if !title.is_empty() {
+            self.report(Construct::LinkTitle, range);
+        }
+    }
+
+    /// Returns text of the current container, used for code and metadata.
+    fn text(&mut self) -> String {
+        let mut text = String::new();
+        for (event, _) in self.events.by_ref() {
+            match event {
+                Event::Text(t) => text.push_str(&t),
+                Event::End(_) => break,
+                _ => {}
+            }
+        }
+        text
+    }
+
+    /// Skips events until the end of the current container.
+    fn skip(&mut self) {
+        let mut depth = 0;
+        for (event, _) in self.events.by_ref() {
+            match event {
+                Event::Start(_) => depth += 1,
+                Event::End(_) if depth == 0 => break,
+                Event::End(_) => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+
+    /// Paragraph that consists of images only becomes [Image] or [Images], paragraph that consists
+    /// of display math only becomes [DisplayMath].
+    fn paragraph_or_block(&mut self, inlines: Vec<Inline>, range: Range<usize>) -> YamdNodes {
+        if let [Inline::DisplayMath(math)] = inlines.as_slice() {
+            if is_display_math(math) {
+                return DisplayMath::new(math.as_str()).into();
+            }
+        }
+        let images_only = inlines.iter().any(|i| matches!(i, Inline::Image(_)))
+            && inlines.iter().all(|i| match i {
+                Inline::Image(_) => true,
+                Inline::Node(ParagraphNodes::Text(text)) => text.trim().is_empty(),
+                Inline::Node(_) | Inline::DisplayMath(_) => false,
+            });
+        if !images_only {
+            return Paragraph::new(self.paragraph_nodes(inlines, range)).into();
+        }
+        let mut images: Vec<Image> = inlines
+            .into_iter()
+            .filter_map(|i| match i {
+                Inline::Image(image) => Some(image),
+                Inline::Node(_) | Inline::DisplayMath(_) => None,
+            })
+            .collect();
+        if images.len() == 1 {
+            images.remove(0).into()
+        } else {
+            Images::new(images).into()
+        }
+    }
+
+    fn paragraph_nodes(
+        &mut self,
+        inlines: Vec<Inline>,
+        range: Range<usize>,
+    ) -> Vec<ParagraphNodes> {
+        let mut reported = false;
+        let mut out: Vec<ParagraphNodes> = vec![];
+        for inline in inlines {
+            match inline {
+                Inline::Node(node) => out.push(node),
+                Inline::Image(image) => {
+                    if !reported {
+                        self.report(Construct::InlineImage, range.clone());
+                        reported = true;
+                    }
+                    out.push(Anchor::new(image.alt, image.src).into());
+                }
+                Inline::DisplayMath(math) => out.push(self.inline_math(math, range.clone())),
+            }
+        }
+        out
+    }
+
+    /// Display math inside of text becomes [InlineMath] if possible.
+    fn inline_math(&mut self, math: String, range: Range<usize>) -> ParagraphNodes {
+        self.report(Construct::Math, range);
+        if is_inline_math(&math) {
+            InlineMath::new(math).into()
+        } else {
+            format!("$${math}$$").into()
+        }
+    }
+
+    fn heading_nodes(&mut self, inlines: Vec<Inline>, range: Range<usize>) -> Vec<HeadingNodes> {
+        let mut flattened = false;
+        let mut out: Vec<HeadingNodes> = vec![];
+        for inline in inlines {
+            let node = match inline {
+                Inline::Node(ParagraphNodes::Anchor(anchor)) => HeadingNodes::Anchor(anchor),
+                Inline::Node(ParagraphNodes::Text(text)) => HeadingNodes::Text(text),
+                Inline::Node(ParagraphNodes::InlineMath(math)) => HeadingNodes::InlineMath(math),
+                Inline::DisplayMath(math) => match self.inline_math(math, range.clone()) {
+                    ParagraphNodes::InlineMath(math) => HeadingNodes::InlineMath(math),
+                    node => HeadingNodes::Text(node_text(&node)),
+                },
+                inline => {
+                    flattened = true;
+                    HeadingNodes::Text(inline_text(&inline))
+                }
+            };
+            match (out.last_mut(), node) {
+                (Some(HeadingNodes::Text(last)), HeadingNodes::Text(text)) => last.push_str(&text),
+                (_, node) => out.push(node),
+            }
+        }
+        if flattened {
+            self.report(Construct::NestedFormatting, range);
+        }
+        out
+    }
+
+    fn bold_nodes(&mut self, inlines: Vec<Inline>, range: Range<usize>) -> Vec<BoldNodes> {
+        let mut flattened = false;
+        let mut out: Vec<BoldNodes> = vec![];
+        for inline in inlines {
+            let node = match inline {
+                Inline::Node(ParagraphNodes::Text(text)) => BoldNodes::Text(text),
+                Inline::Node(ParagraphNodes::Italic(italic)) => BoldNodes::Italic(italic),
+                Inline::Node(ParagraphNodes::Strikethrough(strikethrough)) => {
+                    BoldNodes::Strikethrough(strikethrough)
+                }
+                Inline::Node(ParagraphNodes::Emphasis(emphasis)) => {
+                    flattened = true;
+                    BoldNodes::Italic(Italic::new(emphasis.0))
+                }
+                inline => {
+                    flattened = true;
+                    BoldNodes::Text(inline_text(&inline))
+                }
+            };
+            match (out.last_mut(), node) {
+                (Some(BoldNodes::Text(last)), BoldNodes::Text(text)) => last.push_str(&text),
+                (_, node) => out.push(node),
+            }
+        }
+        if flattened {
+            self.report(Construct::NestedFormatting, range);
+        }
+        out
+    }
+
+    /// Returns text of `inlines`, reporting formatting that was lost.
+    fn plain(&mut self, inlines: Vec<Inline>, range: Range<usize>) -> String {
+        if inlines
+            .iter()
+            .any(|i| !matches!(i, Inline::Node(ParagraphNodes::Text(_))))
+        {
+            self.report(Construct::NestedFormatting, range);
+        }
+        inlines.iter().map(inline_text).collect()
+    }
+
+    /// Paragraphs of blockquote or footnote body. Other blocks are flattened to paragraphs.
+    fn paragraphs(&mut self, blocks: Vec<YamdNodes>, range: Range<usize>) -> Vec<Paragraph> {
+        let mut reported = false;
+        let mut out = vec![];
+        for block in blocks {
+            match block {
+                YamdNodes::Paragraph(paragraph) => out.push(paragraph),
+                block => {
+                    if !reported {
+                        self.report(Construct::BlockInContainer, range.clone());
+                        reported = true;
+                    }
+                    out.push(flatten([block]));
+                }
+            }
+        }
+        out
+    }
+}
+
+fn icon(kind: BlockQuoteKind) -> &'static str {
+    match kind {
+        BlockQuoteKind::Note => "note",
+        BlockQuoteKind::Tip => "tip",
+        BlockQuoteKind::Important => "important",
+        BlockQuoteKind::Warning => "warning",
+        BlockQuoteKind::Caution => "caution",
+    }
+}
+
+fn alignment(alignment: Alignment) -> TableAlignment {
+    match alignment {
+        Alignment::None => TableAlignment::None,
+        Alignment::Left => TableAlignment::Left,
+        Alignment::Center => TableAlignment::Center,
+        Alignment::Right => TableAlignment::Right,
+    }
+}
+
+fn is_inline(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::Text(_)
+            | Event::Code(_)
+            | Event::InlineMath(_)
+            | Event::DisplayMath(_)
+            | Event::InlineHtml(_)
+            | Event::FootnoteReference(_)
+            | Event::SoftBreak
+            | Event::HardBreak
+            | Event::TaskListMarker(_)
+            | Event::Start(
+                Tag::Emphasis
+                    | Tag::Strong
+                    | Tag::Strikethrough
+                    | Tag::Superscript
+                    | Tag::Subscript
+                    | Tag::Link { .. }
+                    | Tag::Image { .. }
+            )
+    )
+}
+
+fn push_text(out: &mut Vec<Inline>, text: &str) {
+    if let Some(Inline::Node(ParagraphNodes::Text(last))) = out.last_mut() {
+        last.push_str(text);
+    } else {
+        out.push(Inline::Node(text.to_string().into()));
+    }
+}
+
+fn extend(out: &mut Vec<Inline>, inlines: impl IntoIterator<Item = Inline>) {
+    for inline in inlines {
+        match inline {
+            Inline::Node(ParagraphNodes::Text(text)) => push_text(out, &text),
+            inline => out.push(inline),
+        }
+    }
+}
+
+fn push_line_break(out: &mut Vec<Inline>) {
+    if !out.is_empty() {
+        push_text(out, "\n");
+    }
+}
+
+fn inline_text(inline: &Inline) -> String {
+    match inline {
+        Inline::Node(node) => node_text(node),
+        Inline::Image(image) => image.alt.clone(),
+        Inline::DisplayMath(math) => math.clone(),
+    }
+}
+
+fn node_text(node: &ParagraphNodes) -> String {
+    match node {
+        ParagraphNodes::Anchor(anchor) => anchor.text.clone(),
+        ParagraphNodes::Bold(bold) => bold
+            .body
+            .iter()
+            .map(|node| match node {
+                BoldNodes::Italic(italic) => italic.0.as_str(),
+                BoldNodes::Strikethrough(strikethrough) => strikethrough.0.as_str(),
+                BoldNodes::Text(text) => text.as_str(),
+            })
+            .collect(),
+        ParagraphNodes::Italic(italic) => italic.0.clone(),
+        ParagraphNodes::Strikethrough(strikethrough) => strikethrough.0.clone(),
+        ParagraphNodes::Text(text) => text.clone(),
+        ParagraphNodes::CodeSpan(code_span) => code_span.0.clone(),
+        ParagraphNodes::Emphasis(emphasis) => emphasis.0.clone(),
+        ParagraphNodes::FootnoteReference(reference) => format!("[^{}]", reference.label),
+        ParagraphNodes::InlineMath(math) => math.0.clone(),
+    }
+}
+
+/// Flattens blocks into a paragraph, one line per line of a block, keeping inline nodes where
+/// possible.
+fn flatten(blocks: impl IntoIterator<Item = YamdNodes>) -> Paragraph {
+    fn lines(block: YamdNodes, out: &mut Vec<ParagraphNodes>) {
+        let push = |nodes: Vec<ParagraphNodes>, out: &mut Vec<ParagraphNodes>| {
+            if !out.is_empty() {
+                out.push(String::from("\n").into());
+            }
+            out.extend(nodes);
+        };
+        match block {
+            YamdNodes::Paragraph(paragraph) => push(paragraph.body, out),
+            YamdNodes::Heading(heading) => push(
+                heading
+                    .body
+                    .into_iter()
+                    .map(|node| match node {
+                        HeadingNodes::Text(text) => text.into(),
+                        HeadingNodes::Anchor(anchor) => anchor.into(),
+                        HeadingNodes::InlineMath(math) => math.into(),
+                    })
+                    .collect(),
+                out,
+            ),
+            YamdNodes::Image(image) => push(vec![Anchor::new(image.alt, image.src).into()], out),
+            YamdNodes::Images(images) => images
+                .body
+                .into_iter()
+                .for_each(|image| lines(image.into(), out)),
+            YamdNodes::Code(code) => push(vec![CodeSpan::new(code.code).into()], out),
+            YamdNodes::DisplayMath(math) => push(vec![InlineMath::new(math.body).into()], out),
+            YamdNodes::List(list) => list.body.into_iter().for_each(|item| {
+                push(item.text, out);
+                if let Some(nested_list) = item.nested_list {
+                    lines(nested_list.into(), out);
+                }
+            }),
+            YamdNodes::Highlight(highlight) => highlight
+                .body
+                .into_iter()
+                .for_each(|paragraph| lines(paragraph.into(), out)),
+            YamdNodes::Collapsible(collapsible) => collapsible
+                .body
+                .into_iter()
+                .for_each(|node| lines(node, out)),
+            YamdNodes::Table(table) => {
+                std::iter::once(table.header)
+                    .chain(table.body)
+                    .for_each(|row| {
+                        let mut nodes: Vec<ParagraphNodes> = vec![];
+                        for (i, cell) in row.cells.into_iter().enumerate() {
+                            if i > 0 {
+                                nodes.push(String::from(" | ").into());
+                            }
+                            nodes.extend(cell.body);
+                        }
+                        push(nodes, out);
+                    })
+            }
+            YamdNodes::FootnoteDefinition(definition) => {
+                let mut nodes = vec![format!("[^{}]: ", definition.label).into()];
+                nodes.extend(definition.body);
+                push(nodes, out);
+            }
+            YamdNodes::ThematicBreak(_) | YamdNodes::Embed(_) | YamdNodes::Custom(_) => {}
+        }
+    }
+    let mut body = vec![];
+    blocks.into_iter().for_each(|block| lines(block, &mut body));
+    let mut merged: Vec<ParagraphNodes> = vec![];
+    for node in body {
+        match (merged.last_mut(), node) {
+            (Some(ParagraphNodes::Text(last)), ParagraphNodes::Text(text)) => last.push_str(&text),
+            (_, node) => merged.push(node),
+        }
+    }
+    Paragraph::new(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        commonmark::{Construct, from_commonmark},
+        nodes::{
+            Anchor, Bold, BoldNodes, Code, CodeSpan, DisplayMath, Emphasis, FootnoteDefinition,
+            FootnoteReference, Heading, Highlight, Image, Images, InlineMath, Italic, List,
+            ListItem, ListTypes, Paragraph, Strikethrough, Table, TableAlignment, TableCell,
+            TableRow, Yamd,
+        },
+    };
+
+    fn constructs(input: &str) -> Vec<Construct> {
+        from_commonmark(input)
+            .1
+            .into_iter()
+            .map(|a| a.construct)
+            .collect()
+    }
+
+    #[test]
+    fn exact_equivalents() {
+        let input = "---\ntitle: a\n---\n# h [a](b)\n\ntext **b _i_ ~~s~~** _i_ *e* ~~s~~ `c`\n\n```rust\nlet a;\n```\n\n---\n\n![a](b)\n\n![c](d)\n![e](f)\n\n- a\n  1. b\n  2. c\n- d";
+        let (yamd, approximations) = from_commonmark(input);
+        assert_eq!(approximations, vec![]);
+        assert_eq!(
+            yamd,
+            Yamd::new(
+                Some("title: a".to_string()),
+                vec![
+                    Heading::new(
+                        1,
+                        vec![String::from("h ").into(), Anchor::new("a", "b").into()]
+                    )
+                    .into(),
+                    Paragraph::new(vec![
+                        String::from("text ").into(),
+                        Bold::new(vec![
+                            BoldNodes::Text("b ".to_string()),
+                            Italic::new("i").into(),
+                            BoldNodes::Text(" ".to_string()),
+                            Strikethrough::new("s").into(),
+                        ])
+                        .into(),
+                        String::from(" ").into(),
+                        Italic::new("i").into(),
+                        String::from(" ").into(),
+                        Emphasis::new("e").into(),
+                        String::from(" ").into(),
+                        Strikethrough::new("s").into(),
+                        String::from(" ").into(),
+                        CodeSpan::new("c").into(),
+                    ])
+                    .into(),
+                    Code::new("rust", "let a;").into(),
+                    crate::nodes::ThematicBreak::new().into(),
+                    Image::new("a", "b").into(),
+                    Images::new(vec![Image::new("c", "d"), Image::new("e", "f")]).into(),
+                    List::new(
+                        ListTypes::Unordered,
+                        0,
+                        vec![
+                            ListItem::new(
+                                vec![String::from("a").into()],
+                                Some(List::new(
+                                    ListTypes::Ordered,
+                                    1,
+                                    vec![
+                                        ListItem::new(vec![String::from("b").into()], None),
+                                        ListItem::new(vec![String::from("c").into()], None),
+                                    ]
+                                ))
+                            ),
+                            ListItem::new(vec![String::from("d").into()], None),
+                        ]
+                    )
+                    .into(),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn blockquote() {
+        let (yamd, approximations) = from_commonmark("> a\n> b\n>\n> - c\n\n> [!TIP]\n> d");
+        assert_eq!(
+            yamd,
+            Yamd::new(
+                None,
+                vec![
+                    Highlight::new(
+                        None::<String>,
+                        None::<String>,
+                        vec![
+                            Paragraph::new(vec![String::from("a\nb").into()]),
+                            Paragraph::new(vec![String::from("c").into()]),
+                        ]
+                    )
+                    .into(),
+                    Highlight::new(
+                        None::<String>,
+                        Some("tip"),
+                        vec![Paragraph::new(vec![String::from("d").into()])]
+                    )
+                    .into(),
+                ]
+            )
+        );
+        let constructs: Vec<_> = approximations
+            .iter()
+            .map(|a| (a.construct, a.range.clone()))
+            .collect();
+        assert_eq!(
+            constructs,
+            vec![
+                (Construct::BlockQuote, 0..16),
+                (Construct::BlockInContainer, 0..16)
+            ]
+        );
+    }
+
+    #[test]
+    fn setext_heading() {
+        let (yamd, approximations) = from_commonmark("a\n===\n\nb\n---");
+        assert_eq!(yamd.to_string(), "# a\n\n## b");
+        assert_eq!(approximations.len(), 2);
+        assert!(
+            approximations
+                .iter()
+                .all(|a| a.construct == Construct::SetextHeading)
+        );
+    }
+
+    #[test]
+    fn ordered_list() {
+        let (yamd, approximations) = from_commonmark("3. a\n4. b");
+        assert_eq!(yamd.to_string(), "+ a\n+ b");
+        assert_eq!(approximations[0].construct, Construct::OrderedListStart);
+        assert!(approximations[0].construct.is_dropped());
+        assert_eq!(constructs("1. a\n2. b"), vec![]);
+    }
+
+    #[test]
+    fn loose_list() {
+        let (yamd, approximations) = from_commonmark("- a\n\n  b\n\n- c");
+        assert_eq!(
+            yamd,
+            Yamd::new(
+                None,
+                vec![
+                    List::new(
+                        ListTypes::Unordered,
+                        0,
+                        vec![
+                            ListItem::new(vec![String::from("a\nb").into()], None),
+                            ListItem::new(vec![String::from("c").into()], None),
+                        ]
+                    )
+                    .into()
+                ]
+            )
+        );
+        assert_eq!(approximations[0].construct, Construct::LooseList);
+    }
+
+    #[test]
+    fn block_in_list_item() {
+        let (yamd, approximations) = from_commonmark("- a\n  ```\n  code\n  ```");
+        assert_eq!(
+            yamd,
+            Yamd::new(
+                None,
+                vec![
+                    List::new(
+                        ListTypes::Unordered,
+                        0,
+                        vec![ListItem::new(
+                            vec![String::from("a\n").into(), CodeSpan::new("code").into()],
+                            None
+                        )]
+                    )
+                    .into()
+                ]
+            )
+        );
+        assert_eq!(approximations[0].construct, Construct::BlockInContainer);
+    }
+
+    #[test]
+    fn indented_code() {
+        let (yamd, approximations) = from_commonmark("    let a;\n    let b;\n");
+        assert_eq!(
+            yamd,
+            Yamd::new(None, vec![Code::new("", "let a;\nlet b;").into()])
+        );
+        assert_eq!(approximations[0].construct, Construct::IndentedCode);
+    }
+
+    #[test]
+    fn links() {
+        let (yamd, approximations) = from_commonmark(
+            "[a][r] [b] [c](d \"title\") <https://e> <f@g.h> [**i**](j)\n\n[r]: k\n[b]: l",
+        );
+        assert_eq!(
+            yamd.to_string(),
+            "[a](k) [b](l) [c](d) [https://e](https://e) [f@g.h](mailto:f@g.h) [i](j)"
+        );
+        let constructs: Vec<_> = approximations.iter().map(|a| a.construct).collect();
+        assert_eq!(
+            constructs,
+            vec![
+                Construct::ReferenceLink,
+                Construct::ReferenceLink,
+                Construct::LinkTitle,
+                Construct::NestedFormatting
+            ]
+        );
+    }
+
+    #[test]
+    fn html_is_dropped() {
+        let (yamd, approximations) = from_commonmark("<div>\nblock\n</div>\n\na <br> b");
+        assert_eq!(yamd.to_string(), "a  b");
+        assert_eq!(approximations.len(), 2);
+        assert!(approximations.iter().all(|a| a.construct.is_dropped()));
+    }
+
+    #[test]
+    fn inline_image_and_hard_break() {
+        let (yamd, approximations) = from_commonmark("a ![b](c)  \nd");
+        assert_eq!(
+            yamd,
+            Yamd::new(
+                None,
+                vec![
+                    Paragraph::new(vec![
+                        String::from("a ").into(),
+                        Anchor::new("b", "c").into(),
+                        String::from("\nd").into(),
+                    ])
+                    .into()
+                ]
+            )
+        );
+        let constructs: Vec<_> = approximations.iter().map(|a| a.construct).collect();
+        assert_eq!(
+            constructs,
+            vec![Construct::HardBreak, Construct::InlineImage]
+        );
+    }
+
+    #[test]
+    fn formatting_in_heading() {
+        let (yamd, approximations) = from_commonmark("# a **b** `c`");
+        assert_eq!(
+            yamd,
+            Yamd::new(
+                None,
+                vec![Heading::new(1, vec![String::from("a b c").into()]).into()]
+            )
+        );
+        assert_eq!(approximations[0].construct, Construct::NestedFormatting);
+    }
+
+    #[test]
+    fn table() {
+        let (yamd, approximations) =
+            from_commonmark("| a | **b** |\n|:--|--:|\n| `c\\|d` |\n\n> | e |\n> |---|");
+        assert_eq!(
+            yamd.body[0],
+            Table::new(
+                TableRow::new(vec![
+                    TableCell::new(vec![String::from("a").into()]),
+                    TableCell::new(vec![
+                        Bold::new(vec![BoldNodes::from("b".to_string())]).into()
+                    ]),
+                ]),
+                vec![TableAlignment::Left, TableAlignment::Right],
+                vec![TableRow::new(vec![
+                    TableCell::new(vec![CodeSpan::new("c|d").into()]),
+                    TableCell::new(vec![]),
+                ])],
+            )
+            .into()
+        );
+        assert_eq!(
+            yamd.body[1],
+            Highlight::new(
+                None::<String>,
+                None::<String>,
+                vec![Paragraph::new(vec![String::from("e").into()])]
+            )
+            .into()
+        );
+        let constructs: Vec<Construct> = approximations.iter().map(|a| a.construct).collect();
+        assert_eq!(
+            constructs,
+            vec![Construct::BlockQuote, Construct::BlockInContainer]
+        );
+    }
+
+    #[test]
+    fn footnotes() {
+        let (yamd, approximations) =
+            from_commonmark("a[^1] b[^x y]\n\n[^1]: c\n\n    d\n\n[^x y]: e");
+        assert_eq!(
+            yamd,
+            Yamd::new(
+                None,
+                vec![
+                    Paragraph::new(vec![
+                        String::from("a").into(),
+                        FootnoteReference::new("1").into(),
+                        String::from(" b[^x y]").into(),
+                    ])
+                    .into(),
+                    FootnoteDefinition::new("1", vec![String::from("c\nd").into()]).into(),
+                    Paragraph::new(vec![String::from("[^x y]: e").into()]).into(),
+                ]
+            )
+        );
+        let constructs: Vec<Construct> = approximations.iter().map(|a| a.construct).collect();
+        assert_eq!(
+            constructs,
+            vec![
+                Construct::Footnote,
+                Construct::BlockInContainer,
+                Construct::Footnote
+            ]
+        );
+    }
+
+    #[test]
+    fn task_list() {
+        let (yamd, approximations) = from_commonmark("- [x] a\n  - [ ] b\n- c\n\n- [ ] d");
+        assert_eq!(yamd.to_string(), "- [x] a\n - [ ] b\n- c\n- [ ] d");
+        assert_eq!(approximations.len(), 1);
+        assert_eq!(approximations[0].construct, Construct::LooseList);
+    }
+
+    #[test]
+    fn math() {
+        let (yamd, approximations) = from_commonmark("$a$ $$b$$ $ c $\n\n$$\nd\n$$");
+        assert_eq!(
+            yamd,
+            Yamd::new(
+                None,
+                vec![
+                    Paragraph::new(vec![
+                        InlineMath::new("a").into(),
+                        String::from(" ").into(),
+                        InlineMath::new("b").into(),
+                        String::from(" $ c $").into(),
+                    ])
+                    .into(),
+                    DisplayMath::new("d").into(),
+                ]
+            )
+        );
+        assert_eq!(approximations.len(), 1);
+        assert_eq!(approximations[0].construct, Construct::Math);
+    }
+}
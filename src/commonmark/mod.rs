@@ -0,0 +1,108 @@
+//! Conversion between CommonMark and YAMD.
+//!
+//! YAMD reuses most of CommonMark's syntax, but not all of CommonMark has a YAMD equivalent:
+//! blockquotes, HTML, reference links and so on. [from_commonmark] converts such constructs to the
+//! closest YAMD node, or drops them, and reports every place it did so as an [Approximation].
+//! [to_commonmark] goes the other way and writes GitHub flavoured Markdown.
+//!
+//! Input is parsed with GitHub flavoured extensions: strikethrough, tables, footnotes, task lists
+//! and alerts, plus YAML metadata block at the start of the document.
+//!
+//! Example:
+//!
+//! ```rust
+//! use yamd::commonmark::{Construct, from_commonmark};
+//!
+//! let input = "Title\n=====\n\n> quote\n\n1. one\n2. two";
+//! let (yamd, approximations) = from_commonmark(input);
+//! assert_eq!(yamd.to_string(), "# Title\n\n!!\nquote\n!!\n\n+ one\n+ two");
+//!
+//! let constructs: Vec<Construct> = approximations.iter().map(|a| a.construct).collect();
+//! assert_eq!(constructs, vec![Construct::SetextHeading, Construct::BlockQuote]);
+//! assert_eq!(approximations[1].range, 13..21);
+//! ```
+
+mod export;
+mod import;
+
+use std::{fmt::Display, ops::Range};
+
+pub use export::{EmbedFallback, ExportOptions, to_commonmark, to_commonmark_with};
+pub use import::from_commonmark;
+
+/// CommonMark construct without exact YAMD equivalent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Construct {
+    /// Converted to [Highlight](crate::nodes::Highlight) without title and icon. GitHub alerts
+    /// (`> [!NOTE]`) are converted to a highlight with icon and are not reported.
+    BlockQuote,
+    /// Converted to `#` [Heading](crate::nodes::Heading).
+    SetextHeading,
+    /// Start number of an ordered list other than `1`. Dropped, YAMD lists always start at one.
+    OrderedListStart,
+    /// List with blank lines between items. Converted to a tight list.
+    LooseList,
+    /// Converted to [Code](crate::nodes::Code) without language.
+    IndentedCode,
+    /// Reference, collapsed or shortcut link. Converted to [Anchor](crate::nodes::Anchor) with the
+    /// resolved url.
+    ReferenceLink,
+    /// Title of a link or an image. Dropped.
+    LinkTitle,
+    /// Converted to a line break.
+    HardBreak,
+    /// HTML block or inline HTML. Dropped.
+    Html,
+    /// Image inside of text. Converted to [Anchor](crate::nodes::Anchor).
+    InlineImage,
+    /// Formatting inside of a node that can contain only text, e.g. bold text in a link or a
+    /// heading. Flattened to text.
+    NestedFormatting,
+    /// Block other than paragraph inside of a blockquote or list item, or more than one block in a
+    /// footnote definition. Flattened to text.
+    BlockInContainer,
+    /// Footnote reference or definition with a label that is not a valid
+    /// [FootnoteReference](crate::nodes::FootnoteReference) label. Converted to text.
+    Footnote,
+    /// Display math inside of text, converted to [InlineMath](crate::nodes::InlineMath), or math
+    /// that YAMD can not represent, converted to text.
+    Math,
+}
+
+impl Construct {
+    /// Returns `true` if the construct is dropped rather than approximated.
+    pub fn is_dropped(&self) -> bool {
+        matches!(
+            self,
+            Construct::OrderedListStart | Construct::LinkTitle | Construct::Html
+        )
+    }
+}
+
+impl Display for Construct {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Construct::BlockQuote => "blockquote converted to highlight",
+            Construct::SetextHeading => "setext heading converted to `#` heading",
+            Construct::OrderedListStart => "ordered list start number dropped",
+            Construct::LooseList => "loose list converted to tight list",
+            Construct::IndentedCode => "indented code converted to fenced code",
+            Construct::ReferenceLink => "reference link converted to inline link",
+            Construct::LinkTitle => "link title dropped",
+            Construct::HardBreak => "hard line break converted to line break",
+            Construct::Html => "HTML dropped",
+            Construct::InlineImage => "inline image converted to anchor",
+            Construct::NestedFormatting => "nested formatting flattened to text",
+            Construct::BlockInContainer => "nested block flattened to text",
+            Construct::Footnote => "footnote converted to text",
+            Construct::Math => "math converted to inline math or text",
+        })
+    }
+}
+
+/// A [Construct] found in the input, with its byte range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Approximation {
+    pub construct: Construct,
+    pub range: Range<usize>,
+}
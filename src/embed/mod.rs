@@ -0,0 +1,355 @@
+//! Resolving [Embed]s with resolvers registered per [kind](Embed::kind).
+//!
+//! An [EmbedResolver] turns [arguments](Embed::arguments) of an embed into [Resolved] YAMD nodes
+//! or HTML. [EmbedResolvers::expand] replaces embeds with the nodes they resolve to, and, with the
+//! `html` feature enabled, [EmbedResolvers::to_html] renders them. An embed whose kind has no
+//! resolver is an [EmbedError::UnknownKind].
+//!
+//! ```
+//! use yamd::{
+//!     deserialize,
+//!     embed::{EmbedError, EmbedResolvers, Resolved},
+//!     nodes::{Anchor, EmbedArgs, Paragraph},
+//! };
+//!
+//! let resolvers = EmbedResolvers::new().register("link", |args: &EmbedArgs| {
+//!     let [url] = &args.positional[..] else {
+//!         return Err("expected one url".to_string());
+//!     };
+//!     let text = args.get("text").unwrap_or(url);
+//!     Ok(Resolved::Nodes(vec![Paragraph::new(vec![Anchor::new(text, url).into()]).into()]))
+//! });
+//!
+//! let yamd = resolvers.expand(deserialize("{{link|https://yamd.dev|text=YAMD}}")).unwrap();
+//! assert_eq!(yamd.to_string(), "[YAMD](https://yamd.dev)");
+//!
+//! let error = resolvers.expand(deserialize("{{video|123}}")).unwrap_err();
+//! assert_eq!(error, EmbedError::UnknownKind { kind: "video".to_string() });
+//! assert_eq!(error.to_string(), "unknown embed kind `video`");
+//! ```
+
+use std::{collections::HashMap, fmt::Display};
+
+use crate::nodes::{Collapsible, Custom, Embed, EmbedArgs, Yamd, YamdNodes};
+
+/// Result of resolving an [Embed].
+#[derive(Debug, PartialEq, Clone)]
+pub enum Resolved {
+    /// Nodes that replace the embed.
+    Nodes(Vec<YamdNodes>),
+    /// HTML the embed is rendered to.
+    Html(String),
+}
+
+/// Resolves embeds of one [kind](Embed::kind), see [EmbedResolvers::register].
+///
+/// Implemented for closures that take [EmbedArgs].
+pub trait EmbedResolver {
+    /// Resolves an embed with `args`, or returns a message that explains why it can't.
+    fn resolve(&self, args: &EmbedArgs) -> Result<Resolved, String>;
+}
+
+impl<F> EmbedResolver for F
+where
+    F: Fn(&EmbedArgs) -> Result<Resolved, String>,
+{
+    fn resolve(&self, args: &EmbedArgs) -> Result<Resolved, String> {
+        self(args)
+    }
+}
+
+/// Error of resolving an [Embed].
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub enum EmbedError {
+    /// No resolver is registered for the kind.
+    UnknownKind { kind: String },
+    /// Resolver of the kind returned an error.
+    Resolver { kind: String, message: String },
+}
+
+impl Display for EmbedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmbedError::UnknownKind { kind } => write!(f, "unknown embed kind `{kind}`"),
+            EmbedError::Resolver { kind, message } => write!(f, "embed `{kind}`: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for EmbedError {}
+
+/// [EmbedResolver]s by [kind](Embed::kind), see [embed](crate::embed).
+#[derive(Default)]
+pub struct EmbedResolvers {
+    resolvers: HashMap<String, Box<dyn EmbedResolver>>,
+}
+
+impl EmbedResolvers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `resolver` for embeds of `kind`, replacing the one registered before.
+    pub fn register(
+        mut self,
+        kind: impl Into<String>,
+        resolver: impl EmbedResolver + 'static,
+    ) -> Self {
+        self.resolvers.insert(kind.into(), Box::new(resolver));
+        self
+    }
+
+    /// Resolves `embed` with the resolver of its kind.
+    pub fn resolve(&self, embed: &Embed) -> Result<Resolved, EmbedError> {
+        let Some(resolver) = self.resolvers.get(&embed.kind) else {
+            return Err(EmbedError::UnknownKind {
+                kind: embed.kind.clone(),
+            });
+        };
+        resolver
+            .resolve(&embed.arguments())
+            .map_err(|message| EmbedError::Resolver {
+                kind: embed.kind.clone(),
+                message,
+            })
+    }
+
+    /// Replaces every embed in the document, including embeds in [Collapsible]s and in
+    /// [nodes](Custom::nodes) of [Custom] blocks, with the nodes it resolves to. An embed resolved
+    /// to [Html](Resolved::Html) stays, nodes an embed is resolved to are not resolved again.
+    ///
+    /// Returns the first error, if any.
+    pub fn expand(&self, yamd: Yamd) -> Result<Yamd, EmbedError> {
+        Ok(Yamd {
+            body: self.expand_nodes(yamd.body)?,
+            ..yamd
+        })
+    }
+
+    fn expand_nodes(&self, nodes: Vec<YamdNodes>) -> Result<Vec<YamdNodes>, EmbedError> {
+        let mut expanded = Vec::with_capacity(nodes.len());
+        for node in nodes {
+            match node {
+                YamdNodes::Embed(embed) => match self.resolve(&embed)? {
+                    Resolved::Nodes(nodes) => expanded.extend(nodes),
+                    Resolved::Html(_) => expanded.push(embed.into()),
+                },
+                YamdNodes::Collapsible(collapsible) => expanded.push(
+                    Collapsible {
+                        title: collapsible.title,
+                        body: self.expand_nodes(collapsible.body)?,
+                    }
+                    .into(),
+                ),
+                YamdNodes::Custom(custom) => expanded.push(
+                    Custom {
+                        nodes: self.expand_nodes(custom.nodes)?,
+                        ..custom
+                    }
+                    .into(),
+                ),
+                node => expanded.push(node),
+            }
+        }
+        Ok(expanded)
+    }
+
+    /// Renders `yamd` to HTML like [to_html](crate::html::to_html), with every embed rendered as
+    /// it resolves to. Like in [expand](EmbedResolvers::expand), nodes an embed is resolved to are
+    /// not resolved again, embeds in them are rendered as is.
+    ///
+    /// Returns the first error, if any.
+    ///
+    /// ```
+    /// use yamd::{deserialize, embed::{EmbedResolvers, Resolved}, nodes::EmbedArgs};
+    ///
+    /// let resolvers = EmbedResolvers::new().register("youtube", |args: &EmbedArgs| {
+    ///     let id = args.positional.first().ok_or("expected video id")?;
+    ///     Ok(Resolved::Html(format!(
+    ///         "<iframe src=\"https://www.youtube.com/embed/{id}\"></iframe>"
+    ///     )))
+    /// });
+    /// let yamd = deserialize("{{youtube|dQw4w9WgXcQ}}");
+    /// assert_eq!(
+    ///     resolvers.to_html(&yamd).unwrap(),
+    ///     "<iframe src=\"https://www.youtube.com/embed/dQw4w9WgXcQ\"></iframe>"
+    /// );
+    /// ```
+    #[cfg(feature = "html")]
+    pub fn to_html(&self, yamd: &Yamd) -> Result<String, EmbedError> {
+        use crate::html::Renderer;
+
+        let mut renderer = ResolvingRenderer {
+            resolvers: self,
+            html: crate::html::HtmlRenderer::default(),
+            error: None,
+        };
+        let html = renderer.render(yamd);
+        match renderer.error {
+            Some(error) => Err(error),
+            None => Ok(html),
+        }
+    }
+}
+
+impl std::fmt::Debug for EmbedResolvers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.resolvers.keys()).finish()
+    }
+}
+
+#[cfg(feature = "html")]
+struct ResolvingRenderer<'a> {
+    resolvers: &'a EmbedResolvers,
+    /// Renders nodes an embed resolves to, and has the ids of the headings.
+    html: crate::html::HtmlRenderer,
+    /// First error, rendering goes on to the end of the document.
+    error: Option<EmbedError>,
+}
+
+#[cfg(feature = "html")]
+impl crate::html::Renderer for ResolvingRenderer<'_> {
+    fn render_yamd(&mut self, yamd: &Yamd, out: &mut String) {
+        self.html.set_heading_ids(yamd);
+        yamd.body
+            .iter()
+            .for_each(|node| self.render_yamd_node(node, out));
+    }
+
+    fn heading_id(&mut self, heading: &crate::nodes::Heading) -> Option<String> {
+        self.html.heading_id(heading)
+    }
+
+    fn render_embed(&mut self, embed: &Embed, out: &mut String) {
+        match self.resolvers.resolve(embed) {
+            Ok(Resolved::Html(html)) => out.push_str(&html),
+            Ok(Resolved::Nodes(nodes)) => nodes
+                .iter()
+                .for_each(|node| self.html.render_yamd_node(node, out)),
+            Err(error) => {
+                self.error.get_or_insert(error);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        deserialize,
+        nodes::{Heading, Paragraph, ThematicBreak},
+    };
+
+    fn resolvers() -> EmbedResolvers {
+        EmbedResolvers::new()
+            .register("heading", |args: &EmbedArgs| {
+                let text = args.positional.join(" ");
+                Ok(Resolved::Nodes(vec![
+                    Heading::new(1, vec![text.into()]).into(),
+                    ThematicBreak::new().into(),
+                ]))
+            })
+            .register("html", |_: &EmbedArgs| {
+                Ok(Resolved::Html("<br />".to_string()))
+            })
+            .register("fail", |args: &EmbedArgs| {
+                Err(format!("{} arguments", args.positional.len()))
+            })
+    }
+
+    #[test]
+    fn expand() {
+        let yamd = resolvers()
+            .expand(deserialize(
+                "a\n\n{{heading|b|c}}\n\n{% d\n{{heading|e}}\n%}\n\n{{html|f}}",
+            ))
+            .unwrap();
+        assert_eq!(
+            yamd.body,
+            vec![
+                Paragraph::new(vec!["a".to_string().into()]).into(),
+                Heading::new(1, vec!["b c".to_string().into()]).into(),
+                ThematicBreak::new().into(),
+                Collapsible::new(
+                    "d",
+                    vec![
+                        Heading::new(1, vec!["e".to_string().into()]).into(),
+                        ThematicBreak::new().into(),
+                    ]
+                )
+                .into(),
+                Embed::new("html", "f").into(),
+            ]
+        );
+        let yamd = Yamd::new(
+            None,
+            vec![
+                Custom::new("note", "", "", "")
+                    .with_nodes(vec![Embed::new("heading", "a").into()])
+                    .into(),
+            ],
+        );
+        assert_eq!(
+            resolvers().expand(yamd).unwrap().body,
+            vec![
+                Custom::new("note", "", "", "")
+                    .with_nodes(vec![
+                        Heading::new(1, vec!["a".to_string().into()]).into(),
+                        ThematicBreak::new().into(),
+                    ])
+                    .into()
+            ]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let error = resolvers().expand(deserialize("{{fail|a|b}}")).unwrap_err();
+        assert_eq!(
+            error,
+            EmbedError::Resolver {
+                kind: "fail".to_string(),
+                message: "2 arguments".to_string()
+            }
+        );
+        assert_eq!(error.to_string(), "embed `fail`: 2 arguments");
+        assert_eq!(
+            resolvers().resolve(&Embed::new("unknown", "")),
+            Err(EmbedError::UnknownKind {
+                kind: "unknown".to_string()
+            })
+        );
+    }
+
+    #[cfg(feature = "html")]
+    #[test]
+    fn to_html() {
+        let yamd = deserialize("{{heading|a}}\n\n{{html|b}}");
+        assert_eq!(
+            resolvers().to_html(&yamd),
+            Ok("<h1 id=\"a\">a</h1><hr /><br />".to_string())
+        );
+        let yamd = deserialize("{{html|a}}\n\n{{unknown|b}}\n\n{{fail|c}}");
+        assert_eq!(
+            resolvers().to_html(&yamd),
+            Err(EmbedError::UnknownKind {
+                kind: "unknown".to_string()
+            })
+        );
+    }
+
+    #[cfg(feature = "html")]
+    #[test]
+    fn to_html_does_not_resolve_resolved_nodes() {
+        let resolvers = EmbedResolvers::new().register("a", |_: &EmbedArgs| {
+            Ok(Resolved::Nodes(vec![Embed::new("a", "").into()]))
+        });
+        assert_eq!(
+            resolvers.to_html(&deserialize("{{a|b}}")),
+            Ok("<iframe class=\"a\" src=\"\"></iframe>".to_string())
+        );
+    }
+}
@@ -0,0 +1,801 @@
+//! HTML rendering of the [`Yamd`] AST.
+//!
+//! Every node renders to the markup documented in its "HTML equivalent" section. Text is
+//! escaped, so the output is safe to embed as-is. [Metadata](Yamd::metadata) is not rendered.
+//!
+//! Example:
+//!
+//! ```rust
+//! use yamd::{deserialize, html::to_html};
+//!
+//! let yamd = deserialize("# Hello\n\nA paragraph with **bold** <text>.");
+//! assert_eq!(
+//!     to_html(&yamd),
+//!     "<h1 id=\"hello\">Hello</h1><p>A paragraph with <b>bold</b> &lt;text&gt;.</p>"
+//! );
+//! ```
+//!
+//! Headings get ids from [Yamd::heading_ids], the same ids that links of [Yamd::toc] point to.
+//!
+//! Output of individual nodes can be customized by implementing [`Renderer`]. Single nodes can be
+//! rendered with [`ToHtml`]:
+//!
+//! ```rust
+//! use yamd::{html::ToHtml, nodes::Anchor};
+//!
+//! let anchor = Anchor::new("link", "https://example.com?a=1&b=2");
+//! assert_eq!(
+//!     anchor.to_html(),
+//!     r#"<a href="https://example.com?a=1&amp;b=2">link</a>"#
+//! );
+//! ```
+
+use std::collections::HashMap;
+
+use crate::nodes::{
+    Anchor, Bold, BoldNodes, Code, CodeSpan, Collapsible, Custom, DisplayMath, Embed, Emphasis,
+    FootnoteDefinition, FootnoteReference, Heading, HeadingNodes, Highlight, Image, Images,
+    InlineMath, Italic, List, ListItem, ListTypes, Paragraph, ParagraphNodes, Strikethrough, Table,
+    TableAlignment, ThematicBreak, Yamd, YamdNodes,
+};
+
+/// Renders [`Yamd`] to HTML.
+pub fn to_html(yamd: &Yamd) -> String {
+    HtmlRenderer::default().render(yamd)
+}
+
+/// Renders a node to HTML using [`HtmlRenderer`].
+pub trait ToHtml {
+    /// Appends HTML representation of the node to `out`.
+    fn write_html(&self, out: &mut String);
+
+    /// Returns HTML representation of the node.
+    fn to_html(&self) -> String {
+        let mut out = String::new();
+        self.write_html(&mut out);
+        out
+    }
+}
+
+/// Appends `input` to `out`, escaping characters that are not safe in HTML text or attribute
+/// values.
+pub fn push_escaped(out: &mut String, input: &str) {
+    for c in input.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+fn push_element(out: &mut String, tag: &str, text: &str) {
+    out.push('<');
+    out.push_str(tag);
+    out.push('>');
+    push_escaped(out, text);
+    out.push_str("</");
+    out.push_str(tag);
+    out.push('>');
+}
+
+fn push_cell_start(out: &mut String, tag: &str, alignment: TableAlignment) {
+    out.push('<');
+    out.push_str(tag);
+    match alignment {
+        TableAlignment::None => {}
+        TableAlignment::Left => out.push_str(" style=\"text-align: left\""),
+        TableAlignment::Center => out.push_str(" style=\"text-align: center\""),
+        TableAlignment::Right => out.push_str(" style=\"text-align: right\""),
+    }
+    out.push('>');
+}
+
+/// Renders the AST node by node.
+///
+/// Every method has a default implementation that produces the documented HTML equivalent and
+/// calls back into the renderer for child nodes, so an implementation overrides only the nodes it
+/// cares about:
+///
+/// ```rust
+/// use yamd::{
+///     deserialize,
+///     html::{Renderer, push_escaped},
+///     nodes::Embed,
+/// };
+///
+/// struct Youtube;
+///
+/// impl Renderer for Youtube {
+///     fn render_embed(&mut self, embed: &Embed, out: &mut String) {
+///         out.push_str("<iframe src=\"https://www.youtube.com/embed/");
+///         let args = embed.arguments();
+///         push_escaped(out, args.positional.first().map_or("", |id| id.as_ref()));
+///         out.push_str("\"></iframe>");
+///     }
+/// }
+///
+/// let yamd = deserialize("# Video\n\n{{youtube|dQw4w9WgXcQ|start=30}}");
+/// assert_eq!(
+///     Youtube.render(&yamd),
+///     "<h1 id=\"video\">Video</h1><iframe src=\"https://www.youtube.com/embed/dQw4w9WgXcQ\"></iframe>"
+/// );
+/// ```
+pub trait Renderer {
+    /// Returns rendered document.
+    fn render(&mut self, yamd: &Yamd) -> String {
+        let mut out = String::new();
+        self.render_yamd(yamd, &mut out);
+        out
+    }
+
+    fn render_yamd(&mut self, yamd: &Yamd, out: &mut String) {
+        yamd.body
+            .iter()
+            .for_each(|node| self.render_yamd_node(node, out));
+    }
+
+    fn render_yamd_node(&mut self, node: &YamdNodes, out: &mut String) {
+        match node {
+            YamdNodes::Paragraph(node) => self.render_paragraph(node, out),
+            YamdNodes::Heading(node) => self.render_heading(node, out),
+            YamdNodes::Image(node) => self.render_image(node, out),
+            YamdNodes::Images(node) => self.render_images(node, out),
+            YamdNodes::Code(node) => self.render_code(node, out),
+            YamdNodes::List(node) => self.render_list(node, out),
+            YamdNodes::Highlight(node) => self.render_highlight(node, out),
+            YamdNodes::ThematicBreak(node) => self.render_thematic_break(node, out),
+            YamdNodes::Embed(node) => self.render_embed(node, out),
+            YamdNodes::Collapsible(node) => self.render_collapsible(node, out),
+            YamdNodes::Table(node) => self.render_table(node, out),
+            YamdNodes::FootnoteDefinition(node) => self.render_footnote_definition(node, out),
+            YamdNodes::DisplayMath(node) => self.render_display_math(node, out),
+            YamdNodes::Custom(node) => self.render_custom(node, out),
+        }
+    }
+
+    fn render_paragraph(&mut self, paragraph: &Paragraph, out: &mut String) {
+        out.push_str("<p>");
+        paragraph
+            .body
+            .iter()
+            .for_each(|node| self.render_paragraph_node(node, out));
+        out.push_str("</p>");
+    }
+
+    fn render_paragraph_node(&mut self, node: &ParagraphNodes, out: &mut String) {
+        match node {
+            ParagraphNodes::Anchor(node) => self.render_anchor(node, out),
+            ParagraphNodes::Bold(node) => self.render_bold(node, out),
+            ParagraphNodes::Italic(node) => self.render_italic(node, out),
+            ParagraphNodes::Strikethrough(node) => self.render_strikethrough(node, out),
+            ParagraphNodes::Text(text) => self.render_text(text, out),
+            ParagraphNodes::CodeSpan(node) => self.render_code_span(node, out),
+            ParagraphNodes::Emphasis(node) => self.render_emphasis(node, out),
+            ParagraphNodes::FootnoteReference(node) => self.render_footnote_reference(node, out),
+            ParagraphNodes::InlineMath(node) => self.render_inline_math(node, out),
+        }
+    }
+
+    /// Returns the id of `heading`, rendered as its `id` attribute, or `None` for no attribute.
+    ///
+    /// [Slug](Heading::slug) of the heading by default. [HtmlRenderer] uses
+    /// [Yamd::heading_ids], so that headings with the same text have different ids.
+    fn heading_id(&mut self, heading: &Heading) -> Option<String> {
+        Some(heading.slug())
+    }
+
+    fn render_heading(&mut self, heading: &Heading, out: &mut String) {
+        let tag = format!("h{}", heading.level);
+        out.push('<');
+        out.push_str(&tag);
+        if let Some(id) = self.heading_id(heading).filter(|id| !id.is_empty()) {
+            out.push_str(" id=\"");
+            push_escaped(out, &id);
+            out.push('"');
+        }
+        out.push('>');
+        heading
+            .body
+            .iter()
+            .for_each(|node| self.render_heading_node(node, out));
+        out.push_str("</");
+        out.push_str(&tag);
+        out.push('>');
+    }
+
+    fn render_heading_node(&mut self, node: &HeadingNodes, out: &mut String) {
+        match node {
+            HeadingNodes::Text(text) => self.render_text(text, out),
+            HeadingNodes::Anchor(node) => self.render_anchor(node, out),
+            HeadingNodes::InlineMath(node) => self.render_inline_math(node, out),
+        }
+    }
+
+    /// Renders plain text of [ParagraphNodes], [HeadingNodes] and [BoldNodes].
+    fn render_text(&mut self, text: &str, out: &mut String) {
+        push_escaped(out, text);
+    }
+
+    fn render_anchor(&mut self, anchor: &Anchor, out: &mut String) {
+        out.push_str("<a href=\"");
+        push_escaped(out, &anchor.url);
+        out.push_str("\">");
+        push_escaped(out, &anchor.text);
+        out.push_str("</a>");
+    }
+
+    fn render_bold(&mut self, bold: &Bold, out: &mut String) {
+        out.push_str("<b>");
+        bold.body
+            .iter()
+            .for_each(|node| self.render_bold_node(node, out));
+        out.push_str("</b>");
+    }
+
+    fn render_bold_node(&mut self, node: &BoldNodes, out: &mut String) {
+        match node {
+            BoldNodes::Italic(node) => self.render_italic(node, out),
+            BoldNodes::Strikethrough(node) => self.render_strikethrough(node, out),
+            BoldNodes::Text(text) => self.render_text(text, out),
+        }
+    }
+
+    fn render_italic(&mut self, italic: &Italic, out: &mut String) {
+        push_element(out, "i", &italic.0);
+    }
+
+    fn render_strikethrough(&mut self, strikethrough: &Strikethrough, out: &mut String) {
+        push_element(out, "s", &strikethrough.0);
+    }
+
+    fn render_emphasis(&mut self, emphasis: &Emphasis, out: &mut String) {
+        push_element(out, "em", &emphasis.0);
+    }
+
+    fn render_code_span(&mut self, code_span: &CodeSpan, out: &mut String) {
+        push_element(out, "code", &code_span.0);
+    }
+
+    fn render_inline_math(&mut self, inline_math: &InlineMath, out: &mut String) {
+        out.push_str("<span class=\"math math-inline\">");
+        push_escaped(out, &inline_math.0);
+        out.push_str("</span>");
+    }
+
+    fn render_image(&mut self, image: &Image, out: &mut String) {
+        out.push_str("<img src=\"");
+        push_escaped(out, &image.src);
+        out.push_str("\" alt=\"");
+        push_escaped(out, &image.alt);
+        out.push_str("\" />");
+    }
+
+    fn render_images(&mut self, images: &Images, out: &mut String) {
+        out.push_str("<div class=\"images\">");
+        images
+            .body
+            .iter()
+            .for_each(|node| self.render_image(node, out));
+        out.push_str("</div>");
+    }
+
+    fn render_code(&mut self, code: &Code, out: &mut String) {
+        out.push_str("<pre><code");
+        if !code.lang.is_empty() {
+            out.push_str(" class=\"");
+            push_escaped(out, &code.lang);
+            out.push('"');
+        }
+        out.push('>');
+        push_escaped(out, &code.code);
+        out.push_str("</code></pre>");
+    }
+
+    fn render_list(&mut self, list: &List, out: &mut String) {
+        let tag = match list.list_type {
+            ListTypes::Unordered => "ul",
+            ListTypes::Ordered => "ol",
+        };
+        out.push('<');
+        out.push_str(tag);
+        out.push('>');
+        list.body
+            .iter()
+            .for_each(|node| self.render_list_item(node, out));
+        out.push_str("</");
+        out.push_str(tag);
+        out.push('>');
+    }
+
+    fn render_list_item(&mut self, list_item: &ListItem, out: &mut String) {
+        out.push_str("<li>");
+        match list_item.checked {
+            Some(true) => out.push_str("<input type=\"checkbox\" disabled checked />"),
+            Some(false) => out.push_str("<input type=\"checkbox\" disabled />"),
+            None => {}
+        }
+        list_item
+            .text
+            .iter()
+            .for_each(|node| self.render_paragraph_node(node, out));
+        if let Some(nested_list) = &list_item.nested_list {
+            self.render_list(nested_list, out);
+        }
+        out.push_str("</li>");
+    }
+
+    fn render_highlight(&mut self, highlight: &Highlight, out: &mut String) {
+        out.push_str("<div class=\"highlight\">");
+        if let Some(icon) = &highlight.icon {
+            out.push_str("<div class=\"icon ");
+            push_escaped(out, icon);
+            out.push_str("\"></div>");
+        }
+        out.push_str("<div class=\"body\">");
+        if let Some(title) = &highlight.title {
+            push_element(out, "h3", title);
+        }
+        highlight
+            .body
+            .iter()
+            .for_each(|node| self.render_paragraph(node, out));
+        out.push_str("</div></div>");
+    }
+
+    fn render_thematic_break(&mut self, _thematic_break: &ThematicBreak, out: &mut String) {
+        out.push_str("<hr />");
+    }
+
+    fn render_embed(&mut self, embed: &Embed, out: &mut String) {
+        out.push_str("<iframe class=\"");
+        push_escaped(out, &embed.kind);
+        out.push_str("\" src=\"");
+        let args = embed.arguments();
+        push_escaped(out, args.positional.first().map_or("", |src| src.as_ref()));
+        out.push_str("\"></iframe>");
+    }
+
+    fn render_custom(&mut self, custom: &Custom, out: &mut String) {
+        out.push_str("<div class=\"");
+        push_escaped(out, &custom.name);
+        out.push_str("\">");
+        if custom.nodes.is_empty() {
+            push_escaped(out, &custom.body);
+        } else {
+            custom
+                .nodes
+                .iter()
+                .for_each(|node| self.render_yamd_node(node, out));
+        }
+        out.push_str("</div>");
+    }
+
+    fn render_collapsible(&mut self, collapsible: &Collapsible, out: &mut String) {
+        out.push_str("<div class=\"collapsible\"><input type=\"checkbox\" id=\"");
+        push_escaped(out, &collapsible.title);
+        out.push_str("\" /><label for=\"");
+        push_escaped(out, &collapsible.title);
+        out.push_str("\">");
+        push_escaped(out, &collapsible.title);
+        out.push_str("</label><div class=\"body\">");
+        collapsible
+            .body
+            .iter()
+            .for_each(|node| self.render_yamd_node(node, out));
+        out.push_str("</div></div>");
+    }
+
+    fn render_table(&mut self, table: &Table, out: &mut String) {
+        out.push_str("<table><thead><tr>");
+        for (column, cell) in table.header.cells.iter().enumerate() {
+            push_cell_start(out, "th", table.alignment(column));
+            cell.body
+                .iter()
+                .for_each(|node| self.render_paragraph_node(node, out));
+            out.push_str("</th>");
+        }
+        out.push_str("</tr></thead>");
+        if !table.body.is_empty() {
+            out.push_str("<tbody>");
+            for row in &table.body {
+                out.push_str("<tr>");
+                for (column, cell) in row.cells.iter().enumerate() {
+                    push_cell_start(out, "td", table.alignment(column));
+                    cell.body
+                        .iter()
+                        .for_each(|node| self.render_paragraph_node(node, out));
+                    out.push_str("</td>");
+                }
+                out.push_str("</tr>");
+            }
+            out.push_str("</tbody>");
+        }
+        out.push_str("</table>");
+    }
+
+    fn render_footnote_reference(
+        &mut self,
+        footnote_reference: &FootnoteReference,
+        out: &mut String,
+    ) {
+        out.push_str("<sup><a href=\"#fn-");
+        push_escaped(out, &footnote_reference.label);
+        out.push_str("\" id=\"fnref-");
+        push_escaped(out, &footnote_reference.label);
+        out.push_str("\">");
+        push_escaped(out, &footnote_reference.label);
+        out.push_str("</a></sup>");
+    }
+
+    fn render_footnote_definition(
+        &mut self,
+        footnote_definition: &FootnoteDefinition,
+        out: &mut String,
+    ) {
+        out.push_str("<div class=\"footnote\" id=\"fn-");
+        push_escaped(out, &footnote_definition.label);
+        out.push_str("\">");
+        push_element(out, "sup", &footnote_definition.label);
+        footnote_definition
+            .body
+            .iter()
+            .for_each(|node| self.render_paragraph_node(node, out));
+        out.push_str("</div>");
+    }
+
+    fn render_display_math(&mut self, display_math: &DisplayMath, out: &mut String) {
+        out.push_str("<div class=\"math math-display\">");
+        push_escaped(out, &display_math.body);
+        out.push_str("</div>");
+    }
+}
+
+/// [Renderer] that uses default implementation for every node, except that headings of a
+/// rendered [Yamd] get ids from [Yamd::heading_ids].
+#[derive(Debug, Default, Clone)]
+pub struct HtmlRenderer {
+    /// Ids of the headings of the last rendered [Yamd] by address.
+    heading_ids: HashMap<usize, String>,
+}
+
+impl HtmlRenderer {
+    /// Takes ids of the headings of `yamd`, see [Yamd::heading_ids].
+    pub(crate) fn set_heading_ids(&mut self, yamd: &Yamd) {
+        self.heading_ids = yamd
+            .heading_ids()
+            .into_iter()
+            .map(|(heading, id)| (heading as *const Heading as usize, id))
+            .collect();
+    }
+}
+
+impl Renderer for HtmlRenderer {
+    fn render_yamd(&mut self, yamd: &Yamd, out: &mut String) {
+        self.set_heading_ids(yamd);
+        yamd.body
+            .iter()
+            .for_each(|node| self.render_yamd_node(node, out));
+    }
+
+    fn heading_id(&mut self, heading: &Heading) -> Option<String> {
+        let id = self.heading_ids.get(&(heading as *const Heading as usize));
+        Some(id.cloned().unwrap_or_else(|| heading.slug()))
+    }
+}
+
+macro_rules! to_html {
+    ($($node:ty => $method:ident),+ $(,)?) => {
+        $(
+            impl ToHtml for $node {
+                fn write_html(&self, out: &mut String) {
+                    HtmlRenderer::default().$method(self, out);
+                }
+            }
+        )+
+    };
+}
+
+to_html!(
+    Yamd => render_yamd,
+    YamdNodes => render_yamd_node,
+    Paragraph => render_paragraph,
+    ParagraphNodes => render_paragraph_node,
+    Heading => render_heading,
+    HeadingNodes => render_heading_node,
+    Anchor => render_anchor,
+    Bold => render_bold,
+    BoldNodes => render_bold_node,
+    Italic => render_italic,
+    Strikethrough => render_strikethrough,
+    Emphasis => render_emphasis,
+    CodeSpan => render_code_span,
+    Image => render_image,
+    Images => render_images,
+    Code => render_code,
+    InlineMath => render_inline_math,
+    DisplayMath => render_display_math,
+    List => render_list,
+    ListItem => render_list_item,
+    Highlight => render_highlight,
+    ThematicBreak => render_thematic_break,
+    Embed => render_embed,
+    Custom => render_custom,
+    Collapsible => render_collapsible,
+    Table => render_table,
+    FootnoteReference => render_footnote_reference,
+    FootnoteDefinition => render_footnote_definition,
+);
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        deserialize,
+        html::{Renderer, ToHtml, push_escaped, to_html},
+        nodes::{
+            Anchor, Bold, Code, CodeSpan, Collapsible, Custom, Embed, Emphasis, Heading, Highlight,
+            Image, Images, Italic, List, ListItem, ListTypes, Paragraph, Strikethrough, Table,
+            TableAlignment, TableCell, TableRow, ThematicBreak, Yamd,
+        },
+    };
+
+    #[test]
+    fn paragraph() {
+        let paragraph = Paragraph::new(vec![
+            String::from("a ").into(),
+            Bold::new(vec![
+                Italic::new("i").into(),
+                String::from(" b ").into(),
+                Strikethrough::new("s").into(),
+            ])
+            .into(),
+            Emphasis::new("e").into(),
+            CodeSpan::new("c").into(),
+            Anchor::new("t", "u").into(),
+        ]);
+        assert_eq!(
+            paragraph.to_html(),
+            "<p>a <b><i>i</i> b <s>s</s></b><em>e</em><code>c</code><a href=\"u\">t</a></p>"
+        );
+    }
+
+    #[test]
+    fn heading() {
+        let heading = Heading::new(
+            3,
+            vec![String::from("a ").into(), Anchor::new("b", "#").into()],
+        );
+        assert_eq!(
+            heading.to_html(),
+            "<h3 id=\"a-b\">a <a href=\"#\">b</a></h3>"
+        );
+        assert_eq!(
+            Heading::new(1, vec![String::from("!").into()]).to_html(),
+            "<h1>!</h1>"
+        );
+    }
+
+    #[test]
+    fn heading_ids_match_toc() {
+        let yamd = deserialize("# Intro\n\n## Intro\n\n## Intro-1\n\n{% more\n### Intro\n%}");
+        let html = to_html(&yamd);
+        let toc = yamd.toc(1..=6).to_list().unwrap().to_html();
+        let links = toc
+            .split("href=\"#")
+            .skip(1)
+            .map(|rest| &rest[..rest.find('"').unwrap()])
+            .collect::<Vec<_>>();
+        assert_eq!(links, vec!["intro", "intro-1", "intro-1-1", "intro-2"]);
+        for link in links {
+            assert!(
+                html.contains(&format!(" id=\"{link}\">")),
+                "{link} in {html}"
+            );
+        }
+    }
+
+    #[test]
+    fn code() {
+        assert_eq!(
+            Code::new("rust", "let a = 42;").to_html(),
+            "<pre><code class=\"rust\">let a = 42;</code></pre>"
+        );
+        assert_eq!(
+            Code::new("", "a < b").to_html(),
+            "<pre><code>a &lt; b</code></pre>"
+        );
+    }
+
+    #[test]
+    fn list() {
+        let list = List::new(
+            ListTypes::Unordered,
+            0,
+            vec![ListItem::new(
+                vec![String::from("a").into()],
+                Some(List::new(
+                    ListTypes::Ordered,
+                    1,
+                    vec![ListItem::new(vec![String::from("b").into()], None)],
+                )),
+            )],
+        );
+        assert_eq!(list.to_html(), "<ul><li>a<ol><li>b</li></ol></li></ul>");
+    }
+
+    #[test]
+    fn task_list() {
+        let list = List::new(
+            ListTypes::Unordered,
+            0,
+            vec![
+                ListItem::task(true, vec![String::from("a").into()], None),
+                ListItem::task(false, vec![String::from("b").into()], None),
+            ],
+        );
+        assert_eq!(
+            list.to_html(),
+            "<ul><li><input type=\"checkbox\" disabled checked />a</li><li><input type=\"checkbox\" disabled />b</li></ul>"
+        );
+    }
+
+    #[test]
+    fn images() {
+        let images = Images::new(vec![Image::new("a", "u"), Image::new("b\"", "v")]);
+        assert_eq!(
+            images.to_html(),
+            "<div class=\"images\"><img src=\"u\" alt=\"a\" /><img src=\"v\" alt=\"b&quot;\" /></div>"
+        );
+    }
+
+    #[test]
+    fn highlight() {
+        let highlight = Highlight::new(
+            Some("t"),
+            Some("warning"),
+            vec![Paragraph::new(vec![String::from("b").into()])],
+        );
+        assert_eq!(
+            highlight.to_html(),
+            "<div class=\"highlight\"><div class=\"icon warning\"></div><div class=\"body\"><h3>t</h3><p>b</p></div></div>"
+        );
+        let highlight = Highlight::new::<String, String>(None, None, vec![]);
+        assert_eq!(
+            highlight.to_html(),
+            "<div class=\"highlight\"><div class=\"body\"></div></div>"
+        );
+    }
+
+    #[test]
+    fn collapsible() {
+        let collapsible = Collapsible::new("t", vec![ThematicBreak::new().into()]);
+        assert_eq!(
+            collapsible.to_html(),
+            "<div class=\"collapsible\"><input type=\"checkbox\" id=\"t\" /><label for=\"t\">t</label><div class=\"body\"><hr /></div></div>"
+        );
+    }
+
+    #[test]
+    fn embed() {
+        assert_eq!(
+            Embed::new("youtube", "https://www.youtube.com/embed/dQw4w9WgXcQ").to_html(),
+            "<iframe class=\"youtube\" src=\"https://www.youtube.com/embed/dQw4w9WgXcQ\"></iframe>"
+        );
+        assert_eq!(
+            Embed::new("link", "https://a.b/?c\\|d | start=30").to_html(),
+            "<iframe class=\"link\" src=\"https://a.b/?c|d\"></iframe>"
+        );
+    }
+
+    #[test]
+    fn custom() {
+        assert_eq!(
+            Custom::new("note", ":::", "\n<b>\n", ":::").to_html(),
+            "<div class=\"note\">\n&lt;b&gt;\n</div>"
+        );
+        assert_eq!(
+            Custom::new("note", ":::", "\n---\n", ":::")
+                .with_nodes(vec![ThematicBreak::new().into()])
+                .to_html(),
+            "<div class=\"note\"><hr /></div>"
+        );
+    }
+
+    #[test]
+    fn table() {
+        let table = Table::new(
+            TableRow::new(vec![
+                TableCell::new(vec![String::from("a").into()]),
+                TableCell::new(vec![Bold::new(vec![String::from("b").into()]).into()]),
+            ]),
+            vec![TableAlignment::None, TableAlignment::Center],
+            vec![TableRow::new(vec![
+                TableCell::new(vec![String::from("<c>").into()]),
+                TableCell::new(vec![]),
+            ])],
+        );
+        assert_eq!(
+            table.to_html(),
+            "<table><thead><tr><th>a</th><th style=\"text-align: center\"><b>b</b></th></tr></thead><tbody><tr><td>&lt;c&gt;</td><td style=\"text-align: center\"></td></tr></tbody></table>"
+        );
+        let table = Table::new(
+            TableRow::new(vec![TableCell::new(vec![])]),
+            vec![TableAlignment::None],
+            vec![],
+        );
+        assert_eq!(
+            table.to_html(),
+            "<table><thead><tr><th></th></tr></thead></table>"
+        );
+    }
+
+    #[test]
+    fn footnotes() {
+        let yamd = deserialize("a[^1]\n\n[^1]: **b**");
+        assert_eq!(
+            to_html(&yamd),
+            "<p>a<sup><a href=\"#fn-1\" id=\"fnref-1\">1</a></sup></p><div class=\"footnote\" id=\"fn-1\"><sup>1</sup><b>b</b></div>"
+        );
+    }
+
+    #[test]
+    fn math() {
+        let yamd = deserialize("# $a<b$\n\n$$\n\\frac{1}{2}\n$$");
+        assert_eq!(
+            to_html(&yamd),
+            "<h1 id=\"ab\"><span class=\"math math-inline\">a&lt;b</span></h1><div class=\"math math-display\">\\frac{1}{2}</div>"
+        );
+    }
+
+    #[test]
+    fn escapes_text_and_attributes() {
+        let yamd = deserialize("<script>'&'</script>\n\n[\"x\"](\"><script>)");
+        assert_eq!(
+            to_html(&yamd),
+            "<p>&lt;script&gt;&#39;&amp;&#39;&lt;/script&gt;</p><p><a href=\"&quot;&gt;&lt;script&gt;\">&quot;x&quot;</a></p>"
+        );
+    }
+
+    #[test]
+    fn metadata_is_not_rendered() {
+        let yamd = Yamd::new(Some("title: a".into()), vec![ThematicBreak::new().into()]);
+        assert_eq!(to_html(&yamd), "<hr />");
+    }
+
+    struct Gallery;
+
+    impl Renderer for Gallery {
+        fn render_images(&mut self, images: &Images, out: &mut String) {
+            out.push_str("<figure>");
+            images
+                .body
+                .iter()
+                .for_each(|image| self.render_image(image, out));
+            out.push_str("</figure>");
+        }
+
+        fn render_image(&mut self, image: &Image, out: &mut String) {
+            out.push_str("<img data-src=\"");
+            push_escaped(out, &image.src);
+            out.push_str("\" />");
+        }
+
+        fn render_text(&mut self, text: &str, out: &mut String) {
+            push_escaped(out, &text.to_uppercase());
+        }
+    }
+
+    #[test]
+    fn renderer_overrides_apply_to_nested_nodes() {
+        let yamd = deserialize("![a](u)\n![b](v)\n\n{% t\n![c](w)\n\n- **bold** and text\n%}");
+        assert_eq!(
+            Gallery.render(&yamd),
+            "<figure><img data-src=\"u\" /><img data-src=\"v\" /></figure><div class=\"collapsible\"><input type=\"checkbox\" id=\"t\" /><label for=\"t\">t</label><div class=\"body\"><img data-src=\"w\" /><ul><li><b>BOLD</b> AND TEXT</li></ul></div></div>"
+        );
+    }
+}
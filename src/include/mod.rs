@@ -0,0 +1,402 @@
+//! Including documents into other documents.
+//!
+//! An include is an [Embed] of kind `include` with a path: `{{include|path.yamd}}`. [Includes]
+//! loads the document at the path with an [IncludeResolver] and puts its [body](Yamd::body) in
+//! place of the include, the metadata of an included document is dropped. Includes in included
+//! documents and in [Collapsible]s are expanded too.
+//!
+//! Paths are passed to the resolver without `.` components and empty segments, joined with `/`, so
+//! `./a.yamd` and `a.yamd` are the same document. Paths are relative to the resolver, not to the
+//! including document: `{{include|b.yamd}}` in `docs/a.yamd` includes `b.yamd`, not
+//! `docs/b.yamd`. [Directory] reads them from a directory and a [HashMap] returns its values. A
+//! document that includes itself, directly or through other documents, and includes nested deeper
+//! than [max_depth](Includes::max_depth) are errors.
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use yamd::include::{IncludeErrorKind, Includes};
+//!
+//! let documents = HashMap::from([
+//!     ("note.yamd".to_string(), "!! Note\nSnippet\n!!".to_string()),
+//!     ("loop.yamd".to_string(), "{{include|./loop.yamd}}".to_string()),
+//! ]);
+//! let includes = Includes::new(documents);
+//!
+//! let yamd = includes.deserialize("# Page\n\n{{include|note.yamd}}").unwrap();
+//! assert_eq!(yamd.to_string(), "# Page\n\n!! Note\nSnippet\n!!");
+//!
+//! let error = includes.deserialize("# Page\n\n{{include|loop.yamd}}").unwrap_err();
+//! assert_eq!(error.documents, vec!["loop.yamd"]);
+//! assert_eq!(error.kind, IncludeErrorKind::Cycle { path: "loop.yamd".to_string() });
+//! assert_eq!(
+//!     error.to_string(),
+//!     "`loop.yamd` includes itself at line 1, column 1 of loop.yamd"
+//! );
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::{
+    deserialize_with_spans,
+    nodes::{Collapsible, Embed, Yamd, YamdNodes},
+    op::{Span, SpanNode},
+};
+
+/// Loads documents for [Includes].
+pub trait IncludeResolver {
+    /// Returns the source of the document at `path`, or a message that explains why it can't.
+    fn load(&self, path: &str) -> Result<String, String>;
+}
+
+/// Documents by path.
+impl IncludeResolver for HashMap<String, String> {
+    fn load(&self, path: &str) -> Result<String, String> {
+        self.get(path)
+            .cloned()
+            .ok_or_else(|| "document not found".to_string())
+    }
+}
+
+/// Reads documents from files in a directory. Paths are relative to the directory and can't leave
+/// it.
+#[derive(Debug, Clone)]
+pub struct Directory {
+    root: PathBuf,
+}
+
+impl Directory {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl IncludeResolver for Directory {
+    fn load(&self, path: &str) -> Result<String, String> {
+        let relative = Path::new(path);
+        if !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+        {
+            return Err("path is outside of the directory".to_string());
+        }
+        std::fs::read_to_string(self.root.join(relative)).map_err(|error| error.to_string())
+    }
+}
+
+/// Error of an include, see [Includes::deserialize].
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncludeError {
+    /// Paths of the included documents that lead to the include, outermost first. Empty if the
+    /// include is in the document passed to [Includes::deserialize].
+    pub documents: Vec<String>,
+    /// Location of the include in the last of the [documents](IncludeError::documents).
+    pub span: Span,
+    pub kind: IncludeErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IncludeErrorKind {
+    /// Include has no path.
+    MissingPath,
+    /// Resolver could not load the document.
+    Load { path: String, message: String },
+    /// Document includes itself, directly or through other documents.
+    Cycle { path: String },
+    /// Includes are nested deeper than [max_depth](Includes::max_depth).
+    TooDeep { max_depth: usize },
+}
+
+impl Display for IncludeErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IncludeErrorKind::MissingPath => write!(f, "include has no path"),
+            IncludeErrorKind::Load { path, message } => {
+                write!(f, "could not load `{path}`: {message}")
+            }
+            IncludeErrorKind::Cycle { path } => write!(f, "`{path}` includes itself"),
+            IncludeErrorKind::TooDeep { max_depth } => {
+                write!(f, "includes are nested deeper than {max_depth}")
+            }
+        }
+    }
+}
+
+impl Display for IncludeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.kind,
+            self.span.start.row + 1,
+            self.span.start.column + 1
+        )?;
+        match self.documents.last() {
+            Some(document) => write!(f, " of {document}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for IncludeError {}
+
+/// Expands includes with an [IncludeResolver], see [include](mod@crate::include).
+#[derive(Debug, Clone)]
+pub struct Includes<R> {
+    resolver: R,
+    max_depth: usize,
+}
+
+impl<R: IncludeResolver> Includes<R> {
+    pub fn new(resolver: R) -> Self {
+        Self {
+            resolver,
+            max_depth: 16,
+        }
+    }
+
+    /// Sets how deep includes can be nested, 16 by default. With 0, any include is an error.
+    ///
+    /// Only the depth is limited, a document can be included any number of times. A document that
+    /// includes the next one twice, nested `max_depth` deep, expands to 2^`max_depth` copies of the
+    /// last one, so lower the limit for documents that are not trusted.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Deserializes `source` and replaces every include with the body of the included document.
+    ///
+    /// Returns the first error, if any.
+    pub fn deserialize(&self, source: &str) -> Result<Yamd, Box<IncludeError>> {
+        self.expand(source, &mut Vec::new())
+    }
+
+    /// `documents` are paths of the included documents that lead to `source`.
+    fn expand(&self, source: &str, documents: &mut Vec<String>) -> Result<Yamd, Box<IncludeError>> {
+        let (yamd, spans) = deserialize_with_spans(source);
+        Ok(Yamd {
+            body: self.expand_nodes(yamd.body, &spans.body, documents)?,
+            ..yamd
+        })
+    }
+
+    fn expand_nodes(
+        &self,
+        nodes: Vec<YamdNodes>,
+        spans: &[SpanNode],
+        documents: &mut Vec<String>,
+    ) -> Result<Vec<YamdNodes>, Box<IncludeError>> {
+        let mut expanded = Vec::with_capacity(nodes.len());
+        for (node, span) in nodes.into_iter().zip(spans) {
+            match node {
+                YamdNodes::Embed(embed) if embed.kind == "include" => {
+                    expanded.extend(self.include(&embed, &span.span, documents)?.body)
+                }
+                YamdNodes::Collapsible(collapsible) => expanded.push(
+                    Collapsible {
+                        title: collapsible.title,
+                        body: self.expand_nodes(collapsible.body, &span.children, documents)?,
+                    }
+                    .into(),
+                ),
+                node => expanded.push(node),
+            }
+        }
+        Ok(expanded)
+    }
+
+    fn include(
+        &self,
+        embed: &Embed,
+        span: &Span,
+        documents: &mut Vec<String>,
+    ) -> Result<Yamd, Box<IncludeError>> {
+        let error = |kind| {
+            Box::new(IncludeError {
+                documents: documents.clone(),
+                span: span.clone(),
+                kind,
+            })
+        };
+        let args = embed.arguments();
+        let Some(path) = args.positional.first().filter(|path| !path.is_empty()) else {
+            return Err(error(IncludeErrorKind::MissingPath));
+        };
+        let path = &normalize(path);
+        if documents.iter().any(|document| document == path) {
+            return Err(error(IncludeErrorKind::Cycle {
+                path: path.to_string(),
+            }));
+        }
+        if documents.len() >= self.max_depth {
+            return Err(error(IncludeErrorKind::TooDeep {
+                max_depth: self.max_depth,
+            }));
+        }
+        let source = self.resolver.load(path).map_err(|message| {
+            error(IncludeErrorKind::Load {
+                path: path.to_string(),
+                message,
+            })
+        })?;
+        documents.push(path.to_string());
+        let yamd = self.expand(&source, documents)?;
+        documents.pop();
+        Ok(yamd)
+    }
+}
+
+/// Drops `.` and empty segments of `path`, the result uses `/` as a separator on every platform.
+fn normalize(path: &str) -> String {
+    path.split('/')
+        .filter(|segment| !segment.is_empty() && *segment != ".")
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{deserialize, nodes::Paragraph};
+
+    fn includes(documents: &[(&str, &str)]) -> Includes<HashMap<String, String>> {
+        Includes::new(
+            documents
+                .iter()
+                .map(|(path, source)| (path.to_string(), source.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn body_is_spliced() {
+        let includes = includes(&[
+            ("a", "---\ntitle: a\n---\n\na1\n\n{{include|b}}\n\na2"),
+            ("b", "b1\n\nb2"),
+        ]);
+        let yamd = includes
+            .deserialize(
+                "---\ntitle: root\n---\n\n{% c\n{{include|a}}\n%}\n\n{{include|b}}\n\n{{other|b}}",
+            )
+            .unwrap();
+        assert_eq!(
+            yamd,
+            deserialize(
+                "---\ntitle: root\n---\n\n{% c\na1\n\nb1\n\nb2\n\na2\n%}\n\nb1\n\nb2\n\n{{other|b}}"
+            )
+        );
+        // an include can be used more than once, as long as it does not include itself
+        assert_eq!(
+            includes
+                .deserialize("{{include|b}}\n{{include|b}}")
+                .unwrap()
+                .body,
+            vec![
+                Paragraph::new(vec!["b1".to_string().into()]).into(),
+                Paragraph::new(vec!["b2".to_string().into()]).into(),
+                Paragraph::new(vec!["b1".to_string().into()]).into(),
+                Paragraph::new(vec!["b2".to_string().into()]).into(),
+            ]
+        );
+    }
+
+    #[test]
+    fn errors_point_to_the_include() {
+        let includes = includes(&[
+            ("a", "text\n\n{% c\n{{include|b}}\n%}"),
+            ("b", "{{include|./a}}"),
+        ]);
+        let error = includes.deserialize("# a\n\n{{include|a}}").unwrap_err();
+        assert_eq!(error.documents, vec!["a", "b"]);
+        assert_eq!(error.span.range, 0..15);
+        assert_eq!(error.kind, IncludeErrorKind::Cycle { path: "a".into() });
+
+        let error = includes.deserialize("# a\n\n{{include|c}}").unwrap_err();
+        assert_eq!(error.documents, Vec::<String>::new());
+        assert_eq!((error.span.start.row, error.span.start.column), (2, 0));
+        assert_eq!(
+            error.to_string(),
+            "could not load `c`: document not found at line 3, column 1"
+        );
+
+        let error = includes.deserialize("{{include|./a}}").unwrap_err();
+        assert_eq!(error.documents, vec!["a", "b"]);
+        assert_eq!(error.kind, IncludeErrorKind::Cycle { path: "a".into() });
+
+        let error = includes.deserialize("{{include|./c/./d}}").unwrap_err();
+        assert_eq!(
+            error.kind,
+            IncludeErrorKind::Load {
+                path: "c/d".into(),
+                message: "document not found".into()
+            }
+        );
+
+        let error = includes.deserialize("{% c\n{{include|}}\n%}").unwrap_err();
+        assert_eq!(error.kind, IncludeErrorKind::MissingPath);
+        assert_eq!(error.span.range, 5..18);
+    }
+
+    #[test]
+    fn normalize_path() {
+        for (path, normalized) in [
+            ("a.yamd", "a.yamd"),
+            ("./a.yamd", "a.yamd"),
+            ("docs/./a.yamd", "docs/a.yamd"),
+            ("docs//a.yamd", "docs/a.yamd"),
+            ("docs/../a.yamd", "docs/../a.yamd"),
+        ] {
+            assert_eq!(normalize(path), normalized);
+        }
+    }
+
+    #[test]
+    fn max_depth() {
+        let includes = includes(&[("a", "{{include|b}}"), ("b", "b")]);
+        assert!(
+            includes
+                .clone()
+                .max_depth(2)
+                .deserialize("{{include|a}}")
+                .is_ok()
+        );
+        let error = includes
+            .max_depth(1)
+            .deserialize("{{include|a}}")
+            .unwrap_err();
+        assert_eq!(error.documents, vec!["a"]);
+        assert_eq!(error.kind, IncludeErrorKind::TooDeep { max_depth: 1 });
+    }
+
+    #[test]
+    fn directory() {
+        let root = std::env::temp_dir().join(format!("yamd-include-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("snippets")).unwrap();
+        std::fs::write(root.join("snippets/a.yamd"), "# A").unwrap();
+        let includes = Includes::new(Directory::new(&root));
+
+        let yamd = includes.deserialize("{{include|snippets/a.yamd}}");
+        let escape = includes.deserialize("{{include|../a.yamd}}");
+        let missing = includes.deserialize("{{include|b.yamd}}");
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(yamd.unwrap(), deserialize("# A"));
+        assert_eq!(
+            escape.unwrap_err().kind,
+            IncludeErrorKind::Load {
+                path: "../a.yamd".into(),
+                message: "path is outside of the directory".into()
+            }
+        );
+        assert!(matches!(
+            missing.unwrap_err().kind,
+            IncludeErrorKind::Load { .. }
+        ));
+    }
+}
@@ -0,0 +1,257 @@
+//! Reparsing a document after an edit.
+//!
+//! [Document] keeps the source together with its [Yamd] and [Spans]. An
+//! [edit](Document::edit) reparses only the top level blocks that the changed text can affect,
+//! reuses the nodes of every other block, and shifts their spans.
+//!
+//! A block is affected if the parser read the changed text when it parsed the block. Usually that
+//! is the block that contains the edit, and the block before it if the edit is at the start of a
+//! block, since a line end can join them. A block that can contain [Terminator](crate::lexer::TokenKind::Terminator)s, like
+//! [Code](crate::nodes::Code), looks for its closing delimiter until the end of the document, so
+//! if it is not closed, it is parsed again after every edit that follows it.
+//!
+//! ```
+//! use yamd::incremental::Document;
+//!
+//! let mut document = Document::new("# Title\n\nfirst\n\nsecond");
+//! let replaced = document.edit(11..14, "ne");
+//! assert_eq!(replaced, 1..2);
+//! assert_eq!(document.source(), "# Title\n\nfine\n\nsecond");
+//! assert_eq!(document.spans().body[2].span.range, 15..21);
+//! assert_eq!(
+//!     (document.yamd(), document.spans()),
+//!     (&yamd::deserialize(document.source()), &yamd::deserialize_with_spans(document.source()).1)
+//! );
+//! ```
+
+use std::ops::Range;
+
+use crate::{
+    nodes::Yamd,
+    op::{Span, SpanNode, Spans, Units, document_ops, to_spans, to_yamd},
+};
+
+/// Source of a document with its [Yamd] and [Spans] that are updated on every
+/// [edit](Document::edit), see [incremental](crate::incremental).
+#[derive(Debug, Clone)]
+pub struct Document {
+    source: String,
+    yamd: Yamd,
+    spans: Spans,
+    /// Top level blocks in the source order, parsed in units that end where a line ends.
+    units: Vec<Unit>,
+}
+
+#[derive(Debug, Clone)]
+struct Unit {
+    /// End of the unit in the source. The unit starts where the previous one ends.
+    end: usize,
+    /// Row of `end`.
+    end_row: usize,
+    /// End of the text the unit was parsed from, `None` if it depends on the end of the source.
+    read: Option<usize>,
+    /// Number of nodes in [Yamd::body].
+    nodes: usize,
+}
+
+impl Document {
+    /// Parses `source`.
+    pub fn new(source: impl Into<String>) -> Self {
+        let mut document = Self {
+            source: source.into(),
+            yamd: Yamd::default(),
+            spans: Spans::default(),
+            units: Vec::new(),
+        };
+        document.edit(0..0, "");
+        document
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn yamd(&self) -> &Yamd {
+        &self.yamd
+    }
+
+    pub fn spans(&self) -> &Spans {
+        &self.spans
+    }
+
+    pub fn into_parts(self) -> (String, Yamd, Spans) {
+        (self.source, self.yamd, self.spans)
+    }
+
+    /// Replaces `range` of the source with `text` and reparses the affected top level blocks.
+    /// Returns the range of [Yamd::body] and [Spans::body] indexes with new nodes, every other node
+    /// is the same node as before the edit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds or does not lie on char boundaries, like
+    /// [String::replace_range].
+    pub fn edit(&mut self, range: Range<usize>, text: &str) -> Range<usize> {
+        let first = self
+            .units
+            .iter()
+            .position(|unit| unit.read.is_none_or(|read| range.start <= read + 1))
+            .unwrap_or(self.units.len());
+        let (restart, restart_row) = match first.checked_sub(1) {
+            Some(previous) => (self.units[previous].end, self.units[previous].end_row),
+            None => (0, 0),
+        };
+
+        let removed_rows = self.source[range.clone()].matches('\n').count();
+        self.source.replace_range(range.clone(), text);
+        let delta = text.len() as isize - range.len() as isize;
+        let row_delta = text.matches('\n').count() as isize - removed_rows as isize;
+
+        let input = &self.source[restart..];
+        let mut ops = Vec::new();
+        let mut yamd = Yamd::default();
+        let mut units = Vec::new();
+        let mut reused = self.units.len();
+        let mut old = first;
+        let mut row = restart_row;
+        let mut parsed = 0;
+        for unit in Units::new(input, first == 0) {
+            let unit_ops = ops.len();
+            ops.append(&mut document_ops(unit.metadata, unit.body));
+            let unit_yamd = to_yamd(&ops[unit_ops..], input);
+            if unit_yamd.metadata.is_some() {
+                yamd.metadata = unit_yamd.metadata;
+                yamd.metadata_delimiter = unit_yamd.metadata_delimiter;
+            }
+            row += input[parsed..unit.end].matches('\n').count();
+            parsed = unit.end;
+            units.push(Unit {
+                end: restart + unit.end,
+                end_row: row,
+                read: unit.read.map(|read| restart + read),
+                nodes: unit_yamd.body.len(),
+            });
+            yamd.body.extend(unit_yamd.body);
+
+            // the rest is the same once a unit ends where an old unit after the edit ended
+            let end = (restart + unit.end) as isize - delta;
+            while self
+                .units
+                .get(old)
+                .is_some_and(|unit| (unit.end as isize) < end)
+            {
+                old += 1;
+            }
+            if self
+                .units
+                .get(old)
+                .is_some_and(|unit| unit.end as isize == end && unit.end >= range.end)
+            {
+                reused = old + 1;
+                break;
+            }
+        }
+        let mut spans = to_spans(&ops, &input[..parsed]);
+        spans
+            .body
+            .iter_mut()
+            .for_each(|node| shift_node(node, restart as isize, restart_row as isize));
+
+        let nodes_start: usize = self.units[..first].iter().map(|unit| unit.nodes).sum();
+        let nodes_end: usize = self.units[..reused].iter().map(|unit| unit.nodes).sum();
+        let replaced = nodes_start..nodes_start + yamd.body.len();
+
+        for unit in &mut self.units[reused..] {
+            unit.end = unit.end.wrapping_add_signed(delta);
+            unit.end_row = unit.end_row.wrapping_add_signed(row_delta);
+            unit.read = unit.read.map(|read| read.wrapping_add_signed(delta));
+        }
+        for node in &mut self.spans.body[nodes_end..] {
+            shift_node(node, delta, row_delta);
+        }
+        if first == 0 {
+            self.yamd.metadata = yamd.metadata;
+            self.yamd.metadata_delimiter = yamd.metadata_delimiter;
+            self.spans.metadata = spans.metadata;
+        }
+        self.units.splice(first..reused, units);
+        self.yamd.body.splice(nodes_start..nodes_end, yamd.body);
+        self.spans.body.splice(nodes_start..nodes_end, spans.body);
+        replaced
+    }
+}
+
+fn shift_node(node: &mut SpanNode, bytes: isize, rows: isize) {
+    shift(&mut node.span, bytes, rows);
+    node.children
+        .iter_mut()
+        .for_each(|child| shift_node(child, bytes, rows));
+}
+
+fn shift(span: &mut Span, bytes: isize, rows: isize) {
+    span.range =
+        span.range.start.wrapping_add_signed(bytes)..span.range.end.wrapping_add_signed(bytes);
+    for position in [&mut span.start, &mut span.end] {
+        position.byte_index = position.byte_index.wrapping_add_signed(bytes);
+        position.row = position.row.wrapping_add_signed(rows);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::deserialize_with_spans;
+
+    fn assert_parsed(document: &Document) {
+        let (yamd, spans) = deserialize_with_spans(document.source());
+        assert_eq!((document.yamd(), document.spans()), (&yamd, &spans));
+    }
+
+    #[test]
+    fn only_affected_blocks_are_reparsed() {
+        let mut document = Document::new("a\n\nb\n\nc\n\nd");
+        // the block before the edit is parsed again, the edit can join it with the next one
+        assert_eq!(document.edit(6..7, "cc"), 1..3);
+        assert_eq!(document.source(), "a\n\nb\n\ncc\n\nd");
+        assert_parsed(&document);
+        assert_eq!(document.edit(5..6, ""), 1..2);
+        assert_eq!(document.source(), "a\n\nb\ncc\n\nd");
+        assert_parsed(&document);
+        assert_eq!(document.edit(0..0, "# 🤔\n\n"), 0..2);
+        assert_parsed(&document);
+        assert_eq!(document.edit(0..document.source().len(), ""), 0..0);
+        assert_parsed(&document);
+    }
+
+    #[test]
+    fn unclosed_block_is_reparsed_after_edits_that_follow_it() {
+        let mut document = Document::new("a\n\n```\nb\n\nc\n\nd");
+        assert_eq!(document.edit(13..14, "e"), 1..4);
+        assert_parsed(&document);
+        assert_eq!(document.edit(10..10, "```\n\n"), 1..2);
+        assert_eq!(document.source(), "a\n\n```\nb\n\n```\n\nc\n\ne");
+        assert_parsed(&document);
+        assert_eq!(document.edit(18..19, "f"), 2..4);
+        assert_parsed(&document);
+    }
+
+    #[test]
+    fn metadata() {
+        let mut document = Document::new("---\na: b\n---\n\nc");
+        // nodes after the metadata stay the same
+        assert_eq!(document.edit(4..5, "d"), 0..0);
+        assert_eq!(document.yamd().metadata.as_deref(), Some("d: b"));
+        assert_parsed(&document);
+        assert_eq!(document.edit(0..3, "+++"), 0..1);
+        assert_eq!(document.yamd().metadata, None);
+        assert_parsed(&document);
+        assert_eq!(document.edit(9..12, "+++"), 0..0);
+        assert_eq!(
+            document.yamd().metadata_delimiter,
+            crate::nodes::MetadataDelimiter::Pluses
+        );
+        assert_parsed(&document);
+    }
+}
@@ -154,6 +154,7 @@ impl<'input> Lexer<'input> {
             '!' => self.take_while('!', TokenKind::Bang, position),
             '`' => self.take_while('`', TokenKind::Backtick, position),
             '+' => self.take_while('+', TokenKind::Plus, position),
+            '$' => self.take_while('$', TokenKind::Dollar, position),
             '[' => self.emit(self.to_token(TokenKind::LeftSquareBracket, position, 1)),
             ']' => self.emit(self.to_token(TokenKind::RightSquareBracket, position, 1)),
             '(' => self.emit(self.to_token(TokenKind::LeftParenthesis, position, 1)),
@@ -673,6 +674,14 @@ mod tests {
         )
     }
 
+    #[test]
+    fn dollar() {
+        assert_eq!(
+            Lexer::new("$$").collect::<Vec<_>>(),
+            vec![Token::new(TokenKind::Dollar, 0..2, Position::default())]
+        )
+    }
+
     #[test]
     fn strikethrough() {
         assert_eq!(
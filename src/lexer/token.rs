@@ -36,6 +36,8 @@ pub enum TokenKind {
     Backtick,
     /// One or more `+`
     Plus,
+    /// One or more `$`
+    Dollar,
     /// Exactly one `[`
     LeftSquareBracket,
     /// Exactly one `]`
@@ -70,6 +72,7 @@ impl Display for TokenKind {
             TokenKind::Bang => f.write_str("Bang"),
             TokenKind::Backtick => f.write_str("Backtick"),
             TokenKind::Plus => f.write_str("Plus"),
+            TokenKind::Dollar => f.write_str("Dollar"),
             TokenKind::LeftSquareBracket => f.write_str("LeftSquareBracket"),
             TokenKind::RightSquareBracket => f.write_str("RightSquareBracket"),
             TokenKind::LeftParenthesis => f.write_str("LeftParenthesis"),
@@ -82,7 +85,7 @@ impl Display for TokenKind {
 }
 
 /// The `Position` struct represents the position of a token in the input.
-#[derive(Clone, Debug, PartialEq, Default)]
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct Position {
     /// The byte index of the token in the input string.
     pub byte_index: usize,
@@ -138,6 +141,7 @@ mod tests {
         assert_eq!(TokenKind::Bang.to_string(), "Bang");
         assert_eq!(TokenKind::Backtick.to_string(), "Backtick");
         assert_eq!(TokenKind::Plus.to_string(), "Plus");
+        assert_eq!(TokenKind::Dollar.to_string(), "Dollar");
         assert_eq!(
             TokenKind::LeftSquareBracket.to_string(),
             "LeftSquareBracket"
@@ -25,13 +25,41 @@
 //!   suitable for walking, pattern-matching, or round-tripping back to markdown via
 //!   [`Display`](std::fmt::Display). The AST makes invalid nestings unrepresentable, and
 //!   `deserialize` is fuzz-tested for panic-freedom and property-tested for round-trip fidelity.
-//! - [`parse`] returns a flat `Vec<`[`Op`](op::Op)`>` of Start/End/Value events.
-//!   [`to_yamd`] promotes an event stream to the tree form. Fuzz-tested for panic-freedom
+//! - [`parse`] returns a flat `Vec<`[`Op`](op::Op)`>` of Start/End/Value events, and
+//!   [`parse_iter`] yields them lazily, block by block. [`to_yamd`] promotes an event stream to the tree form. Fuzz-tested for panic-freedom
 //!   (transitively, via `deserialize`); the AST's type-level invariants and round-trip property
 //!   do not apply at this layer.
 //!
-//! Rendering is out of scope; [`Yamd`] is an AST you walk and render however you
-//! like. With the `serde` feature enabled, the AST is also serde-serializable.
+//! [`deserialize_borrowed`] builds the same tree with text that borrows from the input instead of
+//! copying it, for when the tree is only read and then dropped.
+//!
+//! [`deserialize_with`], [`parse_with`] and [`parse_iter_with`] recognize only the constructs
+//! enabled in [`ParseOptions`](op::ParseOptions), for example only inline formatting in comments.
+//! Syntax of a disabled construct stays literal text. [`BlockExtension`](op::BlockExtension)s
+//! registered there parse blocks of your own syntax into [`Custom`](nodes::Custom) nodes, or into
+//! any other blocks.
+//!
+//! [`stream`] parses a document that arrives in chunks or from an [`io::Read`](std::io::Read), and
+//! returns top level blocks as soon as they are final.
+//!
+//! [`deserialize_with_spans`] additionally returns [`Spans`] with the source location of every
+//! node, for editor integrations and error reporting. [`incremental`] keeps both up to date while
+//! the source is edited, parsing again only the blocks an edit affects.
+//!
+//! [`Yamd`] is an AST you walk and render however you like. With the `html` feature enabled,
+//! [`html::to_html`] renders it following the "HTML equivalent" documented on every node. With the
+//! `serde` feature enabled, the AST is also serde-serializable.
+//!
+//! [`embed`] expands [`Embed`](nodes::Embed)s into nodes or HTML with resolvers registered per
+//! embed kind. [`include`](mod@include) replaces `{{include|path.yamd}}` embeds with the body of the document
+//! at the path, loaded from a directory or a map.
+//!
+//! [`metadata`] parses the frontmatter into a [`MetadataValue`](metadata::MetadataValue) tree, or,
+//! with the `serde` feature enabled, deserializes it into your own type.
+//!
+//! With the `commonmark` feature enabled, [`commonmark::from_commonmark`] converts CommonMark
+//! documents to YAMD and reports every construct it had to approximate or drop, and
+//! [`commonmark::to_commonmark`] writes YAMD as GitHub flavoured Markdown.
 //!
 //! # Difference from CommonMark
 //!
@@ -70,7 +98,8 @@
 //! | ``- \`one\n- two\` ``     | ``<ol><li>`one</li><li>two`</li><ol>``    |
 //!
 //! The reasoning: issues like this should be caught by tooling such as linters or language servers
-//! — that tooling doesn't exist yet.
+//! — see [`lint`]. [`parse_with_diagnostics`] reports constructs that fell back to text because
+//! their closing delimiter is missing.
 //!
 //! ## Nodes
 //!
@@ -80,15 +109,34 @@
 //!
 //! YAMD minimal supported Rust version is 1.87.
 
+use std::borrow::Cow;
+
+#[cfg(feature = "commonmark")]
+pub mod commonmark;
+pub mod embed;
+#[cfg(feature = "html")]
+pub mod html;
+pub mod include;
+pub mod incremental;
 #[deny(missing_docs, rustdoc::broken_intra_doc_links)]
 pub mod lexer;
+pub mod lint;
+pub mod metadata;
 pub mod nodes;
 pub mod op;
+pub mod stream;
+pub mod visit;
 
 #[doc(inline)]
 pub use nodes::Yamd;
 pub use op::parse;
+pub use op::parse_iter;
+pub use op::parse_iter_with;
+pub use op::parse_with;
+pub use op::parse_with_diagnostics;
+pub use op::to_borrowed_yamd;
 pub use op::to_yamd;
+pub use op::{Spans, to_spans};
 pub use op::{UnbalancedOpStream, try_to_yamd};
 
 /// Deserialize a string into a Yamd struct
@@ -103,6 +151,55 @@ pub fn deserialize(input: &str) -> Yamd {
     op::to_yamd(&ops, input)
 }
 
+/// Deserialize a string into a Yamd struct, recognizing only the constructs enabled in `options`,
+/// see [ParseOptions](op::ParseOptions).
+///
+/// # Example
+/// ```
+/// use yamd::{deserialize_with, op::ParseOptions};
+/// let input = "# header";
+/// let yamd = deserialize_with(input, ParseOptions::inline());
+/// // the text is escaped, so it stays a paragraph when deserialized with every construct enabled
+/// assert_eq!(yamd.to_string(), "\\# header");
+/// ```
+pub fn deserialize_with(input: &str, options: op::ParseOptions) -> Yamd {
+    let ops = op::parse_with(input, options);
+    op::to_yamd(&ops, input)
+}
+
+/// Deserialize a string into a Yamd struct with text that borrows from `input`, see
+/// [to_borrowed_yamd]. Saves an allocation per text node when the tree is only read.
+///
+/// # Example
+/// ```
+/// use std::borrow::Cow;
+/// use yamd::{deserialize_borrowed, nodes::{Heading, HeadingNodes, YamdNodes}};
+/// let input = "# header";
+/// let yamd = deserialize_borrowed(input);
+/// let YamdNodes::Heading(Heading { body, .. }) = &yamd.body[0] else { unreachable!() };
+/// assert!(matches!(&body[0], HeadingNodes::Text(Cow::Borrowed("header"))));
+/// ```
+pub fn deserialize_borrowed(input: &str) -> Yamd<Cow<'_, str>> {
+    let ops = op::parse(input);
+    op::to_borrowed_yamd(&ops, input)
+}
+
+/// Deserialize a string into a Yamd struct, along with source [Spans] of every node.
+///
+/// # Example
+/// ```
+/// use yamd::deserialize_with_spans;
+/// let input = "# header\n\ntext";
+/// let (yamd, spans) = deserialize_with_spans(input);
+/// assert_eq!(yamd.body.len(), spans.body.len());
+/// assert_eq!(spans.body[1].span.range, 10..14);
+/// assert_eq!(spans.body[1].span.start.row, 2);
+/// ```
+pub fn deserialize_with_spans(input: &str) -> (Yamd, Spans) {
+    let ops = op::parse(input);
+    (op::to_yamd(&ops, input), op::to_spans(&ops, input))
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+
+use crate::{
+    lint::{Context, Finding},
+    nodes::YamdNodes,
+};
+
+/// Reports headings with the same text as one of the previous headings.
+pub(super) fn check(ctx: &Context) -> Vec<Finding> {
+    let mut seen = HashMap::new();
+    let mut findings = vec![];
+    for (node, span) in ctx.blocks() {
+        let YamdNodes::Heading(heading) = node else {
+            continue;
+        };
+        let text = heading.text().trim().to_string();
+        match seen.get(&text) {
+            Some(first) => findings.push(Finding {
+                span: span.span.clone(),
+                message: format!("duplicate heading `{text}`, first defined on line {first}"),
+                suggestion: None,
+            }),
+            None => {
+                seen.insert(text, span.span.start.row + 1);
+            }
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::lint::{Rule, lint};
+
+    fn messages(input: &str) -> Vec<String> {
+        lint(input)
+            .into_iter()
+            .filter(|d| d.rule == Rule::DuplicateHeading)
+            .map(|d| d.message)
+            .collect()
+    }
+
+    #[test]
+    fn duplicate() {
+        assert_eq!(
+            messages("# Intro\n\n## Intro\n\n## [Intro](#intro)\n\n## Other"),
+            vec![
+                "duplicate heading `Intro`, first defined on line 1",
+                "duplicate heading `Intro`, first defined on line 1"
+            ]
+        );
+    }
+
+    #[test]
+    fn duplicate_in_collapsible() {
+        assert_eq!(
+            messages("## a\n\n{% t\n## a\n%}"),
+            vec!["duplicate heading `a`, first defined on line 1"]
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    lint::{Context, Finding},
+    nodes::{Anchor, HeadingNodes, List, ParagraphNodes, YamdNodes},
+    op::SpanNode,
+};
+
+/// Reports anchors with empty url.
+pub(super) fn check(ctx: &Context) -> Vec<Finding> {
+    let mut anchors = vec![];
+    for (node, span) in ctx.blocks() {
+        match node {
+            YamdNodes::Paragraph(paragraph) => {
+                paragraph_anchors(&paragraph.body, &span.children, &mut anchors)
+            }
+            YamdNodes::Heading(heading) => {
+                for (node, span) in heading.body.iter().zip(&span.children) {
+                    if let HeadingNodes::Anchor(anchor) = node {
+                        anchors.push((anchor, span));
+                    }
+                }
+            }
+            YamdNodes::List(list) => list_anchors(list, span, &mut anchors),
+            YamdNodes::Highlight(highlight) => {
+                for (paragraph, span) in highlight.body.iter().zip(&span.children) {
+                    paragraph_anchors(&paragraph.body, &span.children, &mut anchors);
+                }
+            }
+            YamdNodes::FootnoteDefinition(definition) => {
+                paragraph_anchors(&definition.body, &span.children, &mut anchors)
+            }
+            YamdNodes::Table(table) => {
+                let rows = std::iter::once(&table.header).chain(&table.body);
+                for (row, span) in rows.zip(&span.children) {
+                    for (cell, span) in row.cells.iter().zip(&span.children) {
+                        paragraph_anchors(&cell.body, &span.children, &mut anchors);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    anchors
+        .into_iter()
+        .filter(|(anchor, _)| anchor.url.trim().is_empty())
+        .map(|(anchor, span)| Finding {
+            span: span.span.clone(),
+            message: format!("anchor `{}` has empty url", anchor.text),
+            suggestion: Some(format!("[{}](url)", anchor.text)),
+        })
+        .collect()
+}
+
+fn paragraph_anchors<'a>(
+    nodes: &'a [ParagraphNodes],
+    spans: &'a [SpanNode],
+    anchors: &mut Vec<(&'a Anchor, &'a SpanNode)>,
+) {
+    for (node, span) in nodes.iter().zip(spans) {
+        if let ParagraphNodes::Anchor(anchor) = node {
+            anchors.push((anchor, span));
+        }
+    }
+}
+
+fn list_anchors<'a>(
+    list: &'a List,
+    span: &'a SpanNode,
+    anchors: &mut Vec<(&'a Anchor, &'a SpanNode)>,
+) {
+    for (item, item_span) in list.body.iter().zip(&span.children) {
+        paragraph_anchors(&item.text, &item_span.children, anchors);
+        if let (Some(nested), Some(nested_span)) = (&item.nested_list, item_span.children.last()) {
+            list_anchors(nested, nested_span, anchors);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::lint::{Rule, Severity, lint};
+
+    fn ranges(input: &str) -> Vec<std::ops::Range<usize>> {
+        lint(input)
+            .into_iter()
+            .filter(|d| d.rule == Rule::EmptyAnchorUrl)
+            .map(|d| d.span.range)
+            .collect()
+    }
+
+    #[test]
+    fn empty_url() {
+        let diagnostics = lint("see [docs]()");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[0].span.range, 4..12);
+        assert_eq!(diagnostics[0].message, "anchor `docs` has empty url");
+        assert_eq!(diagnostics[0].suggestion.as_deref(), Some("[docs](url)"));
+    }
+
+    #[test]
+    fn anchors_everywhere() {
+        assert_eq!(
+            ranges(
+                "# [a]()\n\n- [b](c)\n - [d]( )\n\n!!\n[e]()\n!!\n\n{% t\n[f]()\n%}\n\n| [g]() |\n\n[^h]: [i]()"
+            ),
+            vec![2..7, 21..27, 32..37, 47..52, 59..64, 74..79]
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{
+    lint::{Context, Finding},
+    nodes::YamdNodes,
+};
+
+/// Reports headings that are more than one level deeper than the previous heading.
+pub(super) fn check(ctx: &Context) -> Vec<Finding> {
+    let mut previous: Option<u8> = None;
+    let mut findings = vec![];
+    for (node, span) in ctx.blocks() {
+        let YamdNodes::Heading(heading) = node else {
+            continue;
+        };
+        if let Some(previous) = previous.filter(|previous| heading.level > previous + 1) {
+            findings.push(Finding {
+                span: span.span.clone(),
+                message: format!("heading level skipped from {previous} to {}", heading.level),
+                suggestion: Some(format!(
+                    "use `{}` or a shallower heading",
+                    "#".repeat(previous as usize + 1)
+                )),
+            });
+        }
+        previous = Some(heading.level);
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::lint::{Rule, lint};
+
+    fn messages(input: &str) -> Vec<String> {
+        lint(input)
+            .into_iter()
+            .filter(|d| d.rule == Rule::HeadingLevelSkip)
+            .map(|d| d.message)
+            .collect()
+    }
+
+    #[test]
+    fn skipped_level() {
+        let diagnostics = lint("# a\n\n### b");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].span.range, 5..10);
+        assert_eq!(diagnostics[0].message, "heading level skipped from 1 to 3");
+        assert_eq!(
+            diagnostics[0].suggestion.as_deref(),
+            Some("use `##` or a shallower heading")
+        );
+    }
+
+    #[test]
+    fn going_up_is_fine() {
+        assert_eq!(
+            messages("## a\n\n### b\n\n# c\n\n## d"),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn headings_in_collapsible() {
+        assert_eq!(
+            messages("# a\n\n{% t\n#### b\n%}"),
+            vec!["heading level skipped from 1 to 4"]
+        );
+    }
+}
@@ -0,0 +1,280 @@
+//! Linter for YAMD documents.
+//!
+//! YAMD resolves ambiguous input without errors: a delimiter without a closing pair becomes text,
+//! and a line that looks like a list item can end up inside the previous one. [`lint`] runs
+//! [rules](Rule) over the token stream, op stream and AST, and reports such places as
+//! [Diagnostic]s.
+//!
+//! Example:
+//!
+//! ```rust
+//! use yamd::lint::{Config, Rule, Severity, lint, lint_with};
+//!
+//! let input = "# Title\n\n### Skipped\n\n**unclosed";
+//! let diagnostics = lint(input);
+//! assert_eq!(diagnostics.len(), 2);
+//! assert_eq!(diagnostics[0].rule, Rule::HeadingLevelSkip);
+//! assert_eq!(diagnostics[1].rule, Rule::UnbalancedDelimiter);
+//! assert_eq!(diagnostics[1].span.start.row, 4);
+//!
+//! let config = Config::default()
+//!     .severity(Rule::HeadingLevelSkip, None)
+//!     .severity(Rule::UnbalancedDelimiter, Some(Severity::Error));
+//! let diagnostics = lint_with(input, &config);
+//! assert_eq!(diagnostics.len(), 1);
+//! assert_eq!(diagnostics[0].severity, Severity::Error);
+//! ```
+
+mod duplicate_heading;
+mod empty_anchor_url;
+mod heading_level_skip;
+mod swallowed_list_item;
+mod unbalanced_delimiter;
+
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{
+    lexer::{Lexer, Token},
+    nodes::{Yamd, YamdNodes},
+    op::{self, LineIndex, Op, Span, SpanNode, Spans},
+};
+
+/// Lint rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    /// `**` or `~~` without a closing pair, rendered as text.
+    UnbalancedDelimiter,
+    /// Line inside a list item that looks like a list item of its own.
+    SwallowedListItem,
+    /// Heading more than one level deeper than the previous heading.
+    HeadingLevelSkip,
+    /// Anchor with empty [url](crate::nodes::Anchor::url).
+    EmptyAnchorUrl,
+    /// Heading with the same text as one of the previous headings.
+    DuplicateHeading,
+}
+
+impl Rule {
+    /// All rules, in the order they are run.
+    pub const ALL: [Rule; 5] = [
+        Rule::UnbalancedDelimiter,
+        Rule::SwallowedListItem,
+        Rule::HeadingLevelSkip,
+        Rule::EmptyAnchorUrl,
+        Rule::DuplicateHeading,
+    ];
+
+    /// Returns kebab-case name of the rule.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rule::UnbalancedDelimiter => "unbalanced-delimiter",
+            Rule::SwallowedListItem => "swallowed-list-item",
+            Rule::HeadingLevelSkip => "heading-level-skip",
+            Rule::EmptyAnchorUrl => "empty-anchor-url",
+            Rule::DuplicateHeading => "duplicate-heading",
+        }
+    }
+
+    /// Returns severity used by [Config::default].
+    pub fn default_severity(&self) -> Severity {
+        match self {
+            Rule::EmptyAnchorUrl => Severity::Error,
+            Rule::UnbalancedDelimiter
+            | Rule::SwallowedListItem
+            | Rule::HeadingLevelSkip
+            | Rule::DuplicateHeading => Severity::Warning,
+        }
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// A problem found by a [Rule].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub rule: Rule,
+    pub severity: Severity,
+    pub span: Span,
+    pub message: String,
+    /// How to fix the problem, if there is an obvious fix.
+    pub suggestion: Option<String>,
+}
+
+/// Enabled rules and their severities.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    severities: HashMap<Rule, Option<Severity>>,
+}
+
+impl Config {
+    /// Sets severity of the rule, `None` disables it.
+    pub fn severity(mut self, rule: Rule, severity: Option<Severity>) -> Self {
+        self.severities.insert(rule, severity);
+        self
+    }
+
+    /// Returns severity of the rule, `None` if the rule is disabled.
+    pub fn get(&self, rule: Rule) -> Option<Severity> {
+        self.severities.get(&rule).copied().flatten()
+    }
+}
+
+impl Default for Config {
+    /// Every rule is enabled with its [default severity](Rule::default_severity).
+    fn default() -> Self {
+        Self {
+            severities: Rule::ALL
+                .iter()
+                .map(|rule| (*rule, Some(rule.default_severity())))
+                .collect(),
+        }
+    }
+}
+
+/// Everything rules can look at.
+struct Context<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    ops: Vec<Op>,
+    yamd: Yamd,
+    spans: Spans,
+    lines: LineIndex<'a>,
+}
+
+impl<'a> Context<'a> {
+    fn new(source: &'a str) -> Self {
+        let ops = op::parse(source);
+        Self {
+            source,
+            tokens: Lexer::new(source).collect(),
+            yamd: op::to_yamd(&ops, source),
+            spans: op::to_spans(&ops, source),
+            ops,
+            lines: LineIndex::new(source),
+        }
+    }
+
+    /// Returns every block with its span in document order, including blocks nested in
+    /// [Collapsible](crate::nodes::Collapsible).
+    fn blocks(&self) -> Vec<(&YamdNodes, &SpanNode)> {
+        fn collect<'n>(
+            nodes: &'n [YamdNodes],
+            spans: &'n [SpanNode],
+            out: &mut Vec<(&'n YamdNodes, &'n SpanNode)>,
+        ) {
+            for (node, span) in nodes.iter().zip(spans) {
+                out.push((node, span));
+                if let YamdNodes::Collapsible(collapsible) = node {
+                    collect(&collapsible.body, &span.children, out);
+                }
+            }
+        }
+        let mut out = Vec::new();
+        collect(&self.yamd.body, &self.spans.body, &mut out);
+        out
+    }
+}
+
+/// Problem reported by a rule, before [Config] is applied.
+struct Finding {
+    span: Span,
+    message: String,
+    suggestion: Option<String>,
+}
+
+/// Lints `input` with [default](Config::default) configuration.
+pub fn lint(input: &str) -> Vec<Diagnostic> {
+    lint_with(input, &Config::default())
+}
+
+/// Lints `input` with the rules enabled in `config`. Diagnostics are ordered by position.
+pub fn lint_with(input: &str, config: &Config) -> Vec<Diagnostic> {
+    let ctx = Context::new(input);
+    let mut diagnostics: Vec<Diagnostic> = Rule::ALL
+        .iter()
+        .filter_map(|rule| config.get(*rule).map(|severity| (*rule, severity)))
+        .flat_map(|(rule, severity)| {
+            let findings = match rule {
+                Rule::UnbalancedDelimiter => unbalanced_delimiter::check(&ctx),
+                Rule::SwallowedListItem => swallowed_list_item::check(&ctx),
+                Rule::HeadingLevelSkip => heading_level_skip::check(&ctx),
+                Rule::EmptyAnchorUrl => empty_anchor_url::check(&ctx),
+                Rule::DuplicateHeading => duplicate_heading::check(&ctx),
+            };
+            findings.into_iter().map(move |finding| Diagnostic {
+                rule,
+                severity,
+                span: finding.span,
+                message: finding.message,
+                suggestion: finding.suggestion,
+            })
+        })
+        .collect();
+    diagnostics.sort_by_key(|d| d.span.range.start);
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::lint::{Config, Rule, Severity, lint, lint_with};
+
+    #[test]
+    fn clean_document() {
+        assert_eq!(lint("# a\n\n## b\n\n- [c](d)\n - e\n\n**f**"), vec![]);
+    }
+
+    #[test]
+    fn diagnostics_are_ordered_by_position() {
+        let diagnostics = lint("[a]()\n\n# b\n\n### b");
+        let rules: Vec<Rule> = diagnostics.iter().map(|d| d.rule).collect();
+        assert_eq!(
+            rules,
+            vec![
+                Rule::EmptyAnchorUrl,
+                Rule::HeadingLevelSkip,
+                Rule::DuplicateHeading
+            ]
+        );
+    }
+
+    #[test]
+    fn config() {
+        let config = Config::default()
+            .severity(Rule::EmptyAnchorUrl, None)
+            .severity(Rule::DuplicateHeading, Some(Severity::Info));
+        assert_eq!(config.get(Rule::EmptyAnchorUrl), None);
+        assert_eq!(config.get(Rule::HeadingLevelSkip), Some(Severity::Warning));
+        let diagnostics = lint_with("[a]()\n\n# b\n\n# b", &config);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, Rule::DuplicateHeading);
+        assert_eq!(diagnostics[0].severity, Severity::Info);
+    }
+
+    #[test]
+    fn rule_names() {
+        let names: Vec<String> = Rule::ALL.iter().map(|r| r.to_string()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "unbalanced-delimiter",
+                "swallowed-list-item",
+                "heading-level-skip",
+                "empty-anchor-url",
+                "duplicate-heading"
+            ]
+        );
+    }
+}
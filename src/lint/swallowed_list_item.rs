@@ -0,0 +1,122 @@
+use crate::{
+    lint::{Context, Finding},
+    nodes::{List, ListTypes, YamdNodes},
+    op::SpanNode,
+};
+
+/// Reports lines inside list item text that look like list items of their own. It happens when a
+/// nested item is indented by more than one extra space, or when a list mixes `-` and `+`.
+pub(super) fn check(ctx: &Context) -> Vec<Finding> {
+    let mut findings = vec![];
+    for (node, span) in ctx.blocks() {
+        if let YamdNodes::List(list) = node {
+            check_list(ctx, list, span, &mut findings);
+        }
+    }
+    findings
+}
+
+fn check_list(ctx: &Context, list: &List, span: &SpanNode, findings: &mut Vec<Finding>) {
+    for (item, item_span) in list.body.iter().zip(&span.children) {
+        if let (Some(first), Some(last)) = (
+            item_span.children.first(),
+            item.text
+                .len()
+                .checked_sub(1)
+                .and_then(|i| item_span.children.get(i)),
+        ) {
+            let text = first.span.range.start..last.span.range.end;
+            check_text(ctx, list, text, findings);
+        }
+        if let (Some(nested), Some(nested_span)) = (&item.nested_list, item_span.children.last()) {
+            check_list(ctx, nested, nested_span, findings);
+        }
+    }
+}
+
+fn check_text(
+    ctx: &Context,
+    list: &List,
+    text: std::ops::Range<usize>,
+    findings: &mut Vec<Finding>,
+) {
+    let mut offset = text.start;
+    for line in ctx.source[text.clone()].split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        if start == text.start {
+            continue;
+        }
+        let marker = line.trim_start_matches(' ');
+        let indent = line.len() - marker.len();
+        let list_type = match marker.as_bytes() {
+            [b'-', b' ', ..] => ListTypes::Unordered,
+            [b'+', b' ', ..] => ListTypes::Ordered,
+            _ => continue,
+        };
+        let suggestion = if list_type != list.list_type {
+            "a list can not mix `-` and `+` markers, separate lists with an empty line".to_string()
+        } else {
+            format!(
+                "indent the item with {} or {} spaces, or escape the marker to keep it as text",
+                list.level,
+                list.level + 1
+            )
+        };
+        findings.push(Finding {
+            span: ctx.lines.span(start..start + indent + 1),
+            message: "line looks like a list item, but is part of the previous item".to_string(),
+            suggestion: Some(suggestion),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::lint::{Rule, lint};
+
+    fn findings(input: &str) -> Vec<(std::ops::Range<usize>, String)> {
+        lint(input)
+            .into_iter()
+            .filter(|d| d.rule == Rule::SwallowedListItem)
+            .map(|d| (d.span.range, d.suggestion.unwrap_or_default()))
+            .collect()
+    }
+
+    #[test]
+    fn mixed_markers() {
+        assert_eq!(
+            findings("- a\n+ b"),
+            vec![(
+                4..5,
+                "a list can not mix `-` and `+` markers, separate lists with an empty line"
+                    .to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn over_indented() {
+        assert_eq!(
+            findings("+ a\n  + b"),
+            vec![(
+                4..7,
+                "indent the item with 0 or 1 spaces, or escape the marker to keep it as text"
+                    .to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn nested_list() {
+        assert_eq!(findings("- a\n - b\n   - c").len(), 1);
+        assert_eq!(findings("- a\n - b\n  - c\n- d"), vec![]);
+    }
+
+    #[test]
+    fn escaped_marker() {
+        assert_eq!(findings("- a\n\\- b"), vec![]);
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{
+    lexer::TokenKind,
+    lint::{Context, Finding},
+    op::{Node, OpKind, op_ranges},
+};
+
+/// Reports `**` and `~~` tokens that ended up inside paragraph or bold text, which means the
+/// parser found no closing pair for them. List items and table cells are parsed as paragraphs, so
+/// their text is checked too.
+pub(super) fn check(ctx: &Context) -> Vec<Finding> {
+    let mut stack: Vec<&Node> = vec![];
+    let mut text = vec![];
+    for (op, range) in ctx.ops.iter().zip(op_ranges(&ctx.ops, ctx.source)) {
+        match &op.kind {
+            OpKind::Start(node) => stack.push(node),
+            OpKind::End(_) => {
+                stack.pop();
+            }
+            OpKind::Value => {
+                if matches!(stack.last(), Some(Node::Paragraph | Node::Bold)) {
+                    text.push(range);
+                }
+            }
+        }
+    }
+
+    ctx.tokens
+        .iter()
+        .filter(|t| matches!(t.kind, TokenKind::Star | TokenKind::Tilde) && t.range.len() == 2)
+        .filter(|t| {
+            let i = text.partition_point(|r| r.start <= t.range.start);
+            i > 0 && t.range.end <= text[i - 1].end
+        })
+        .map(|t| {
+            let delimiter = &ctx.source[t.range.clone()];
+            let escaped: String = delimiter.chars().flat_map(|c| ['\\', c]).collect();
+            Finding {
+                span: ctx.lines.span(t.range.clone()),
+                message: format!("`{delimiter}` has no closing pair and is rendered as text"),
+                suggestion: Some(format!(
+                    "add closing `{delimiter}`, or escape it as `{escaped}`"
+                )),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::lint::{Rule, lint};
+
+    fn ranges(input: &str) -> Vec<std::ops::Range<usize>> {
+        lint(input)
+            .into_iter()
+            .filter(|d| d.rule == Rule::UnbalancedDelimiter)
+            .map(|d| d.span.range)
+            .collect()
+    }
+
+    #[test]
+    fn unclosed_bold() {
+        let diagnostics = lint("a **b");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].span.range, 2..4);
+        assert_eq!(
+            diagnostics[0].message,
+            "`**` has no closing pair and is rendered as text"
+        );
+        assert_eq!(
+            diagnostics[0].suggestion.as_deref(),
+            Some("add closing `**`, or escape it as `\\*\\*`")
+        );
+    }
+
+    #[test]
+    fn unclosed_strikethrough_inside_bold() {
+        assert_eq!(ranges("**a ~~b**"), vec![4..6]);
+    }
+
+    #[test]
+    fn unclosed_in_list_item() {
+        assert_eq!(ranges("- a ~~b\n- **c**"), vec![4..6]);
+    }
+
+    #[test]
+    fn unclosed_in_table_cell() {
+        assert_eq!(
+            ranges("| a **b | c |\n|---|---|\n| ~~d | **e** |"),
+            vec![4..6, 26..28]
+        );
+    }
+
+    #[test]
+    fn balanced_escaped_and_code() {
+        assert_eq!(ranges("**a** ~~b~~ \\*\\*c `**`\n\n```\n**\n```"), vec![]);
+    }
+}
@@ -0,0 +1,405 @@
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+
+use crate::metadata::{
+    MetadataError, MetadataValue,
+    parser::{self, Key, Node, Tree},
+};
+
+/// Deserializes metadata into `T`, see [metadata](crate::metadata) for the supported syntax.
+///
+/// Errors point at the value that could not be deserialized. Plain scalars can be read as strings
+/// whatever type they resolve to, so `version: 1.0` can be deserialized into a `String`.
+///
+/// ```
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// struct Post {
+///     title: String,
+///     tags: Vec<String>,
+///     draft: Option<bool>,
+/// }
+///
+/// let post: Post = yamd::metadata::from_str("title: Yamd\ntags: [software, rust]").unwrap();
+/// assert_eq!(
+///     post,
+///     Post { title: "Yamd".to_string(), tags: vec!["software".into(), "rust".into()], draft: None }
+/// );
+///
+/// let error = yamd::metadata::from_str::<Post>("title: Yamd\ntags: rust").unwrap_err();
+/// assert_eq!((error.row, error.column), (1, 6));
+/// ```
+pub fn from_str<T: DeserializeOwned>(input: &str) -> Result<T, MetadataError> {
+    let node = parser::parse(input)?;
+    T::deserialize(&node)
+}
+
+impl de::Error for MetadataError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Self {
+            message: msg.to_string(),
+            row: 0,
+            column: 0,
+            located: false,
+        }
+    }
+}
+
+impl Node {
+    /// Attaches the position of this node to an error that has none yet.
+    fn locate<T>(&self, result: Result<T, MetadataError>) -> Result<T, MetadataError> {
+        result.map_err(|mut error| {
+            if !error.located {
+                error.row = self.row;
+                error.column = self.column;
+                error.located = true;
+            }
+            error
+        })
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &Node {
+    type Error = MetadataError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let result = match &self.tree {
+            Tree::Scalar { value, .. } => match value {
+                MetadataValue::Null => visitor.visit_unit(),
+                MetadataValue::Bool(value) => visitor.visit_bool(*value),
+                MetadataValue::Integer(value) => visitor.visit_i64(*value),
+                MetadataValue::Float(value) => visitor.visit_f64(*value),
+                MetadataValue::String(value) => visitor.visit_str(value),
+                MetadataValue::List(_) | MetadataValue::Map(_) => {
+                    unreachable!("parser produces scalars only")
+                }
+            },
+            Tree::List(items) => visitor.visit_seq(Seq(items.iter())),
+            Tree::Map(entries) => visitor.visit_map(Map {
+                entries: entries.iter(),
+                value: None,
+            }),
+        };
+        self.locate(result)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match &self.tree {
+            Tree::Scalar {
+                value: MetadataValue::Null,
+                ..
+            } => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match &self.tree {
+            Tree::Scalar {
+                plain: Some(plain),
+                value,
+            } if !value.is_null() => self.locate(visitor.visit_str(plain)),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match &self.tree {
+            // Empty metadata is a struct with default fields.
+            Tree::Scalar {
+                value: MetadataValue::Null,
+                ..
+            } => self.locate(visitor.visit_map(Map {
+                entries: [].iter(),
+                value: None,
+            })),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let result = match &self.tree {
+            Tree::Map(entries) if entries.len() == 1 => {
+                let (key, value) = &entries[0];
+                visitor.visit_enum(Enum {
+                    variant: &key.name,
+                    value: Some(value),
+                })
+            }
+            Tree::Scalar {
+                value: MetadataValue::String(variant),
+                ..
+            } => visitor.visit_enum(Enum {
+                variant,
+                value: None,
+            }),
+            _ => Err(de::Error::custom(
+                "expected a string or a map with a single key",
+            )),
+        };
+        self.locate(result)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char bytes byte_buf unit unit_struct
+        seq tuple tuple_struct map identifier ignored_any
+    }
+}
+
+struct Seq<'a>(std::slice::Iter<'a, Node>);
+
+impl<'de> SeqAccess<'de> for Seq<'_> {
+    type Error = MetadataError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        self.0.next().map(|node| seed.deserialize(node)).transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+struct Map<'a> {
+    entries: std::slice::Iter<'a, (Key, Node)>,
+    value: Option<&'a Node>,
+}
+
+impl<'de> MapAccess<'de> for Map<'_> {
+    type Error = MetadataError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.value = Some(value);
+        let node = Node {
+            tree: Tree::Scalar {
+                value: MetadataValue::String(key.name.clone()),
+                plain: None,
+            },
+            row: key.row,
+            column: key.column,
+        };
+        seed.deserialize(&node).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let value = self.value.take().expect("value is read after its key");
+        seed.deserialize(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+struct Enum<'a> {
+    variant: &'a str,
+    value: Option<&'a Node>,
+}
+
+impl<'de, 'a> EnumAccess<'de> for Enum<'a> {
+    type Error = MetadataError;
+    type Variant = Variant<'a>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let deserializer: de::value::StrDeserializer<'_, MetadataError> =
+            self.variant.into_deserializer();
+        Ok((seed.deserialize(deserializer)?, Variant(self.value)))
+    }
+}
+
+/// Value of an enum variant, `None` for a unit variant written as a string.
+struct Variant<'a>(Option<&'a Node>);
+
+impl<'de> VariantAccess<'de> for Variant<'_> {
+    type Error = MetadataError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        match self.0 {
+            None => Ok(()),
+            Some(node) => de::Deserialize::deserialize(node),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        match self.0 {
+            Some(node) => seed.deserialize(node),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Some(node) => de::Deserializer::deserialize_seq(node, visitor),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"tuple variant",
+            )),
+        }
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Some(node) => de::Deserializer::deserialize_map(node, visitor),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"struct variant",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde::Deserialize;
+
+    use crate::metadata::from_str;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Status {
+        Draft,
+        Published { date: String },
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Author {
+        name: String,
+        age: Option<u8>,
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Post {
+        title: String,
+        version: String,
+        tags: Vec<String>,
+        authors: Vec<Author>,
+        status: Status,
+        score: f32,
+        draft: Option<bool>,
+    }
+
+    #[test]
+    fn post() {
+        let input = "\
+title: Yamd
+version: 1.0
+tags: [software, rust]
+authors:
+- name: a
+  age: 30
+- name: b
+status:
+  published:
+    date: 2024-01-01
+score: 5
+draft: null";
+        assert_eq!(
+            from_str::<Post>(input),
+            Ok(Post {
+                title: "Yamd".to_string(),
+                version: "1.0".to_string(),
+                tags: vec!["software".to_string(), "rust".to_string()],
+                authors: vec![
+                    Author {
+                        name: "a".to_string(),
+                        age: Some(30)
+                    },
+                    Author {
+                        name: "b".to_string(),
+                        age: None
+                    },
+                ],
+                status: Status::Published {
+                    date: "2024-01-01".to_string()
+                },
+                score: 5.0,
+                draft: None,
+            })
+        );
+        assert_eq!(from_str::<Status>("draft"), Ok(Status::Draft));
+    }
+
+    #[test]
+    fn empty_metadata() {
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Optional {
+            title: Option<String>,
+        }
+        assert_eq!(from_str::<Optional>(""), Ok(Optional { title: None }));
+    }
+
+    #[test]
+    fn errors_point_at_value() {
+        let error = from_str::<Author>("name: a\nage: 300").unwrap_err();
+        assert_eq!((error.row, error.column), (1, 5));
+        assert_eq!(
+            error.to_string(),
+            "invalid value: integer `300`, expected u8 at line 2, column 6"
+        );
+
+        let error = from_str::<Vec<Author>>("- name: a\n- age: 1").unwrap_err();
+        assert_eq!(error.message, "missing field `name`");
+        assert_eq!((error.row, error.column), (1, 2));
+
+        let error = from_str::<Author>("name: [a").unwrap_err();
+        assert_eq!(
+            error.message,
+            "unterminated flow list, expected `]` on the same line"
+        );
+    }
+}
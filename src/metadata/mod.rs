@@ -0,0 +1,188 @@
+//! Typed access to [Yamd::metadata](crate::nodes::Yamd::metadata).
+//!
+//! Metadata is kept as a string in the AST, so any format can be used. This module parses the
+//! practical subset of YAML that frontmatter usually consists of into a [MetadataValue] tree,
+//! without any dependencies:
+//!
+//! - maps: `key: value`, nested by indentation
+//! - lists: `- item`, including a list at the same indentation as its key, and lists of maps
+//! - flow collections on a single line: `[a, b]` and `{a: 1, b: 2}`
+//! - scalars: null (`~`, `null` or nothing), booleans, integers, floats, plain strings, single and
+//!   double quoted strings
+//! - block scalars: literal `|` and folded `>`, with `-` or `+` chomping
+//! - comments: `# comment`
+//!
+//! Anchors, aliases, tags, multi-line flow collections, multi-line quoted or plain scalars, and
+//! multiple documents are not supported and are reported as errors.
+//!
+//! ```
+//! use yamd::metadata::MetadataValue;
+//!
+//! let yamd = yamd::deserialize("---\ntitle: Yamd\ntags:\n- software\n- rust\n---\n\n# Hello");
+//! let metadata = yamd.parse_metadata().unwrap().unwrap();
+//! assert_eq!(metadata.get("title").and_then(MetadataValue::as_str), Some("Yamd"));
+//! assert_eq!(
+//!     metadata.get("tags"),
+//!     Some(&MetadataValue::List(vec!["software".into(), "rust".into()]))
+//! );
+//! ```
+//!
+//! With the `serde` feature enabled, [from_str] deserializes metadata into any type that
+//! implements [serde::Deserialize].
+
+#[cfg(feature = "serde")]
+mod de;
+mod parser;
+
+use std::fmt::Display;
+
+#[cfg(feature = "serde")]
+pub use de::from_str;
+
+/// Value of the metadata, see [metadata](crate::metadata) for the supported syntax.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub enum MetadataValue {
+    #[default]
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    List(Vec<MetadataValue>),
+    /// Entries in the source order. Keys are unique.
+    Map(Vec<(String, MetadataValue)>),
+}
+
+impl MetadataValue {
+    /// Returns the value of `key` if this is a [Map](MetadataValue::Map).
+    pub fn get(&self, key: &str) -> Option<&MetadataValue> {
+        match self {
+            MetadataValue::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, MetadataValue::Null)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            MetadataValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            MetadataValue::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns [Float](MetadataValue::Float) or [Integer](MetadataValue::Integer) as `f64`.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            MetadataValue::Float(value) => Some(*value),
+            MetadataValue::Integer(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            MetadataValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[MetadataValue]> {
+        match self {
+            MetadataValue::List(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_map(&self) -> Option<&[(String, MetadataValue)]> {
+        match self {
+            MetadataValue::Map(entries) => Some(entries),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for MetadataValue {
+    fn from(value: &str) -> Self {
+        MetadataValue::String(value.to_string())
+    }
+}
+
+impl From<String> for MetadataValue {
+    fn from(value: String) -> Self {
+        MetadataValue::String(value)
+    }
+}
+
+/// Error of parsing or deserializing metadata.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MetadataError {
+    pub message: String,
+    /// Zero-based row in the metadata string.
+    pub row: usize,
+    /// Zero-based column in the metadata string, in characters.
+    pub column: usize,
+    /// `false` until a deserialization error is attached to the value that caused it.
+    #[cfg(feature = "serde")]
+    located: bool,
+}
+
+impl MetadataError {
+    pub(crate) fn new(message: impl Into<String>, row: usize, column: usize) -> Self {
+        Self {
+            message: message.into(),
+            row,
+            column,
+            #[cfg(feature = "serde")]
+            located: true,
+        }
+    }
+}
+
+impl Display for MetadataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message,
+            self.row + 1,
+            self.column + 1
+        )
+    }
+}
+
+impl std::error::Error for MetadataError {}
+
+/// Parses metadata, see [metadata](crate::metadata) for the supported syntax. Empty metadata is
+/// [Null](MetadataValue::Null).
+///
+/// ```
+/// use yamd::metadata::{MetadataValue, parse};
+///
+/// assert_eq!(
+///     parse("draft: true\nscore: [1, 2.5]"),
+///     Ok(MetadataValue::Map(vec![
+///         ("draft".to_string(), MetadataValue::Bool(true)),
+///         (
+///             "score".to_string(),
+///             MetadataValue::List(vec![MetadataValue::Integer(1), MetadataValue::Float(2.5)])
+///         ),
+///     ]))
+/// );
+///
+/// let error = parse("title: \"Yamd").unwrap_err();
+/// assert_eq!((error.row, error.column), (0, 7));
+/// assert_eq!(error.to_string(), "unterminated string at line 1, column 8");
+/// ```
+pub fn parse(input: &str) -> Result<MetadataValue, MetadataError> {
+    parser::parse(input).map(parser::Node::into_value)
+}
@@ -0,0 +1,893 @@
+use crate::metadata::{MetadataError, MetadataValue};
+
+/// Parsed value with the position of its first character.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Node {
+    pub(crate) tree: Tree,
+    pub(crate) row: usize,
+    pub(crate) column: usize,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) enum Tree {
+    /// Scalar value. `plain` is the source text of a plain (unquoted) scalar, so it can be read as
+    /// a string whatever type it resolves to.
+    Scalar {
+        value: MetadataValue,
+        plain: Option<String>,
+    },
+    List(Vec<Node>),
+    Map(Vec<(Key, Node)>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Key {
+    pub(crate) name: String,
+    pub(crate) row: usize,
+    pub(crate) column: usize,
+}
+
+impl Node {
+    fn scalar(value: MetadataValue, row: usize, column: usize) -> Self {
+        Self {
+            tree: Tree::Scalar { value, plain: None },
+            row,
+            column,
+        }
+    }
+
+    pub(crate) fn into_value(self) -> MetadataValue {
+        match self.tree {
+            Tree::Scalar { value, .. } => value,
+            Tree::List(items) => {
+                MetadataValue::List(items.into_iter().map(Node::into_value).collect())
+            }
+            Tree::Map(entries) => MetadataValue::Map(
+                entries
+                    .into_iter()
+                    .map(|(key, node)| (key.name, node.into_value()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Line<'a> {
+    row: usize,
+    indent: usize,
+    text: &'a str,
+}
+
+impl Line<'_> {
+    fn is_blank(&self) -> bool {
+        self.text.is_empty() || self.text.starts_with('#')
+    }
+}
+
+/// Parses `input` as a YAML subset, see [metadata](crate::metadata).
+pub(crate) fn parse(input: &str) -> Result<Node, MetadataError> {
+    let lines = input
+        .lines()
+        .enumerate()
+        .map(|(row, line)| {
+            let text = line.trim_start_matches(' ');
+            let indent = line.len() - text.len();
+            if text.starts_with('\t') {
+                return Err(MetadataError::new(
+                    "tabs are not allowed in indentation",
+                    row,
+                    indent,
+                ));
+            }
+            Ok(Line {
+                row,
+                indent,
+                text: text.trim_end(),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut parser = Parser { lines, pos: 0 };
+    let Some(first) = parser.peek() else {
+        return Ok(Node::scalar(MetadataValue::Null, 0, 0));
+    };
+    let node = parser.block(first.indent, None)?;
+    match parser.peek() {
+        Some(line) => Err(MetadataError::new(
+            "unexpected content, check indentation",
+            line.row,
+            line.indent,
+        )),
+        None => Ok(node),
+    }
+}
+
+struct Parser<'a> {
+    lines: Vec<Line<'a>>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    /// Returns the next line that is not blank or a comment.
+    fn peek(&mut self) -> Option<Line<'a>> {
+        while let Some(line) = self.lines.get(self.pos) {
+            if !line.is_blank() {
+                return Some(*line);
+            }
+            self.pos += 1;
+        }
+        None
+    }
+
+    /// Parses a list, a map or a scalar that starts on the next line. Block scalar content must be
+    /// indented more than `parent`.
+    fn block(&mut self, indent: usize, parent: Option<usize>) -> Result<Node, MetadataError> {
+        let line = self.peek().expect("block starts on a line");
+        if is_list_item(line.text) {
+            self.list(indent)
+        } else if map_key(line.text, line.row, line.indent)?.is_some() {
+            self.map(indent)
+        } else {
+            self.pos += 1;
+            self.inline(line.text, line.row, line.indent, parent)
+        }
+    }
+
+    fn list(&mut self, indent: usize) -> Result<Node, MetadataError> {
+        let first = self.peek().expect("list starts on a line");
+        let mut items = vec![];
+        while let Some(line) = self.peek() {
+            if line.indent != indent || !is_list_item(line.text) {
+                break;
+            }
+            let rest = line.text[1..].trim_start_matches(' ');
+            let column = line.indent + line.text[..line.text.len() - rest.len()].chars().count();
+            if rest.is_empty() || rest.starts_with('#') {
+                self.pos += 1;
+                items.push(self.nested(indent, false, line.row, column)?);
+            } else {
+                // The rest of the line is parsed as if it started on its own line.
+                self.lines[self.pos] = Line {
+                    row: line.row,
+                    indent: column,
+                    text: rest,
+                };
+                items.push(self.block(column, Some(indent))?);
+            }
+        }
+        Ok(Node {
+            tree: Tree::List(items),
+            row: first.row,
+            column: first.indent,
+        })
+    }
+
+    fn map(&mut self, indent: usize) -> Result<Node, MetadataError> {
+        let first = self.peek().expect("map starts on a line");
+        let mut entries: Vec<(Key, Node)> = vec![];
+        while let Some(line) = self.peek() {
+            if line.indent != indent || is_list_item(line.text) {
+                break;
+            }
+            let Some((key, rest)) = map_key(line.text, line.row, line.indent)? else {
+                return Err(MetadataError::new(
+                    "expected `key: value`",
+                    line.row,
+                    line.indent,
+                ));
+            };
+            if entries.iter().any(|(k, _)| k.name == key.name) {
+                return Err(MetadataError::new(
+                    format!("duplicate key `{}`", key.name),
+                    key.row,
+                    key.column,
+                ));
+            }
+            self.pos += 1;
+            let trimmed = rest.trim_start_matches(' ');
+            let column = line.indent + line.text[..line.text.len() - trimmed.len()].chars().count();
+            let value = if trimmed.is_empty() || trimmed.starts_with('#') {
+                self.nested(indent, true, line.row, column)?
+            } else {
+                self.inline(trimmed, line.row, column, Some(indent))?
+            };
+            entries.push((key, value));
+        }
+        Ok(Node {
+            tree: Tree::Map(entries),
+            row: first.row,
+            column: first.indent,
+        })
+    }
+
+    /// Value of a map entry or a list item that has nothing after `:` or `-`. It is on the
+    /// following lines indented more than `indent`, or, for a map entry, is a list at the same
+    /// indentation as in `tags:\n- a`. Otherwise it is null.
+    fn nested(
+        &mut self,
+        indent: usize,
+        same_indent_list: bool,
+        row: usize,
+        column: usize,
+    ) -> Result<Node, MetadataError> {
+        match self.peek() {
+            Some(next) if next.indent > indent => self.block(next.indent, Some(indent)),
+            Some(next) if same_indent_list && next.indent == indent && is_list_item(next.text) => {
+                self.list(indent)
+            }
+            _ => Ok(Node::scalar(MetadataValue::Null, row, column)),
+        }
+    }
+
+    /// Value that starts in the middle of a line: flow collection, scalar or block scalar header.
+    fn inline(
+        &mut self,
+        text: &str,
+        row: usize,
+        column: usize,
+        parent: Option<usize>,
+    ) -> Result<Node, MetadataError> {
+        if let Some(header) = text.strip_prefix(['|', '>']) {
+            let folded = text.starts_with('>');
+            return self.block_scalar(header, folded, row, column, parent);
+        }
+        let mut scanner = Scanner {
+            text,
+            offset: 0,
+            row,
+            column,
+        };
+        let node = scanner.value(false)?;
+        scanner.skip_spaces();
+        if !scanner.rest().is_empty() && !scanner.rest().starts_with('#') {
+            return Err(scanner.error("unexpected characters after value"));
+        }
+        Ok(node)
+    }
+
+    fn block_scalar(
+        &mut self,
+        header: &str,
+        folded: bool,
+        row: usize,
+        column: usize,
+        parent: Option<usize>,
+    ) -> Result<Node, MetadataError> {
+        let (chomping, comment) = match header.chars().next() {
+            Some(c @ ('-' | '+')) => (Some(c), &header[1..]),
+            _ => (None, header),
+        };
+        let comment = comment.trim_start_matches(' ');
+        if !comment.is_empty() && !comment.starts_with('#') {
+            return Err(MetadataError::new(
+                "unsupported block scalar header, expected `|`, `>`, optionally followed by `-` or `+`",
+                row,
+                column,
+            ));
+        }
+
+        let mut lines: Vec<String> = vec![];
+        let mut content_indent = None;
+        while let Some(line) = self.lines.get(self.pos) {
+            if line.text.is_empty() {
+                lines.push(String::new());
+                self.pos += 1;
+                continue;
+            }
+            let indent = *content_indent.get_or_insert(line.indent);
+            if line.indent < indent || parent.is_some_and(|parent| line.indent <= parent) {
+                break;
+            }
+            lines.push(format!("{}{}", " ".repeat(line.indent - indent), line.text));
+            self.pos += 1;
+        }
+        let trailing = lines
+            .iter()
+            .rev()
+            .take_while(|line| line.is_empty())
+            .count();
+        lines.truncate(lines.len() - trailing);
+
+        let mut value = if folded {
+            fold(&lines)
+        } else {
+            lines.join("\n")
+        };
+        match chomping {
+            Some('-') => {}
+            Some(_) => value.push_str(&"\n".repeat(trailing + usize::from(!lines.is_empty()))),
+            None if !lines.is_empty() => value.push('\n'),
+            None => {}
+        }
+        Ok(Node::scalar(MetadataValue::String(value), row, column))
+    }
+}
+
+fn is_list_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+/// Splits `key: value` into the key and the rest of the line after `:`. Returns `None` if `text`
+/// is not a map entry.
+fn map_key(text: &str, row: usize, column: usize) -> Result<Option<(Key, &str)>, MetadataError> {
+    if text.starts_with(['"', '\'']) {
+        let mut scanner = Scanner {
+            text,
+            offset: 0,
+            row,
+            column,
+        };
+        let Ok(node) = scanner.value(false) else {
+            return Ok(None);
+        };
+        scanner.skip_spaces();
+        let rest = scanner.rest();
+        return Ok(match (node.tree, rest.strip_prefix(':')) {
+            (Tree::Scalar { value, .. }, Some(rest))
+                if rest.is_empty() || rest.starts_with(' ') =>
+            {
+                let name = value.as_str().unwrap_or_default().to_string();
+                Some((Key { name, row, column }, rest))
+            }
+            _ => None,
+        });
+    }
+    if text.starts_with(['[', '{', '#', '|', '>']) {
+        return Ok(None);
+    }
+    let Some(colon) = separator(text, false) else {
+        return Ok(None);
+    };
+    let name = text[..colon].trim_end();
+    if name.is_empty() {
+        return Err(MetadataError::new("expected key before `:`", row, column));
+    }
+    Ok(Some((
+        Key {
+            name: name.to_string(),
+            row,
+            column,
+        },
+        &text[colon + 1..],
+    )))
+}
+
+/// Returns byte offset of `:` that separates key from value: followed by a space or the end of
+/// `text`, or in a flow collection by `,`, `]` or `}`. Comment ends the search.
+fn separator(text: &str, flow: bool) -> Option<usize> {
+    let bytes = text.as_bytes();
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b':' => {
+                let next = bytes.get(i + 1);
+                if next.is_none_or(|&n| n == b' ' || (flow && matches!(n, b',' | b']' | b'}'))) {
+                    return Some(i);
+                }
+            }
+            b'#' if i > 0 && bytes[i - 1] == b' ' => return None,
+            b',' | b']' | b'}' | b'[' | b'{' if flow => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Folds lines of `>` block scalar: lines are joined with a space, empty lines become line breaks,
+/// and lines that start with a space keep their line breaks.
+fn fold(lines: &[String]) -> String {
+    let mut out = String::new();
+    let mut blanks = 0;
+    let mut previous: Option<&str> = None;
+    for line in lines {
+        if line.is_empty() {
+            blanks += 1;
+            continue;
+        }
+        if let Some(previous) = previous {
+            let separator = if blanks > 0 {
+                "\n".repeat(blanks)
+            } else if previous.starts_with(' ') || line.starts_with(' ') {
+                "\n".to_string()
+            } else {
+                " ".to_string()
+            };
+            out.push_str(&separator);
+        } else {
+            out.push_str(&"\n".repeat(blanks));
+        }
+        out.push_str(line);
+        blanks = 0;
+        previous = Some(line);
+    }
+    out
+}
+
+/// Reads values that fit on a single line: flow collections, quoted and plain scalars.
+struct Scanner<'a> {
+    text: &'a str,
+    offset: usize,
+    row: usize,
+    column: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.offset..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_spaces(&mut self) {
+        self.offset = self.text.len() - self.rest().trim_start_matches(' ').len();
+    }
+
+    fn error(&self, message: impl Into<String>) -> MetadataError {
+        MetadataError::new(
+            message,
+            self.row,
+            self.column + self.text[..self.offset].chars().count(),
+        )
+    }
+
+    fn node(&self, tree: Tree, offset: usize) -> Node {
+        Node {
+            tree,
+            row: self.row,
+            column: self.column + self.text[..offset].chars().count(),
+        }
+    }
+
+    fn value(&mut self, flow: bool) -> Result<Node, MetadataError> {
+        let start = self.offset;
+        match self.peek() {
+            Some('[') => self.flow_list(),
+            Some('{') => self.flow_map(),
+            Some('"') => {
+                let value = self.double_quoted()?;
+                Ok(self.node(scalar(MetadataValue::String(value)), start))
+            }
+            Some('\'') => {
+                let value = self.single_quoted()?;
+                Ok(self.node(scalar(MetadataValue::String(value)), start))
+            }
+            Some(c @ ('&' | '*' | '!' | '%' | '@' | '`')) => {
+                Err(self.error(format!("unsupported YAML syntax `{c}`")))
+            }
+            Some('|' | '>') if flow => Err(self.error("block scalar in flow collection")),
+            _ => {
+                let plain = self.plain(flow);
+                Ok(self.node(
+                    Tree::Scalar {
+                        value: resolve(plain),
+                        plain: Some(plain.to_string()),
+                    },
+                    start,
+                ))
+            }
+        }
+    }
+
+    /// Plain scalar ends at ` #`, and in a flow collection also at `,`, `]`, `}` and `: `.
+    fn plain(&mut self, flow: bool) -> &'a str {
+        let rest = self.rest();
+        let mut end = rest.len();
+        for (i, c) in rest.char_indices() {
+            let stop = match c {
+                '#' => i > 0 && rest[..i].ends_with(' '),
+                ',' | ']' | '}' => flow,
+                ':' => flow && separator(&rest[i..], true) == Some(0),
+                _ => false,
+            };
+            if stop {
+                end = i;
+                break;
+            }
+        }
+        let start = self.offset;
+        self.offset += end;
+        self.text[start..start + end].trim_end()
+    }
+
+    fn double_quoted(&mut self) -> Result<String, MetadataError> {
+        let start = self.offset;
+        self.offset += 1;
+        let mut out = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.offset += i + 1;
+                    return Ok(out);
+                }
+                '\\' => {
+                    let escaped = match chars.next().map(|(_, c)| c) {
+                        Some('n') => '\n',
+                        Some('t') => '\t',
+                        Some('r') => '\r',
+                        Some('0') => '\0',
+                        Some(c @ ('"' | '\\' | '/' | ' ')) => c,
+                        Some(c @ ('u' | 'x')) => {
+                            let len = if c == 'u' { 4 } else { 2 };
+                            let hex: String = chars.by_ref().take(len).map(|(_, c)| c).collect();
+                            u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .filter(|_| hex.len() == len)
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| {
+                                    self.offset += i;
+                                    self.error("invalid escape sequence")
+                                })?
+                        }
+                        _ => {
+                            self.offset += i;
+                            return Err(self.error("invalid escape sequence"));
+                        }
+                    };
+                    out.push(escaped);
+                }
+                c => out.push(c),
+            }
+        }
+        self.offset = start;
+        Err(self.error("unterminated string"))
+    }
+
+    fn single_quoted(&mut self) -> Result<String, MetadataError> {
+        let start = self.offset;
+        self.offset += 1;
+        let mut out = String::new();
+        let mut chars = self.rest().char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            if c != '\'' {
+                out.push(c);
+            } else if chars.next_if(|(_, c)| *c == '\'').is_some() {
+                out.push('\'');
+            } else {
+                self.offset += i + 1;
+                return Ok(out);
+            }
+        }
+        self.offset = start;
+        Err(self.error("unterminated string"))
+    }
+
+    fn flow_list(&mut self) -> Result<Node, MetadataError> {
+        let start = self.offset;
+        self.offset += 1;
+        let mut items = vec![];
+        loop {
+            self.skip_spaces();
+            if self.peek() == Some(']') {
+                break;
+            }
+            self.flow_unterminated(start, "list", ']')?;
+            items.push(self.value(true)?);
+            if !self.flow_separator(start, "list", ']')? {
+                break;
+            }
+        }
+        self.offset += 1;
+        Ok(self.node(Tree::List(items), start))
+    }
+
+    fn flow_map(&mut self) -> Result<Node, MetadataError> {
+        let start = self.offset;
+        self.offset += 1;
+        let mut entries: Vec<(Key, Node)> = vec![];
+        loop {
+            self.skip_spaces();
+            if self.peek() == Some('}') {
+                break;
+            }
+            self.flow_unterminated(start, "map", '}')?;
+            let key = self.value(true)?;
+            let name = match &key.tree {
+                Tree::Scalar {
+                    plain: Some(plain), ..
+                } => plain.clone(),
+                Tree::Scalar {
+                    value: MetadataValue::String(name),
+                    ..
+                } => name.clone(),
+                _ => return Err(MetadataError::new("expected key", key.row, key.column)),
+            };
+            if entries.iter().any(|(k, _)| k.name == name) {
+                return Err(MetadataError::new(
+                    format!("duplicate key `{name}`"),
+                    key.row,
+                    key.column,
+                ));
+            }
+            self.skip_spaces();
+            let value = if self.peek() == Some(':') {
+                self.offset += 1;
+                self.skip_spaces();
+                if matches!(self.peek(), Some(',' | '}')) {
+                    self.node(scalar(MetadataValue::Null), self.offset)
+                } else {
+                    self.value(true)?
+                }
+            } else {
+                self.node(scalar(MetadataValue::Null), self.offset)
+            };
+            let key = Key {
+                name,
+                row: key.row,
+                column: key.column,
+            };
+            entries.push((key, value));
+            if !self.flow_separator(start, "map", '}')? {
+                break;
+            }
+        }
+        self.offset += 1;
+        Ok(self.node(Tree::Map(entries), start))
+    }
+
+    /// Returns an error that points at the start of the collection if the line ends before `end`.
+    fn flow_unterminated(
+        &mut self,
+        start: usize,
+        kind: &str,
+        end: char,
+    ) -> Result<(), MetadataError> {
+        if self.peek().is_some() {
+            return Ok(());
+        }
+        self.offset = start;
+        Err(self.error(format!(
+            "unterminated flow {kind}, expected `{end}` on the same line"
+        )))
+    }
+
+    /// Skips `,` between entries. Returns `false` at the closing `end`.
+    fn flow_separator(
+        &mut self,
+        start: usize,
+        kind: &str,
+        end: char,
+    ) -> Result<bool, MetadataError> {
+        self.skip_spaces();
+        self.flow_unterminated(start, kind, end)?;
+        match self.peek() {
+            Some(',') => {
+                self.offset += 1;
+                Ok(true)
+            }
+            Some(c) if c == end => Ok(false),
+            _ => Err(self.error(format!("expected `,` or `{end}`"))),
+        }
+    }
+}
+
+fn scalar(value: MetadataValue) -> Tree {
+    Tree::Scalar { value, plain: None }
+}
+
+/// Resolves plain scalar to null, boolean, number or string.
+fn resolve(plain: &str) -> MetadataValue {
+    match plain {
+        "" | "~" | "null" | "Null" | "NULL" => return MetadataValue::Null,
+        "true" | "True" | "TRUE" => return MetadataValue::Bool(true),
+        "false" | "False" | "FALSE" => return MetadataValue::Bool(false),
+        _ => {}
+    }
+    let digits = plain.strip_prefix(['-', '+']).unwrap_or(plain);
+    if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+        if let Ok(integer) = plain.parse() {
+            return MetadataValue::Integer(integer);
+        }
+    }
+    let mantissa = digits.split(['e', 'E']).next().unwrap_or_default();
+    if digits.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+        && mantissa.bytes().any(|b| b.is_ascii_digit())
+        && mantissa.bytes().all(|b| b.is_ascii_digit() || b == b'.')
+        && mantissa.bytes().filter(|&b| b == b'.').count() <= 1
+    {
+        if let Ok(float) = plain.parse() {
+            return MetadataValue::Float(float);
+        }
+    }
+    MetadataValue::String(plain.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::metadata::{MetadataError, MetadataValue, parse};
+
+    fn map(entries: Vec<(&str, MetadataValue)>) -> MetadataValue {
+        MetadataValue::Map(
+            entries
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        )
+    }
+
+    fn list(items: Vec<MetadataValue>) -> MetadataValue {
+        MetadataValue::List(items)
+    }
+
+    fn error(input: &str) -> (String, usize, usize) {
+        let MetadataError {
+            message,
+            row,
+            column,
+            ..
+        } = parse(input).unwrap_err();
+        (message, row, column)
+    }
+
+    #[test]
+    fn scalars() {
+        for (input, value) in [
+            ("", MetadataValue::Null),
+            ("~", MetadataValue::Null),
+            ("null", MetadataValue::Null),
+            ("true", MetadataValue::Bool(true)),
+            ("False", MetadataValue::Bool(false)),
+            ("-42", MetadataValue::Integer(-42)),
+            ("1.5e3", MetadataValue::Float(1500.0)),
+            (".5", MetadataValue::Float(0.5)),
+            ("1.2.3", "1.2.3".into()),
+            ("99999999999999999999", MetadataValue::Float(1e20)),
+            ("hello world # comment", "hello world".into()),
+            ("a#b: c", map(vec![("a#b", "c".into())])),
+            ("http://example.com", "http://example.com".into()),
+            ("\"a\\\"b\\n\\u00e9\" # c", "a\"b\né".into()),
+            ("'it''s # not a comment'", "it's # not a comment".into()),
+        ] {
+            assert_eq!(parse(input), Ok(value), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn nested() {
+        let input = "\
+title: Yamd # comment
+tags:
+- software
+- rust
+
+author:
+  name: \"Lurk\"
+  links: [https://github.com/Lurk, {kind: site, url: 'x'}]
+posts:
+  - title: one
+    draft: true
+  -
+    - nested
+  - ~";
+        assert_eq!(
+            parse(input),
+            Ok(map(vec![
+                ("title", "Yamd".into()),
+                ("tags", list(vec!["software".into(), "rust".into()])),
+                (
+                    "author",
+                    map(vec![
+                        ("name", "Lurk".into()),
+                        (
+                            "links",
+                            list(vec![
+                                "https://github.com/Lurk".into(),
+                                map(vec![("kind", "site".into()), ("url", "x".into())]),
+                            ])
+                        ),
+                    ])
+                ),
+                (
+                    "posts",
+                    list(vec![
+                        map(vec![
+                            ("title", "one".into()),
+                            ("draft", MetadataValue::Bool(true))
+                        ]),
+                        list(vec!["nested".into()]),
+                        MetadataValue::Null,
+                    ])
+                ),
+            ]))
+        );
+    }
+
+    #[test]
+    fn empty_values() {
+        assert_eq!(
+            parse("a:\nb: []\nc: {}\nd: {e}"),
+            Ok(map(vec![
+                ("a", MetadataValue::Null),
+                ("b", list(vec![])),
+                ("c", map(vec![])),
+                ("d", map(vec![("e", MetadataValue::Null)])),
+            ]))
+        );
+    }
+
+    #[test]
+    fn block_scalars() {
+        let input = "\
+literal: |
+  a
+    b
+
+  c
+
+folded: >-
+  a
+  b
+
+  c
+keep: |+
+  a
+
+strip: |-
+  a
+list:
+- |
+  a
+- b";
+        assert_eq!(
+            parse(input),
+            Ok(map(vec![
+                ("literal", "a\n  b\n\nc\n".into()),
+                ("folded", "a b\nc".into()),
+                ("keep", "a\n\n".into()),
+                ("strip", "a".into()),
+                ("list", list(vec!["a\n".into(), "b".into()])),
+            ]))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        for (input, expected) in [
+            ("a: \"b", ("unterminated string", 0, 3)),
+            (
+                "a: [b, c",
+                (
+                    "unterminated flow list, expected `]` on the same line",
+                    0,
+                    3,
+                ),
+            ),
+            (
+                "a: {b: c",
+                ("unterminated flow map, expected `}` on the same line", 0, 3),
+            ),
+            ("a: [b c] d", ("unexpected characters after value", 0, 9)),
+            ("a: 1\na: 2", ("duplicate key `a`", 1, 0)),
+            (
+                "a: 1\n  b: 2",
+                ("unexpected content, check indentation", 1, 2),
+            ),
+            (
+                "a:\n  b: 1\n c: 2",
+                ("unexpected content, check indentation", 2, 1),
+            ),
+            ("a: 1\nb", ("expected `key: value`", 1, 0)),
+            ("a:\n\t- b", ("tabs are not allowed in indentation", 1, 0)),
+            ("a: &anchor b", ("unsupported YAML syntax `&`", 0, 3)),
+            ("a: *alias", ("unsupported YAML syntax `*`", 0, 3)),
+            ("ü: \"\\x\"", ("invalid escape sequence", 0, 4)),
+            (
+                "a: |2\n  b",
+                (
+                    "unsupported block scalar header, expected `|`, `>`, optionally followed by `-` or `+`",
+                    0,
+                    3,
+                ),
+            ),
+        ] {
+            assert_eq!(
+                error(input),
+                (expected.0.to_string(), expected.1, expected.2),
+                "{input:?}"
+            );
+        }
+    }
+}
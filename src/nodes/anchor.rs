@@ -34,9 +34,9 @@ use serde::{Deserialize, Serialize};
 ///
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Anchor {
-    pub text: String,
-    pub url: String,
+pub struct Anchor<S = String> {
+    pub text: S,
+    pub url: S,
 }
 
 impl Anchor {
@@ -50,9 +50,11 @@ impl Anchor {
 
 impl Display for Anchor {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let escape_caret = if self.text.starts_with('^') { "\\" } else { "" };
         write!(
             f,
-            "[{}]({})",
+            "[{}{}]({})",
+            escape_caret,
             self.text
                 .replace("\\", "\\\\")
                 .replace("[", "\\[")
@@ -77,6 +79,11 @@ mod tests {
         assert_eq!(anchor.to_string(), "[link](url)");
     }
 
+    #[test]
+    fn anchor_that_looks_like_footnote_reference() {
+        assert_eq!(Anchor::new("^a", "u").to_string(), "[\\^a](u)");
+    }
+
     #[test]
     fn anchor_with_nested_squares() {
         let anchor = Anchor::new("link [nested squares]", "url");
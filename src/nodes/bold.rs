@@ -8,20 +8,20 @@ use super::{Italic, Strikethrough};
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
-pub enum BoldNodes {
-    Italic(Italic),
-    Strikethrough(Strikethrough),
-    Text(String),
+pub enum BoldNodes<S = String> {
+    Italic(Italic<S>),
+    Strikethrough(Strikethrough<S>),
+    Text(S),
 }
 
-impl From<Italic> for BoldNodes {
-    fn from(i: Italic) -> Self {
+impl<S> From<Italic<S>> for BoldNodes<S> {
+    fn from(i: Italic<S>) -> Self {
         BoldNodes::Italic(i)
     }
 }
 
-impl From<Strikethrough> for BoldNodes {
-    fn from(s: Strikethrough) -> Self {
+impl<S> From<Strikethrough<S>> for BoldNodes<S> {
+    fn from(s: Strikethrough<S>) -> Self {
         BoldNodes::Strikethrough(s)
     }
 }
@@ -92,8 +92,8 @@ impl Display for BoldNodes {
 /// construction.
 #[derive(Debug, PartialEq, Clone, Default, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Bold {
-    pub body: Vec<BoldNodes>,
+pub struct Bold<S = String> {
+    pub body: Vec<BoldNodes<S>>,
 }
 
 impl Bold {
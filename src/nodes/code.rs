@@ -30,9 +30,9 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Code {
-    pub lang: String,
-    pub code: String,
+pub struct Code<S = String> {
+    pub lang: S,
+    pub code: S,
 }
 
 impl Code {
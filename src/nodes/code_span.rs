@@ -31,7 +31,7 @@ use serde::{Deserialize, Serialize};
 /// future breaking change is expected to reject empty bodies at construction.
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct CodeSpan(pub String);
+pub struct CodeSpan<S = String>(pub S);
 
 impl CodeSpan {
     pub fn new<Body: Into<String>>(body: Body) -> Self {
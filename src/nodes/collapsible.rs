@@ -37,9 +37,9 @@ use super::{YamdNodes, paragraph::escape_leading_block_marker};
 /// ```
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Collapsible {
-    pub title: String,
-    pub body: Vec<YamdNodes>,
+pub struct Collapsible<S = String> {
+    pub title: S,
+    pub body: Vec<YamdNodes<S>>,
 }
 
 impl Collapsible {
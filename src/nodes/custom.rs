@@ -0,0 +1,89 @@
+use std::fmt::Display;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::YamdNodes;
+
+/// # Custom
+///
+/// Block parsed by a [BlockExtension](crate::op::BlockExtension) registered in
+/// [ParseOptions](crate::op::ParseOptions::extensions).
+///
+/// [Name](Custom::name) is the [name](crate::op::BlockExtension::name) of the extension.
+/// [Start](Custom::start) and [end](Custom::end) are the opening and closing delimiters as written
+/// in the source, [body](Custom::body) is the text between them. The text is not unescaped.
+/// [Nodes](Custom::nodes) are the body parsed as YAMD, if the extension asks for it, see
+/// [ExtensionBlock::custom](crate::op::ExtensionBlock::custom).
+///
+/// Example, with an extension that parses `:::` fenced blocks:
+///
+/// ```text
+/// ::: note
+/// text
+/// :::
+/// ```
+///
+/// HTML equivalent, with the body as text:
+///
+/// ```html
+/// <div class="note"> note
+/// text
+/// </div>
+/// ```
+///
+/// With the body parsed into [nodes](Custom::nodes), they are rendered in place of the text.
+///
+/// [Display] writes the block as it was in the source, [nodes](Custom::nodes) are not written, so
+/// it is parsed back to the same node only with the same extension.
+#[derive(Debug, PartialEq, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Custom<S = String> {
+    pub name: S,
+    pub start: S,
+    pub body: S,
+    pub end: S,
+    pub nodes: Vec<YamdNodes<S>>,
+}
+
+impl Custom {
+    pub fn new<N: Into<String>, S: Into<String>, B: Into<String>, E: Into<String>>(
+        name: N,
+        start: S,
+        body: B,
+        end: E,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            start: start.into(),
+            body: body.into(),
+            end: end.into(),
+            nodes: vec![],
+        }
+    }
+
+    /// Sets [nodes](Custom::nodes) the body is parsed into.
+    pub fn with_nodes(mut self, nodes: Vec<YamdNodes>) -> Self {
+        self.nodes = nodes;
+        self
+    }
+}
+
+impl Display for Custom {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}{}", self.start, self.body, self.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom() {
+        let custom = Custom::new("note", ":::", " note\ntext\n", ":::");
+        assert_eq!(custom.to_string(), "::: note\ntext\n:::");
+        let custom = custom.with_nodes(vec![crate::nodes::ThematicBreak::new().into()]);
+        assert_eq!(custom.to_string(), "::: note\ntext\n:::");
+    }
+}
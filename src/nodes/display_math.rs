@@ -0,0 +1,69 @@
+use std::fmt::Display;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// # Display math
+///
+/// Starts with [Dollar](type@crate::lexer::TokenKind::Dollar) of length 2 followed by
+/// [EOL](type@crate::lexer::TokenKind::Eol).
+///
+/// [Body](DisplayMath::body) is every character until the line that starts with
+/// [Dollar](type@crate::lexer::TokenKind::Dollar) of length 2. Body is verbatim: `\` does not escape
+/// anything.
+///
+/// Example:
+///
+/// ```text
+/// $$
+/// e^{i\pi} + 1 = 0
+/// $$
+/// ```
+///
+/// HTML equivalent:
+///
+/// ```html
+/// <div class="math math-display">e^{i\pi} + 1 = 0</div>
+/// ```
+///
+/// # Round-trip invariant
+///
+/// Body has no escapes, so a body with a line that starts with `$$`, or a body that ends with `\`,
+/// will not round-trip.
+#[derive(Debug, PartialEq, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DisplayMath<S = String> {
+    pub body: S,
+}
+
+impl DisplayMath {
+    pub fn new<S: Into<String>>(body: S) -> Self {
+        let body = body.into();
+        let body = body.trim_end_matches('\n').to_owned();
+        Self { body }
+    }
+}
+
+impl Display for DisplayMath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "$$\n{}\n$$", self.body)
+    }
+}
+
+/// Returns `true` if `body` can be a [body](DisplayMath::body) of [DisplayMath] that round-trips.
+#[cfg(feature = "commonmark")]
+pub(crate) fn is_display_math(body: &str) -> bool {
+    !body.ends_with('\\') && !body.lines().any(|line| line.starts_with("$$"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_math() {
+        let math = DisplayMath::new("a \\\\\nb\n");
+        assert_eq!(math.body, "a \\\\\nb");
+        assert_eq!(math.to_string(), "$$\na \\\\\nb\n$$");
+    }
+}
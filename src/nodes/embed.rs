@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{borrow::Cow, fmt::Display};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -13,10 +13,21 @@ use serde::{Deserialize, Serialize};
 /// [Args](Embed::args) every token except [Terminator](type@crate::lexer::TokenKind::Terminator)
 /// until [LeftCurlyBrace](type@crate::lexer::TokenKind::LeftCurlyBrace) of length 2.
 ///
+/// [Args](Embed::args) are separated by [Pipe](type@crate::lexer::TokenKind::Pipe), an argument
+/// that starts with a key and `=` is a named one, see [Embed::arguments]. Escaped `|` and `=` are
+/// part of an argument, [args](Embed::args) keep `\` before them:
+///
+/// ```text
+/// {{link|https://yamd.dev|text=a \| b}}
+/// ```
+///
+/// has args `https://yamd.dev|text=a \| b` and the argument `text` is `a | b`.
+///
 /// Examples:
 ///
 /// ```text
 /// {{youtube|dQw4w9WgXcQ}}
+/// {{youtube|dQw4w9WgXcQ|start=30}}
 /// ```
 ///
 /// HTML equivalent:
@@ -26,9 +37,10 @@ use serde::{Deserialize, Serialize};
 /// ```
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Embed {
-    pub kind: String,
-    pub args: String,
+pub struct Embed<S = String> {
+    pub kind: S,
+    /// Unescaped arguments, except that `\` before `|` and `=` is kept, see [Embed::arguments].
+    pub args: S,
 }
 
 impl Embed {
@@ -38,6 +50,111 @@ impl Embed {
             args: args.into(),
         }
     }
+
+    /// Parses [args](Embed::args) into positional and `key=value` arguments. Arguments are
+    /// separated by `|` and trimmed. A key consists of ASCII alphanumeric characters, `-` and `_`,
+    /// so an argument like a URL with a query is positional.
+    ///
+    /// `\|` and `\=` are a literal `|` and `=`. A run of `\` before `|` or `=` is halved, and the
+    /// `|` or `=` is literal if the run is odd. Other `\` are kept as is.
+    ///
+    /// ```
+    /// use yamd::nodes::Embed;
+    ///
+    /// let embed = Embed::new("youtube", "dQw4w9WgXcQ | start=30 | https://youtu.be/?t=1");
+    /// let args = embed.arguments();
+    /// assert_eq!(args.positional, vec!["dQw4w9WgXcQ", "https://youtu.be/?t=1"]);
+    /// assert_eq!(args.get("start"), Some("30"));
+    ///
+    /// let embed = Embed::new("table", r"a\|b | c\=d | sep=\|");
+    /// let args = embed.arguments();
+    /// assert_eq!(args.positional, vec!["a|b", "c=d"]);
+    /// assert_eq!(args.get("sep"), Some("|"));
+    /// ```
+    pub fn arguments(&self) -> EmbedArgs<'_> {
+        let mut args = EmbedArgs::default();
+        if self.args.trim().is_empty() {
+            return args;
+        }
+        let mut start = 0;
+        for end in unescaped(&self.args, '|').chain([self.args.len()]) {
+            let arg = &self.args[start..end];
+            // a run of `\` at the end of the argument is before the separator
+            let before_pipe = end < self.args.len();
+            match unescaped(arg, '=').next() {
+                Some(i) if is_key(arg[..i].trim()) => args
+                    .named
+                    .push((arg[..i].trim(), unescape(&arg[i + 1..], before_pipe))),
+                _ => args.positional.push(unescape(arg, before_pipe)),
+            }
+            start = end + 1;
+        }
+        args
+    }
+}
+
+fn is_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Byte offsets of `separator`s in `args` that are not escaped, after an even run of `\`.
+fn unescaped(args: &str, separator: char) -> impl Iterator<Item = usize> + '_ {
+    let mut backslashes = 0;
+    args.char_indices().filter_map(move |(i, c)| {
+        let even = backslashes % 2 == 0;
+        backslashes = if c == '\\' { backslashes + 1 } else { 0 };
+        (c == separator && even).then_some(i)
+    })
+}
+
+/// Trimmed `arg` with runs of `\` before `|` and `=` halved, and before the end of `arg` too if it
+/// is `before_pipe`.
+fn unescape(arg: &str, before_pipe: bool) -> Cow<'_, str> {
+    if !arg.contains('\\') {
+        return Cow::Borrowed(arg.trim());
+    }
+    let mut unescaped = String::with_capacity(arg.len());
+    let mut backslashes = 0;
+    for c in arg.chars() {
+        if c == '\\' {
+            backslashes += 1;
+            continue;
+        }
+        if c == '|' || c == '=' {
+            backslashes /= 2;
+        }
+        unescaped.extend(std::iter::repeat_n('\\', backslashes));
+        unescaped.push(c);
+        backslashes = 0;
+    }
+    if before_pipe {
+        backslashes /= 2;
+    }
+    unescaped.extend(std::iter::repeat_n('\\', backslashes));
+    Cow::Owned(unescaped.trim().to_string())
+}
+
+/// [Args](Embed::args) of an [Embed] split into arguments, see [Embed::arguments].
+#[derive(Debug, PartialEq, Clone, Default, Eq)]
+pub struct EmbedArgs<'a> {
+    /// Arguments without a key, in order.
+    pub positional: Vec<Cow<'a, str>>,
+    /// `key=value` arguments, in order.
+    pub named: Vec<(&'a str, Cow<'a, str>)>,
+}
+
+impl EmbedArgs<'_> {
+    /// Returns the value of the last argument with `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.named
+            .iter()
+            .rev()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| value.as_ref())
+    }
 }
 
 impl Display for Embed {
@@ -49,18 +166,39 @@ impl Display for Embed {
                 .replace("\\", "\\\\")
                 .replace("{", "\\{")
                 .replace("|", "\\|"),
-            self.args
-                .replace("\\", "\\\\")
-                .replace("{", "\\{")
-                .replace("%", "\\%")
-                .replace("}", "\\}")
+            escape_args(&self.args)
         )
     }
 }
 
+/// Escapes `args` so that they are parsed back as they are. Runs of `\` before `|` and `=` are
+/// already escapes, see [Embed::arguments].
+fn escape_args(args: &str) -> String {
+    let mut escaped = String::with_capacity(args.len());
+    let mut backslashes = 0;
+    for c in args.chars() {
+        if c == '\\' {
+            backslashes += 1;
+            continue;
+        }
+        if c != '|' && c != '=' {
+            backslashes *= 2;
+        }
+        escaped.extend(std::iter::repeat_n('\\', backslashes));
+        if matches!(c, '{' | '%' | '}') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+        backslashes = 0;
+    }
+    escaped.extend(std::iter::repeat_n('\\', backslashes * 2));
+    escaped
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{deserialize, nodes::YamdNodes};
 
     #[test]
     fn embed() {
@@ -68,6 +206,55 @@ mod tests {
         assert_eq!(embed.to_string(), "{{youtube|dQw4w9WgXcQ}}");
     }
 
+    #[test]
+    fn arguments() {
+        let embed = Embed::new("youtube", "id|start=30|end = 40|=x|a b=c||start=31");
+        let args = embed.arguments();
+        assert_eq!(args.positional, vec!["id", "=x", "a b=c", ""]);
+        assert_eq!(
+            named(&args),
+            vec![("start", "30"), ("end", "40"), ("start", "31")]
+        );
+        assert_eq!(args.get("start"), Some("31"));
+        assert_eq!(Embed::new("a", "").arguments(), EmbedArgs::default());
+    }
+
+    fn named<'a>(args: &'a EmbedArgs) -> Vec<(&'a str, &'a str)> {
+        args.named.iter().map(|(k, v)| (*k, v.as_ref())).collect()
+    }
+
+    #[test]
+    fn escaped_arguments() {
+        let embed = Embed::new("a", r"x\|y | k=\=\| | \\|z\\\\=w | C:\path\ | \\");
+        let args = embed.arguments();
+        assert_eq!(
+            args.positional,
+            vec![r"x|y", r"\", r"z\\=w", r"C:\path\", r"\\"]
+        );
+        assert_eq!(named(&args), vec![("k", "=|")]);
+
+        let yamd = deserialize(r"{{a|x\|y|k=\=\\|C:\\path}}");
+        let YamdNodes::Embed(embed) = &yamd.body[0] else {
+            panic!("expected embed, got {yamd:?}");
+        };
+        assert_eq!(embed.args, r"x\|y|k=\=\\|C:\path");
+        let args = embed.arguments();
+        assert_eq!(args.positional, vec!["x|y", r"C:\path"]);
+        assert_eq!(named(&args), vec![("k", r"=\")]);
+    }
+
+    #[test]
+    fn escaped_arguments_round_trip() {
+        for args in [r"x\|y", r"\\|\\\|\", r"a\b\\=c\", r"{%}\", "|="] {
+            let embed = Embed::new("a", args);
+            assert_eq!(
+                deserialize(&embed.to_string()).body,
+                vec![embed.clone().into()],
+                "{embed}"
+            );
+        }
+    }
+
     #[test]
     fn embed_with_escaped_parts() {
         let embed = Embed::new("youtube|as", "dQw4w9WgXcQ}}");
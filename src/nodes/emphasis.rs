@@ -31,7 +31,7 @@ use serde::{Deserialize, Serialize};
 /// construction.
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Emphasis(pub String);
+pub struct Emphasis<S = String>(pub S);
 
 impl Emphasis {
     pub fn new<Body: Into<String>>(body: Body) -> Self {
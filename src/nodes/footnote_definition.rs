@@ -0,0 +1,103 @@
+use std::fmt::Display;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{ParagraphNodes, paragraph::join_nodes};
+
+/// # Footnote definition
+///
+/// Starts at the beginning of a line with [Label](FootnoteDefinition::label) written the same way
+/// as in [FootnoteReference](super::FootnoteReference), followed by `:` and a
+/// [Space](type@crate::lexer::TokenKind::Space).
+///
+/// [Body](FootnoteDefinition::body) is everything until
+/// [Terminator](type@crate::lexer::TokenKind::Terminator) or end of input, and can contain the same
+/// nodes as [Paragraph](super::Paragraph).
+///
+/// Example:
+///
+/// ```text
+/// [^note]: Footnote can contain **bold** and [anchor](#)
+/// ```
+///
+/// HTML equivalent:
+///
+/// ```html
+/// <div class="footnote" id="fn-note">
+///     <sup>note</sup>
+///     Footnote can contain <b>bold</b> and <a href="#">anchor</a>
+/// </div>
+/// ```
+///
+/// # Round-trip invariant
+///
+/// Same as for [FootnoteReference](super::FootnoteReference): label must be non-empty and must
+/// not contain whitespace, `[`, `]`, `|` or `\`.
+#[derive(Debug, PartialEq, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FootnoteDefinition<S = String> {
+    pub label: S,
+    pub body: Vec<ParagraphNodes<S>>,
+}
+
+impl FootnoteDefinition {
+    pub fn new<L: Into<String>>(label: L, body: Vec<ParagraphNodes>) -> Self {
+        Self {
+            label: label.into(),
+            body,
+        }
+    }
+}
+
+impl Display for FootnoteDefinition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[^{}]: {}", self.label, join_nodes(&self.body))
+    }
+}
+
+/// Footnotes of a document, returned by [Yamd::footnotes](super::Yamd::footnotes).
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Footnotes<'a> {
+    /// Referenced definitions in order of the first reference. Number of a footnote is its index
+    /// plus one.
+    pub resolved: Vec<&'a FootnoteDefinition>,
+    /// Labels of references without a definition, in order of the first reference.
+    pub missing: Vec<String>,
+    /// Definitions that are never referenced, in document order. Includes definitions whose label
+    /// is already taken by a previous definition.
+    pub orphaned: Vec<&'a FootnoteDefinition>,
+}
+
+impl Footnotes<'_> {
+    /// Returns number of the footnote, `None` if the label is not [resolved](Footnotes::resolved).
+    pub fn number(&self, label: &str) -> Option<usize> {
+        self.resolved
+            .iter()
+            .position(|definition| definition.label == label)
+            .map(|index| index + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nodes::{Bold, BoldNodes, FootnoteDefinition, FootnoteReference, ParagraphNodes};
+
+    #[test]
+    fn footnote_definition() {
+        let definition = FootnoteDefinition::new(
+            "note",
+            vec![
+                ParagraphNodes::from("see ".to_string()),
+                Bold::new(vec![BoldNodes::from("bold".to_string())]).into(),
+                FootnoteReference::new("other").into(),
+            ],
+        );
+        assert_eq!(definition.to_string(), "[^note]: see **bold**[^other]");
+    }
+
+    #[test]
+    fn empty_body() {
+        assert_eq!(FootnoteDefinition::new("a", vec![]).to_string(), "[^a]: ");
+    }
+}
@@ -0,0 +1,65 @@
+use std::fmt::Display;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// # Footnote reference
+///
+/// [Label](FootnoteReference::label) surrounded by
+/// [LeftSquareBracket](type@crate::lexer::TokenKind::LeftSquareBracket) followed by `^` and
+/// [RightSquareBracket](type@crate::lexer::TokenKind::RightSquareBracket). Points to the
+/// [FootnoteDefinition](super::FootnoteDefinition) with the same label.
+///
+/// Example:
+///
+/// ```text
+/// Text with a footnote[^note].
+/// ```
+///
+/// HTML equivalent:
+///
+/// ```html
+/// Text with a footnote<sup><a href="#fn-note" id="fnref-note">note</a></sup>.
+/// ```
+///
+/// # Round-trip invariant
+///
+/// Label must be non-empty and must not contain whitespace, `[`, `]`, `|` or `\`, otherwise the
+/// reference is parsed back as text.
+#[derive(Debug, PartialEq, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FootnoteReference<S = String> {
+    pub label: S,
+}
+
+impl FootnoteReference {
+    pub fn new<L: Into<String>>(label: L) -> Self {
+        Self {
+            label: label.into(),
+        }
+    }
+}
+
+impl Display for FootnoteReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[^{}]", self.label)
+    }
+}
+
+/// Returns `true` if `label` can be a label of a footnote.
+pub(crate) fn is_footnote_label(label: &str) -> bool {
+    !label.is_empty()
+        && !label
+            .chars()
+            .any(|c| c.is_whitespace() || matches!(c, '[' | ']' | '|' | '\\'))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nodes::FootnoteReference;
+
+    #[test]
+    fn footnote_reference() {
+        assert_eq!(FootnoteReference::new("note").to_string(), "[^note]");
+    }
+}
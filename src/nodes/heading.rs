@@ -3,14 +3,15 @@ use std::fmt::Display;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use super::Anchor;
+use super::{Anchor, InlineMath, List, ListItem, ListTypes, join_inline};
 
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
-pub enum HeadingNodes {
-    Text(String),
-    Anchor(Anchor),
+pub enum HeadingNodes<S = String> {
+    Text(S),
+    Anchor(Anchor<S>),
+    InlineMath(InlineMath<S>),
 }
 
 impl From<String> for HeadingNodes {
@@ -19,12 +20,18 @@ impl From<String> for HeadingNodes {
     }
 }
 
-impl From<Anchor> for HeadingNodes {
-    fn from(anchor: Anchor) -> Self {
+impl<S> From<Anchor<S>> for HeadingNodes<S> {
+    fn from(anchor: Anchor<S>) -> Self {
         Self::Anchor(anchor)
     }
 }
 
+impl<S> From<InlineMath<S>> for HeadingNodes<S> {
+    fn from(math: InlineMath<S>) -> Self {
+        Self::InlineMath(math)
+    }
+}
+
 impl Display for HeadingNodes {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -35,9 +42,12 @@ impl Display for HeadingNodes {
                     text.replace("\\", "\\\\")
                         .replace("\n\n", "\\\n\n")
                         .replace("#", "\\#")
+                        .replace("[", "\\[")
+                        .replace("$", "\\$")
                 )
             }
             HeadingNodes::Anchor(anchor) => write!(f, "{}", anchor),
+            HeadingNodes::InlineMath(math) => write!(f, "{}", math),
         }
     }
 }
@@ -53,6 +63,7 @@ impl Display for HeadingNodes {
 /// [Body](Heading::body) can contain one or more:
 ///
 /// - [Anchor]
+/// - [InlineMath]
 /// - [String]
 ///
 /// Example:
@@ -64,31 +75,171 @@ impl Display for HeadingNodes {
 /// HTML equivalent:
 ///
 /// ```html
-/// <h3>Header can contain an <a href="#">anchor</a> or regular text.</h3>
+/// <h3 id="header-can-contain-an-anchor-or-regular-text">Header can contain an <a href="#">anchor</a> or regular text.</h3>
 /// ```
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Heading {
+pub struct Heading<S = String> {
     pub level: u8,
-    pub body: Vec<HeadingNodes>,
+    pub body: Vec<HeadingNodes<S>>,
 }
 
 impl Heading {
     pub fn new(level: u8, nodes: Vec<HeadingNodes>) -> Self {
         Self { level, body: nodes }
     }
+
+    /// Returns text of the heading without markup: [Anchor] text and [InlineMath] body are
+    /// flattened into the surrounding text.
+    ///
+    /// ```
+    /// let yamd = yamd::deserialize("## Set [up](#) $x$");
+    /// let yamd::nodes::YamdNodes::Heading(heading) = &yamd.body[0] else { unreachable!() };
+    /// assert_eq!(heading.text(), "Set up x");
+    /// ```
+    pub fn text(&self) -> String {
+        self.body
+            .iter()
+            .map(|node| match node {
+                HeadingNodes::Text(text) => text.as_str(),
+                HeadingNodes::Anchor(anchor) => anchor.text.as_str(),
+                HeadingNodes::InlineMath(math) => math.0.as_str(),
+            })
+            .collect()
+    }
+
+    /// Returns id of the heading in the form used by most Markdown renderers: [text](Heading::text)
+    /// in lowercase, with whitespace and `-` replaced by `-`, and characters other than
+    /// alphanumerics and `_` dropped.
+    ///
+    /// Headings with the same text have the same slug, use
+    /// [Yamd::heading_ids](crate::nodes::Yamd::heading_ids) for ids that are unique in the document.
+    ///
+    /// ```
+    /// let yamd = yamd::deserialize("## Hello, [World](#)!");
+    /// let yamd::nodes::YamdNodes::Heading(heading) = &yamd.body[0] else { unreachable!() };
+    /// assert_eq!(heading.slug(), "hello-world");
+    /// ```
+    pub fn slug(&self) -> String {
+        self.text()
+            .trim()
+            .chars()
+            .filter_map(|c| match c {
+                c if c.is_alphanumeric() || c == '_' => Some(c.to_lowercase().collect::<String>()),
+                c if c.is_whitespace() || c == '-' => Some("-".to_string()),
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 impl Display for Heading {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let body: String = self.body.iter().map(|n| n.to_string()).collect();
+        let mut nodes: Vec<(String, bool)> = self
+            .body
+            .iter()
+            .map(|n| (n.to_string(), matches!(n, HeadingNodes::Text(_))))
+            .collect();
+        let source: String = nodes.iter().map(|(node, _)| node.as_str()).collect();
+        let mut offset = 0;
+        for (node, is_text) in &mut nodes {
+            let start = offset;
+            offset += node.len();
+            if !*is_text {
+                continue;
+            }
+            let escapes: Vec<usize> = node
+                .match_indices('[')
+                .map(|(i, _)| i - 1)
+                .filter(|&i| !opens_anchor(&source, start + i))
+                .collect();
+            for escape in escapes.into_iter().rev() {
+                node.remove(escape);
+            }
+        }
+        let body = join_inline(nodes);
         write!(f, "{} {}", "#".repeat(self.level as usize), body)
     }
 }
 
+/// Returns `true` if the `[` escaped at `escape` would open an [Anchor] otherwise, or would be
+/// joined with a neighbouring `[`.
+fn opens_anchor(text: &str, escape: usize) -> bool {
+    let bracket = escape + 1;
+    if text[..escape].ends_with('[') || text[bracket + 1..].starts_with('[') {
+        return true;
+    }
+    let bytes = text.as_bytes();
+    let mut i = bracket + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b']' => {
+                let rest = &text[i + 1..];
+                return rest.starts_with(']') || (rest.starts_with('(') && rest.contains(')'));
+            }
+            _ => i += 1,
+        }
+    }
+    false
+}
+
+/// Outline of a document, returned by [Yamd::toc](super::Yamd::toc).
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Toc<'a> {
+    /// Top level entries in document order.
+    pub entries: Vec<TocEntry<'a>>,
+}
+
+/// Entry of a [Toc].
+#[derive(Debug, PartialEq, Clone)]
+pub struct TocEntry<'a> {
+    pub heading: &'a Heading,
+    /// Id of the heading, unique in the document, see
+    /// [Yamd::heading_ids](super::Yamd::heading_ids).
+    pub id: String,
+    /// Entries of the following headings with a greater level, up to the next heading with the same
+    /// or a lower level.
+    pub children: Vec<TocEntry<'a>>,
+}
+
+impl Toc<'_> {
+    /// Returns the outline as an unordered [List] of [Anchor]s to the headings, `None` if there
+    /// are no entries.
+    ///
+    /// ```
+    /// let yamd = yamd::deserialize("# Intro\n\n## Set up\n\n# Usage");
+    /// let list = yamd.toc(1..=6).to_list().unwrap();
+    /// assert_eq!(
+    ///     list.to_string(),
+    ///     "- [Intro](#intro)\n - [Set up](#set-up)\n- [Usage](#usage)"
+    /// );
+    /// ```
+    pub fn to_list(&self) -> Option<List> {
+        list(&self.entries, 0)
+    }
+}
+
+fn list(entries: &[TocEntry], level: usize) -> Option<List> {
+    if entries.is_empty() {
+        return None;
+    }
+    let body = entries
+        .iter()
+        .map(|entry| {
+            let anchor = Anchor::new(
+                entry.heading.text().trim().to_string(),
+                format!("#{}", entry.id),
+            );
+            ListItem::new(vec![anchor.into()], list(&entry.children, level + 1))
+        })
+        .collect();
+    Some(List::new(ListTypes::Unordered, level, body))
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::nodes::{Anchor, Heading, HeadingNodes};
+    use crate::nodes::{Anchor, Heading, HeadingNodes, InlineMath};
 
     #[test]
     fn heading() {
@@ -106,6 +257,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn heading_escapes_bracket_only_before_anchor() {
+        for (text, expected) in [
+            ("[a] b", "# [a] b"),
+            ("a [b", "# a [b"),
+            ("[a](b) c", "# \\[a](b) c"),
+            ("[[a]](b)", "# \\[\\[a]](b)"),
+        ] {
+            let heading = Heading::new(1, vec![HeadingNodes::from(text.to_string())]);
+            assert_eq!(heading.to_string(), expected, "{text}");
+        }
+        let heading = Heading::new(
+            1,
+            vec![
+                HeadingNodes::from("[a ".to_string()),
+                HeadingNodes::from(Anchor::new("b", "c")),
+            ],
+        );
+        assert_eq!(heading.to_string(), "# \\[a [b](c)");
+    }
+
+    #[test]
+    fn heading_with_math() {
+        let heading = Heading::new(
+            2,
+            vec![
+                HeadingNodes::from("$1 for ".to_string()),
+                HeadingNodes::from(InlineMath::new("x^2")),
+            ],
+        );
+        assert_eq!(heading.to_string(), "## $1 for $x^2$");
+    }
+
+    #[test]
+    fn slug() {
+        for (text, slug) in [
+            ("Hello, World!", "hello-world"),
+            (" snake_case and-dash ", "snake_case-and-dash"),
+            ("Ünïcode 🤔", "ünïcode-"),
+        ] {
+            let heading = Heading::new(1, vec![HeadingNodes::from(text.to_string())]);
+            assert_eq!(heading.slug(), slug);
+        }
+    }
+
     #[test]
     fn heading_with_hash() {
         let heading = Heading::new(3, vec![HeadingNodes::from("# ##".to_string())]);
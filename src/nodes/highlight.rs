@@ -52,10 +52,10 @@ use super::{Paragraph, paragraph::escape_leading_block_marker};
 ///
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Highlight {
-    pub title: Option<String>,
-    pub icon: Option<String>,
-    pub body: Vec<Paragraph>,
+pub struct Highlight<S = String> {
+    pub title: Option<S>,
+    pub icon: Option<S>,
+    pub body: Vec<Paragraph<S>>,
 }
 
 impl Highlight {
@@ -35,9 +35,9 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Image {
-    pub alt: String,
-    pub src: String,
+pub struct Image<S = String> {
+    pub alt: S,
+    pub src: S,
 }
 
 impl Image {
@@ -37,8 +37,8 @@ use super::Image;
 
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Images {
-    pub body: Vec<Image>,
+pub struct Images<S = String> {
+    pub body: Vec<Image<S>>,
 }
 
 impl Images {
@@ -0,0 +1,164 @@
+use std::fmt::Display;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// # Inline math
+///
+/// Any characters except [Terminator](type@crate::lexer::TokenKind::Terminator) surrounded by a
+/// [Dollar](type@crate::lexer::TokenKind::Dollar) of length 1. Like in pandoc, the opening `$`
+/// must not be followed by whitespace, and the closing `$` must not be preceded by whitespace or
+/// followed by a digit, so `$5 or $10` is text.
+///
+/// Body is verbatim, so TeX commands can be written as is: `\` stays in the body. `\$` does not
+/// close the math, it is kept in the body as `\$`.
+///
+/// Example:
+///
+/// ```text
+/// $\sqrt{a^2 + b^2}$
+/// ```
+///
+/// HTML equivalent:
+///
+/// ```html
+/// <span class="math math-inline">\sqrt{a^2 + b^2}</span>
+/// ```
+///
+/// # Round-trip invariant
+///
+/// Body has no escapes, so it must be non-empty, must not contain `$` or an empty line, must not
+/// start or end with whitespace, and must not end with `\`. Text right after it must not start
+/// with a digit. Any other `InlineMath` will not round-trip.
+#[derive(Debug, PartialEq, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InlineMath<S = String>(pub S);
+
+impl InlineMath {
+    pub fn new<Body: Into<String>>(body: Body) -> Self {
+        InlineMath(body.into())
+    }
+}
+
+impl Display for InlineMath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        write!(f, "${}$", self.0)
+    }
+}
+
+impl From<String> for InlineMath {
+    fn from(value: String) -> Self {
+        InlineMath(value)
+    }
+}
+
+/// Returns `true` if `body` can be a body of [InlineMath] that round-trips.
+#[cfg(feature = "commonmark")]
+pub(crate) fn is_inline_math(body: &str) -> bool {
+    let mut escaped = false;
+    for c in body.chars() {
+        if c == '$' && !escaped {
+            return false;
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    !body.is_empty()
+        && !escaped
+        && !body.starts_with(char::is_whitespace)
+        && !body.ends_with(char::is_whitespace)
+        && !body.contains("\n\n")
+        && !body.contains("\n\r\n")
+}
+
+/// Joins serialized inline nodes, `true` marks a text node that escapes every `$`. An escape is
+/// kept only where the `$` would open [InlineMath] otherwise, so `cost $5` is written as is.
+pub(crate) fn join_inline<I: IntoIterator<Item = (String, bool)>>(nodes: I) -> String {
+    let mut out = String::new();
+    let mut escapes = vec![];
+    for (node, is_text) in nodes {
+        if is_text {
+            escapes.extend(node.match_indices('$').map(|(i, _)| out.len() + i - 1));
+        }
+        out.push_str(&node);
+    }
+    let mut dollars = single_dollars(&out);
+    let mut removed = vec![];
+    for escape in escapes.into_iter().rev() {
+        let dollar = escape + 1;
+        if !opens_inline_math(&out, escape, &dollars) {
+            let index = dollars.partition_point(|&d| d < dollar);
+            dollars.insert(index, dollar);
+            removed.push(escape);
+        }
+    }
+    for escape in removed {
+        out.remove(escape);
+    }
+    out
+}
+
+/// Positions of unescaped `$` that are not part of `$$`.
+fn single_dollars(text: &str) -> Vec<usize> {
+    let bytes = text.as_bytes();
+    let mut dollars = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'$' => {
+                let len = bytes[i..].iter().take_while(|&&b| b == b'$').count();
+                if len == 1 {
+                    dollars.push(i);
+                }
+                i += len;
+            }
+            _ => i += 1,
+        }
+    }
+    dollars
+}
+
+/// Returns `true` if the `$` escaped at `escape` would open [InlineMath] closed by one of
+/// `dollars`, or would be joined with a neighbouring `$`.
+fn opens_inline_math(text: &str, escape: usize, dollars: &[usize]) -> bool {
+    let dollar = escape + 1;
+    let after = &text[dollar + 1..];
+    if text[..escape].ends_with('$') || after.starts_with('$') {
+        return true;
+    }
+    if after.is_empty() || after.starts_with(char::is_whitespace) {
+        return false;
+    }
+    let Some(&close) = dollars.iter().find(|&&d| d > dollar) else {
+        return false;
+    };
+    !text[..close].ends_with(char::is_whitespace)
+        && !text[close + 1..].starts_with(|c: char| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inline_math() {
+        assert_eq!(
+            InlineMath::new("\\frac{a}{b}").to_string(),
+            "$\\frac{a}{b}$"
+        );
+        assert_eq!(InlineMath::new("").to_string(), "");
+    }
+
+    #[test]
+    #[cfg(feature = "commonmark")]
+    fn is_inline_math() {
+        assert!(super::is_inline_math("a\\$b"));
+        assert!(super::is_inline_math("a\nb"));
+        for body in ["", "a$b", "a\\", "a\n\nb", " a", "a\n"] {
+            assert!(!super::is_inline_math(body), "{body}");
+        }
+    }
+}
@@ -30,7 +30,7 @@ use serde::{Deserialize, Serialize};
 /// future breaking change is expected to reject empty bodies at construction.
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Italic(pub String);
+pub struct Italic<S = String>(pub S);
 
 impl Italic {
     pub fn new<Body: Into<String>>(body: Body) -> Self {
@@ -134,10 +134,10 @@ impl Display for ListTypes {
 ///
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct List {
+pub struct List<S = String> {
     pub list_type: ListTypes,
     pub level: usize,
-    pub body: Vec<ListItem>,
+    pub body: Vec<ListItem<S>>,
 }
 
 impl List {
@@ -3,7 +3,7 @@ use std::fmt::Display;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use super::{List, ParagraphNodes};
+use super::{List, ParagraphNodes, paragraph::join_nodes};
 
 /// # ListItem
 ///
@@ -20,6 +20,28 @@ use super::{List, ParagraphNodes};
 /// <ul><li>Item text<ul><li>Nested item</li></ul></li><ul>
 /// ```
 ///
+/// ## Task
+///
+/// Item text can start with `[ ]` or `[x]` ([LeftSquareBracket](type@crate::lexer::TokenKind::LeftSquareBracket),
+/// one [Space](type@crate::lexer::TokenKind::Space) or `x`/`X`,
+/// [RightSquareBracket](type@crate::lexer::TokenKind::RightSquareBracket)) followed by one
+/// [Space](type@crate::lexer::TokenKind::Space). Such item is a task, [checked](ListItem::checked)
+/// tells if it is done.
+///
+/// ```text
+/// - [x] Done
+/// - [ ] Not yet
+/// ```
+///
+/// HTML equivalent:
+///
+/// ```html
+/// <ul>
+///     <li><input type="checkbox" disabled checked />Done</li>
+///     <li><input type="checkbox" disabled />Not yet</li>
+/// </ul>
+/// ```
+///
 /// # Round-trip invariant
 ///
 /// The grammar requires exactly one space between the list marker and the item
@@ -28,24 +50,72 @@ use super::{List, ParagraphNodes};
 /// serializing and re-parsing it will not yield an equal value.
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct ListItem {
-    pub text: Vec<ParagraphNodes>,
-    pub nested_list: Option<List>,
+pub struct ListItem<S = String> {
+    /// `Some` if the item is a task, `true` if the task is done.
+    pub checked: Option<bool>,
+    pub text: Vec<ParagraphNodes<S>>,
+    pub nested_list: Option<List<S>>,
 }
 
 impl ListItem {
     /// See the type-level docs for the round-trip invariant on `text`.
     pub fn new(text: Vec<ParagraphNodes>, nested_list: Option<List>) -> Self {
-        Self { text, nested_list }
+        Self {
+            checked: None,
+            text,
+            nested_list,
+        }
     }
+
+    /// Creates a [task](ListItem#task), `checked` tells if it is done.
+    pub fn task(checked: bool, text: Vec<ParagraphNodes>, nested_list: Option<List>) -> Self {
+        Self {
+            checked: Some(checked),
+            text,
+            nested_list,
+        }
+    }
+}
+
+/// Number of tasks in a document, see [Yamd::tasks](crate::nodes::Yamd::tasks).
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Default)]
+pub struct Tasks {
+    pub completed: usize,
+    pub total: usize,
 }
 
 impl Display for ListItem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let text: String = self.text.iter().map(|t| t.to_string()).collect();
+        let text = join_nodes(&self.text);
+        match self.checked {
+            Some(true) => f.write_str("[x] ")?,
+            Some(false) => f.write_str("[ ] ")?,
+            None => {}
+        }
         match &self.nested_list {
             Some(nested) => write!(f, "{}\n{}", text, nested),
             None => write!(f, "{}", text),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::nodes::ListItem;
+
+    #[test]
+    fn task() {
+        let text = || vec!["a".to_string().into()];
+        assert_eq!(ListItem::task(true, text(), None).to_string(), "[x] a");
+        assert_eq!(ListItem::task(false, text(), None).to_string(), "[ ] a");
+        assert_eq!(ListItem::new(text(), None).to_string(), "a");
+    }
+
+    #[test]
+    fn text_that_looks_like_task() {
+        let item = ListItem::new(vec!["[x] a".to_string().into()], None);
+        assert_eq!(item.to_string(), "\\[x] a");
+        let item = ListItem::task(false, vec!["[x] a".to_string().into()], None);
+        assert_eq!(item.to_string(), "[ ] \\[x] a");
+    }
+}
@@ -9,13 +9,18 @@
 //!
 //! - [`Paragraph`] — a run of inline nodes ([`ParagraphNodes`])
 //! - [`Heading`] — `#`..`######`, with inline content ([`HeadingNodes`])
-//! - [`List`] — ordered or unordered, see [`ListTypes`], made up of [`ListItem`]s
+//! - [`List`] — ordered or unordered, see [`ListTypes`], made up of [`ListItem`]s, which can be
+//!   tasks, see [`Yamd::tasks`] and [`Tasks`]
 //! - [`Code`] — fenced code block
 //! - [`Image`] / [`Images`] — a single image, or a group of images
 //! - [`Highlight`] — a callout/admonition block
 //! - [`Collapsible`] — a `<details>`-style disclosure block
 //! - [`ThematicBreak`] — `---`
 //! - [`Embed`] — an embedded external resource
+//! - [`Table`] — rows of [`TableCell`]s, see [`TableRow`] and [`TableAlignment`]
+//! - [`FootnoteDefinition`] — body of a footnote, see [`Yamd::footnotes`] and [`Footnotes`]
+//! - [`DisplayMath`] — `$$` fenced TeX formula
+//! - [`Custom`] — a block parsed by a [`BlockExtension`](crate::op::BlockExtension)
 //!
 //! # Inline nodes
 //!
@@ -24,8 +29,15 @@
 //! - [`Anchor`] — a link
 //! - [`Bold`] ([`BoldNodes`]) / [`Italic`] / [`Emphasis`] / [`Strikethrough`] — inline text styling
 //! - [`CodeSpan`] — inline code
+//! - [`FootnoteReference`] — a reference to a footnote, paragraph only
+//! - [`InlineMath`] — `$` delimited TeX formula
 //! - `String` — plain text
 //!
+//! Nodes with text are generic over its type, which is `String` unless stated otherwise, so `Yamd`
+//! is `Yamd<String>`. [`deserialize_borrowed`](crate::deserialize_borrowed) builds
+//! `Yamd<Cow<str>>`, whose text borrows from the source. Constructors, [`Display`](std::fmt::Display)
+//! and the methods that inspect a document are implemented for `String` text.
+//!
 //! With the `serde` feature enabled, every node above is also
 //! [`Serialize`](serde::Serialize)/[`Deserialize`](serde::Deserialize).
 
@@ -34,17 +46,23 @@ mod bold;
 mod code;
 mod code_span;
 mod collapsible;
+mod custom;
+mod display_math;
 mod embed;
 mod emphasis;
+mod footnote_definition;
+mod footnote_reference;
 mod heading;
 mod highlight;
 mod image;
 mod images;
+mod inline_math;
 mod italic;
 mod list;
 mod list_item;
 mod paragraph;
 mod strikethrough;
+mod table;
 mod thematic_break;
 mod yamd;
 
@@ -53,16 +71,29 @@ pub use bold::{Bold, BoldNodes};
 pub use code::Code;
 pub use code_span::CodeSpan;
 pub use collapsible::Collapsible;
-pub use embed::Embed;
+pub use custom::Custom;
+pub use display_math::DisplayMath;
+#[cfg(feature = "commonmark")]
+pub(crate) use display_math::is_display_math;
+pub use embed::{Embed, EmbedArgs};
 pub use emphasis::Emphasis;
-pub use heading::{Heading, HeadingNodes};
+pub use footnote_definition::{FootnoteDefinition, Footnotes};
+pub use footnote_reference::FootnoteReference;
+pub(crate) use footnote_reference::is_footnote_label;
+pub use heading::{Heading, HeadingNodes, Toc, TocEntry};
 pub use highlight::Highlight;
 pub use image::Image;
 pub use images::Images;
+pub use inline_math::InlineMath;
+#[cfg(feature = "commonmark")]
+pub(crate) use inline_math::is_inline_math;
+pub(crate) use inline_math::join_inline;
 pub use italic::Italic;
 pub use list::{List, ListTypes};
-pub use list_item::ListItem;
+pub use list_item::{ListItem, Tasks};
 pub use paragraph::{Paragraph, ParagraphNodes};
 pub use strikethrough::Strikethrough;
+pub(crate) use table::is_alignment_cell;
+pub use table::{Table, TableAlignment, TableCell, TableRow};
 pub use thematic_break::ThematicBreak;
-pub use yamd::{Yamd, YamdNodes};
+pub use yamd::{MetadataDelimiter, Yamd, YamdNodes};
@@ -3,41 +3,46 @@ use std::fmt::Display;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use super::{Anchor, Bold, CodeSpan, Emphasis, Italic, Strikethrough};
+use super::{
+    Anchor, Bold, CodeSpan, Emphasis, FootnoteReference, InlineMath, Italic, Strikethrough,
+    join_inline,
+};
 
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
-pub enum ParagraphNodes {
-    Anchor(Anchor),
-    Bold(Bold),
-    Italic(Italic),
-    Strikethrough(Strikethrough),
-    Text(String),
-    CodeSpan(CodeSpan),
-    Emphasis(Emphasis),
-}
-
-impl From<Anchor> for ParagraphNodes {
-    fn from(value: Anchor) -> Self {
+pub enum ParagraphNodes<S = String> {
+    Anchor(Anchor<S>),
+    Bold(Bold<S>),
+    Italic(Italic<S>),
+    Strikethrough(Strikethrough<S>),
+    Text(S),
+    CodeSpan(CodeSpan<S>),
+    Emphasis(Emphasis<S>),
+    FootnoteReference(FootnoteReference<S>),
+    InlineMath(InlineMath<S>),
+}
+
+impl<S> From<Anchor<S>> for ParagraphNodes<S> {
+    fn from(value: Anchor<S>) -> Self {
         ParagraphNodes::Anchor(value)
     }
 }
 
-impl From<Bold> for ParagraphNodes {
-    fn from(value: Bold) -> Self {
+impl<S> From<Bold<S>> for ParagraphNodes<S> {
+    fn from(value: Bold<S>) -> Self {
         ParagraphNodes::Bold(value)
     }
 }
 
-impl From<Italic> for ParagraphNodes {
-    fn from(value: Italic) -> Self {
+impl<S> From<Italic<S>> for ParagraphNodes<S> {
+    fn from(value: Italic<S>) -> Self {
         ParagraphNodes::Italic(value)
     }
 }
 
-impl From<Strikethrough> for ParagraphNodes {
-    fn from(value: Strikethrough) -> Self {
+impl<S> From<Strikethrough<S>> for ParagraphNodes<S> {
+    fn from(value: Strikethrough<S>) -> Self {
         ParagraphNodes::Strikethrough(value)
     }
 }
@@ -48,18 +53,30 @@ impl From<String> for ParagraphNodes {
     }
 }
 
-impl From<CodeSpan> for ParagraphNodes {
-    fn from(value: CodeSpan) -> Self {
+impl<S> From<CodeSpan<S>> for ParagraphNodes<S> {
+    fn from(value: CodeSpan<S>) -> Self {
         ParagraphNodes::CodeSpan(value)
     }
 }
 
-impl From<Emphasis> for ParagraphNodes {
-    fn from(value: Emphasis) -> Self {
+impl<S> From<Emphasis<S>> for ParagraphNodes<S> {
+    fn from(value: Emphasis<S>) -> Self {
         ParagraphNodes::Emphasis(value)
     }
 }
 
+impl<S> From<FootnoteReference<S>> for ParagraphNodes<S> {
+    fn from(value: FootnoteReference<S>) -> Self {
+        ParagraphNodes::FootnoteReference(value)
+    }
+}
+
+impl<S> From<InlineMath<S>> for ParagraphNodes<S> {
+    fn from(value: InlineMath<S>) -> Self {
+        ParagraphNodes::InlineMath(value)
+    }
+}
+
 impl Display for ParagraphNodes {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -77,11 +94,14 @@ impl Display for ParagraphNodes {
                     .replace("`", "\\`")
                     .replace("[", "\\[")
                     .replace("{", "\\{")
+                    .replace("$", "\\$")
                     .replace("\n\n", "\\\n\\\n")
                     .replace("%}", "\\%}")
             ),
             ParagraphNodes::CodeSpan(c) => write!(f, "{}", c),
             ParagraphNodes::Emphasis(e) => write!(f, "{}", e),
+            ParagraphNodes::FootnoteReference(r) => write!(f, "{}", r),
+            ParagraphNodes::InlineMath(m) => write!(f, "{}", m),
         }
     }
 }
@@ -98,6 +118,8 @@ impl Display for ParagraphNodes {
 /// - [Italic]
 /// - [Strikethrough]
 /// - [Emphasis]
+/// - [FootnoteReference]
+/// - [InlineMath]
 /// - [String]
 ///
 /// Example:
@@ -128,8 +150,8 @@ impl Display for ParagraphNodes {
 ///
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Paragraph {
-    pub body: Vec<ParagraphNodes>,
+pub struct Paragraph<S = String> {
+    pub body: Vec<ParagraphNodes<S>>,
 }
 
 impl Paragraph {
@@ -156,35 +178,54 @@ impl From<Vec<ParagraphNodes>> for Paragraph {
 
 impl Display for Paragraph {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for node in &self.body {
-            write!(f, "{}", node)?;
-        }
-        Ok(())
+        f.write_str(&join_nodes(&self.body))
     }
 }
 
+/// Serializes paragraph nodes, `$` in text is escaped only where it would open [InlineMath].
+pub(crate) fn join_nodes(nodes: &[ParagraphNodes]) -> String {
+    join_inline(
+        nodes
+            .iter()
+            .map(|node| (node.to_string(), matches!(node, ParagraphNodes::Text(_)))),
+    )
+}
+
 /// Prepends a `\` escape when the serialized paragraph starts with a block-start
 /// marker that would otherwise hijack re-parsing inside a recursively-parsed
 /// container (Highlight body, Collapsible body). Must be applied AFTER any
 /// container-specific closer escape (e.g. Highlight's universal `!!` escape),
 /// because those escapes can rewrite the leading characters.
 pub(crate) fn escape_leading_block_marker(s: String) -> String {
+    let hashes = s.len() - s.trim_start_matches('#').len();
     if s.starts_with("- ")
         || s.starts_with("+ ")
-        || s.starts_with("# ")
+        || ((1..7).contains(&hashes) && s[hashes..].starts_with(' '))
         || s.starts_with("!!")
+        || s.starts_with("![")
         || s.starts_with("---")
+        || s.starts_with('|')
     {
         format!("\\{}", s)
+    } else if let Some(colon) = footnote_definition_colon(&s) {
+        format!("{}\\{}", &s[..colon], &s[colon..])
     } else {
         s
     }
 }
 
+/// Returns position of `:` if the serialized paragraph starts with a
+/// [FootnoteReference] followed by `: `, which would be parsed back as a
+/// footnote definition.
+fn footnote_definition_colon(s: &str) -> Option<usize> {
+    let colon = s.strip_prefix("[^")?.find(']')? + 3;
+    s[colon..].starts_with(": ").then_some(colon)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::nodes::{
-        Anchor, Bold, BoldNodes, CodeSpan, Emphasis, Italic, Paragraph, ParagraphNodes,
+        Anchor, Bold, BoldNodes, CodeSpan, Emphasis, InlineMath, Italic, Paragraph, ParagraphNodes,
         Strikethrough,
     };
 
@@ -223,6 +264,39 @@ mod tests {
         assert_eq!(paragraph.to_string(), "\\%}");
     }
 
+    #[test]
+    fn paragraph_text_with_dollar() {
+        let paragraph = Paragraph::new(vec![
+            ParagraphNodes::from("$5 for ".to_string()),
+            ParagraphNodes::from(InlineMath::new("x^2")),
+        ]);
+        assert_eq!(paragraph.to_string(), "$5 for $x^2$");
+    }
+
+    #[test]
+    fn paragraph_text_escapes_dollar_only_before_math() {
+        for (text, expected) in [
+            ("cost $5", "cost $5"),
+            ("$5 or $10", "$5 or $10"),
+            ("a $ b $ c", "a $ b $ c"),
+            ("$x$", "\\$x$"),
+            ("$x$ and $y", "\\$x$ and $y"),
+            ("$$", "$\\$"),
+        ] {
+            let paragraph = Paragraph::new(vec![ParagraphNodes::from(text.to_string())]);
+            assert_eq!(paragraph.to_string(), expected, "{text}");
+        }
+    }
+
+    #[test]
+    fn paragraph_text_escapes_dollar_closed_by_other_node() {
+        let paragraph = Paragraph::new(vec![
+            ParagraphNodes::from("$a ".to_string()),
+            ParagraphNodes::from(CodeSpan::new("b$")),
+        ]);
+        assert_eq!(paragraph.to_string(), "\\$a `b$`");
+    }
+
     #[test]
     fn default_produces_empty_paragraph() {
         let p: Paragraph = Default::default();
@@ -31,7 +31,7 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Strikethrough(pub String);
+pub struct Strikethrough<S = String>(pub S);
 
 impl Strikethrough {
     pub fn new<Body: Into<String>>(body: Body) -> Self {
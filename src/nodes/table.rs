@@ -0,0 +1,248 @@
+use std::fmt::Display;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{ParagraphNodes, paragraph::join_nodes};
+
+/// Horizontal alignment of a [Table] column, set by the alignment row.
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TableAlignment {
+    /// `---`
+    None,
+    /// `:--`
+    Left,
+    /// `:-:`
+    Center,
+    /// `--:`
+    Right,
+}
+
+impl Display for TableAlignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TableAlignment::None => f.write_str("---"),
+            TableAlignment::Left => f.write_str(":--"),
+            TableAlignment::Center => f.write_str(":-:"),
+            TableAlignment::Right => f.write_str("--:"),
+        }
+    }
+}
+
+/// # TableCell
+///
+/// Any token except [Terminator](type@crate::lexer::TokenKind::Terminator) and
+/// [EOL](type@crate::lexer::TokenKind::Eol) between two [Pipe](type@crate::lexer::TokenKind::Pipe)s.
+/// One [Space](type@crate::lexer::TokenKind::Space) on each side is padding and is not part of the
+/// [body](TableCell::body).
+///
+/// [Body](TableCell::body) can contain the same nodes as [Paragraph](super::Paragraph).
+#[derive(Debug, PartialEq, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TableCell<S = String> {
+    pub body: Vec<ParagraphNodes<S>>,
+}
+
+impl TableCell {
+    pub fn new(body: Vec<ParagraphNodes>) -> Self {
+        Self { body }
+    }
+}
+
+impl Display for TableCell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let body = join_nodes(&self.body);
+        let mut out = String::with_capacity(body.len());
+        if is_alignment_cell(&body) {
+            out.push('\\');
+        }
+        let mut chars = body.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    out.push(c);
+                    out.extend(chars.next());
+                }
+                '|' | '\n' => {
+                    out.push('\\');
+                    out.push(c);
+                }
+                _ => out.push(c),
+            }
+        }
+        f.write_str(&out)
+    }
+}
+
+/// Returns `true` if `cell` would be read as a cell of the alignment row.
+pub(crate) fn is_alignment_cell(cell: &str) -> bool {
+    let cell = cell.trim_matches(' ');
+    let cell = cell.strip_prefix(':').unwrap_or(cell);
+    let cell = cell.strip_suffix(':').unwrap_or(cell);
+    !cell.is_empty() && cell.chars().all(|c| c == '-')
+}
+
+/// # TableRow
+///
+/// Starts with [Pipe](type@crate::lexer::TokenKind::Pipe) at the beginning of a line, followed by
+/// one or more [TableCell]s, each closed by [Pipe](type@crate::lexer::TokenKind::Pipe).
+#[derive(Debug, PartialEq, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TableRow<S = String> {
+    pub cells: Vec<TableCell<S>>,
+}
+
+impl TableRow {
+    pub fn new(cells: Vec<TableCell>) -> Self {
+        Self { cells }
+    }
+}
+
+impl Display for TableRow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "|")?;
+        for cell in &self.cells {
+            write!(f, " {} |", cell)?;
+        }
+        Ok(())
+    }
+}
+
+/// # Table
+///
+/// [TableRow]s separated by [EOL](type@crate::lexer::TokenKind::Eol). The first row is the
+/// [header](Table::header).
+///
+/// The row right after the header is the [alignment row](Table::alignments), every cell of it
+/// consists of [Minus](type@crate::lexer::TokenKind::Minus), optionally prefixed and/or suffixed
+/// with `:`. Lines without an alignment row after the first one are not a table.
+///
+/// Every other row goes to [body](Table::body).
+///
+/// Example:
+///
+/// ```text
+/// | Name | Value |
+/// | :-- | --: |
+/// | **bold** | [anchor](#) |
+/// ```
+///
+/// HTML equivalent:
+///
+/// ```html
+/// <table>
+///     <thead>
+///         <tr>
+///             <th style="text-align: left">Name</th>
+///             <th style="text-align: right">Value</th>
+///         </tr>
+///     </thead>
+///     <tbody>
+///         <tr>
+///             <td style="text-align: left"><b>bold</b></td>
+///             <td style="text-align: right"><a href="#">anchor</a></td>
+///         </tr>
+///     </tbody>
+/// </table>
+/// ```
+///
+/// # Round-trip invariant
+///
+/// A row without cells has no grammatical form, so a `Table` with an empty header, an empty body
+/// row, or empty [alignments](Table::alignments) will not round-trip.
+#[derive(Debug, PartialEq, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Table<S = String> {
+    pub header: TableRow<S>,
+    pub alignments: Vec<TableAlignment>,
+    pub body: Vec<TableRow<S>>,
+}
+
+impl Table {
+    pub fn new(header: TableRow, alignments: Vec<TableAlignment>, body: Vec<TableRow>) -> Self {
+        Self {
+            header,
+            alignments,
+            body,
+        }
+    }
+
+    /// Returns alignment of the column, [TableAlignment::None] if the alignment row has fewer
+    /// cells.
+    pub fn alignment(&self, column: usize) -> TableAlignment {
+        self.alignments
+            .get(column)
+            .copied()
+            .unwrap_or(TableAlignment::None)
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}\n|", self.header)?;
+        for alignment in &self.alignments {
+            write!(f, " {} |", alignment)?;
+        }
+        for row in &self.body {
+            write!(f, "\n{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nodes::{
+        Anchor, Bold, BoldNodes, ParagraphNodes, Table, TableAlignment, TableCell, TableRow,
+    };
+
+    fn cell(text: &str) -> TableCell {
+        TableCell::new(vec![ParagraphNodes::from(text.to_string())])
+    }
+
+    #[test]
+    fn table() {
+        let table = Table::new(
+            TableRow::new(vec![cell("Name"), cell("Value")]),
+            vec![TableAlignment::Left, TableAlignment::Right],
+            vec![TableRow::new(vec![
+                TableCell::new(vec![
+                    Bold::new(vec![BoldNodes::from("bold".to_string())]).into(),
+                ]),
+                TableCell::new(vec![Anchor::new("anchor", "#").into()]),
+            ])],
+        );
+        assert_eq!(
+            table.to_string(),
+            "| Name | Value |\n| :-- | --: |\n| **bold** | [anchor](#) |"
+        );
+    }
+
+    #[test]
+    fn alignment_of_column_without_alignment() {
+        let table = Table::new(
+            TableRow::new(vec![cell("a"), cell("b")]),
+            vec![TableAlignment::Right],
+            vec![],
+        );
+        assert_eq!(table.to_string(), "| a | b |\n| --: |");
+        assert_eq!(table.alignment(1), TableAlignment::None);
+    }
+
+    #[test]
+    fn cell_escapes_pipe_and_new_line() {
+        assert_eq!(cell("a|b\nc").to_string(), "a\\|b\\\nc");
+        assert_eq!(cell("a\\|").to_string(), "a\\\\\\|");
+        assert_eq!(
+            TableCell::new(vec![Anchor::new("a", "b|c").into()]).to_string(),
+            "[a](b\\|c)"
+        );
+    }
+
+    #[test]
+    fn cell_that_looks_like_alignment() {
+        assert_eq!(cell(":--").to_string(), "\\:--");
+        assert_eq!(cell("a--").to_string(), "a--");
+    }
+}
@@ -1,89 +1,122 @@
-use std::fmt::Display;
+use std::{collections::HashSet, fmt::Display, iter::Peekable, ops::RangeInclusive};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use super::{
-    Code, Collapsible, Embed, Heading, Highlight, Image, Images, List, Paragraph, ThematicBreak,
-    paragraph::escape_leading_block_marker,
+    Code, Collapsible, Custom, DisplayMath, Embed, FootnoteDefinition, FootnoteReference,
+    Footnotes, Heading, Highlight, Image, Images, List, ListItem, Paragraph, Table, Tasks,
+    ThematicBreak, Toc, TocEntry, paragraph::escape_leading_block_marker,
+};
+use crate::{
+    metadata::{self, MetadataError, MetadataValue},
+    visit::{Visit, walk_list_item},
 };
 
 #[derive(Debug, PartialEq, Clone, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
-pub enum YamdNodes {
-    Paragraph(Paragraph),
-    Heading(Heading),
-    Image(Image),
-    Images(Images),
-    Code(Code),
-    List(List),
-    Highlight(Highlight),
+pub enum YamdNodes<S = String> {
+    Paragraph(Paragraph<S>),
+    Heading(Heading<S>),
+    Image(Image<S>),
+    Images(Images<S>),
+    Code(Code<S>),
+    List(List<S>),
+    Highlight(Highlight<S>),
     ThematicBreak(ThematicBreak),
-    Embed(Embed),
-    Collapsible(Collapsible),
+    Embed(Embed<S>),
+    Collapsible(Collapsible<S>),
+    Table(Table<S>),
+    FootnoteDefinition(FootnoteDefinition<S>),
+    DisplayMath(DisplayMath<S>),
+    Custom(Custom<S>),
 }
 
-impl From<Paragraph> for YamdNodes {
-    fn from(value: Paragraph) -> Self {
+impl<S> From<Paragraph<S>> for YamdNodes<S> {
+    fn from(value: Paragraph<S>) -> Self {
         YamdNodes::Paragraph(value)
     }
 }
 
-impl From<Heading> for YamdNodes {
-    fn from(value: Heading) -> Self {
+impl<S> From<Heading<S>> for YamdNodes<S> {
+    fn from(value: Heading<S>) -> Self {
         YamdNodes::Heading(value)
     }
 }
 
-impl From<Image> for YamdNodes {
-    fn from(value: Image) -> Self {
+impl<S> From<Image<S>> for YamdNodes<S> {
+    fn from(value: Image<S>) -> Self {
         YamdNodes::Image(value)
     }
 }
 
-impl From<Code> for YamdNodes {
-    fn from(value: Code) -> Self {
+impl<S> From<Code<S>> for YamdNodes<S> {
+    fn from(value: Code<S>) -> Self {
         YamdNodes::Code(value)
     }
 }
 
-impl From<List> for YamdNodes {
-    fn from(value: List) -> Self {
+impl<S> From<List<S>> for YamdNodes<S> {
+    fn from(value: List<S>) -> Self {
         YamdNodes::List(value)
     }
 }
 
-impl From<Images> for YamdNodes {
-    fn from(value: Images) -> Self {
+impl<S> From<Images<S>> for YamdNodes<S> {
+    fn from(value: Images<S>) -> Self {
         YamdNodes::Images(value)
     }
 }
 
-impl From<Highlight> for YamdNodes {
-    fn from(value: Highlight) -> Self {
+impl<S> From<Highlight<S>> for YamdNodes<S> {
+    fn from(value: Highlight<S>) -> Self {
         YamdNodes::Highlight(value)
     }
 }
 
-impl From<ThematicBreak> for YamdNodes {
+impl<S> From<ThematicBreak> for YamdNodes<S> {
     fn from(value: ThematicBreak) -> Self {
         YamdNodes::ThematicBreak(value)
     }
 }
 
-impl From<Embed> for YamdNodes {
-    fn from(value: Embed) -> Self {
+impl<S> From<Embed<S>> for YamdNodes<S> {
+    fn from(value: Embed<S>) -> Self {
         YamdNodes::Embed(value)
     }
 }
 
-impl From<Collapsible> for YamdNodes {
-    fn from(value: Collapsible) -> Self {
+impl<S> From<Collapsible<S>> for YamdNodes<S> {
+    fn from(value: Collapsible<S>) -> Self {
         YamdNodes::Collapsible(value)
     }
 }
 
+impl<S> From<Table<S>> for YamdNodes<S> {
+    fn from(value: Table<S>) -> Self {
+        YamdNodes::Table(value)
+    }
+}
+
+impl<S> From<FootnoteDefinition<S>> for YamdNodes<S> {
+    fn from(value: FootnoteDefinition<S>) -> Self {
+        YamdNodes::FootnoteDefinition(value)
+    }
+}
+
+impl<S> From<DisplayMath<S>> for YamdNodes<S> {
+    fn from(value: DisplayMath<S>) -> Self {
+        YamdNodes::DisplayMath(value)
+    }
+}
+
+impl<S> From<Custom<S>> for YamdNodes<S> {
+    fn from(value: Custom<S>) -> Self {
+        YamdNodes::Custom(value)
+    }
+}
+
 impl Display for YamdNodes {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -97,6 +130,10 @@ impl Display for YamdNodes {
             YamdNodes::ThematicBreak(node) => write!(f, "{}", node),
             YamdNodes::Embed(node) => write!(f, "{}", node),
             YamdNodes::Collapsible(node) => write!(f, "{}", node),
+            YamdNodes::Table(node) => write!(f, "{}", node),
+            YamdNodes::FootnoteDefinition(node) => write!(f, "{}", node),
+            YamdNodes::DisplayMath(node) => write!(f, "{}", node),
+            YamdNodes::Custom(node) => write!(f, "{}", node),
         }
     }
 }
@@ -110,6 +147,11 @@ impl Display for YamdNodes {
 /// followed by [Minus](type@crate::lexer::TokenKind::Minus) of length 3. Can contain any string that is
 /// parsable by the consumer.
 ///
+/// [Plus](type@crate::lexer::TokenKind::Plus) of length 3 can be used instead of
+/// [Minus](type@crate::lexer::TokenKind::Minus), as in TOML frontmatter. Opening and closing
+/// delimiters must be the same, the one that was used is kept in
+/// [metadata_delimiter](Yamd::metadata_delimiter).
+///
 /// For example toml:
 ///
 /// ```text
@@ -121,6 +163,8 @@ impl Display for YamdNodes {
 /// ---
 /// ```
 ///
+/// [Yamd::parse_metadata] reads metadata written in a subset of YAML.
+///
 /// [Body](Yamd::body) can contain one or more:
 ///
 /// - [Paragraph]
@@ -133,6 +177,10 @@ impl Display for YamdNodes {
 /// - [ThematicBreak]
 /// - [Embed]
 /// - [Collapsible]
+/// - [Table]
+/// - [FootnoteDefinition]
+/// - [DisplayMath]
+/// - [Custom], only with an extension
 ///
 /// Separated by [Terminator](type@crate::lexer::TokenKind::Terminator).
 ///
@@ -190,13 +238,27 @@ impl Display for YamdNodes {
 /// {% Or collapsible
 /// Which is also does not have 1:1 equivalent in HTML
 /// %}
+///
+/// | Or | table |
+/// | --- | --: |
+/// | with | cells |
+///
+/// Or a footnote[^1].
+///
+/// [^1]: Footnote definition.
+///
+/// Or a formula, inline $e^{i\pi}$ or display:
+///
+/// $$
+/// \sum_{n=1}^\infty \frac{1}{n^2} = \frac{\pi^2}{6}
+/// $$
 /// ~~~
 ///
 /// HTML equivalent:
 ///
 /// ```html
 /// <p>Yamd can contain a Paragraph. Or a</p>
-/// <h1>Heading</h1>
+/// <h1 id="heading">Heading</h1>
 /// <p>Or one image:</p>
 /// <img src="url" alt="alt"/>
 /// <p>Or code:</p>
@@ -246,20 +308,282 @@ impl Display for YamdNodes {
 ///         <p>Which is also does not have 1:1 equivalent in HTML</p>
 ///     </div>
 /// </div>
+/// <table>
+///     <thead><tr><th>Or</th><th style="text-align: right">table</th></tr></thead>
+///     <tbody><tr><td>with</td><td style="text-align: right">cells</td></tr></tbody>
+/// </table>
+/// <p>Or a footnote<sup><a href="#fn-1" id="fnref-1">1</a></sup>.</p>
+/// <div class="footnote" id="fn-1"><sup>1</sup>Footnote definition.</div>
+/// <p>Or a formula, inline <span class="math math-inline">e^{i\pi}</span> or display:</p>
+/// <div class="math math-display">\sum_{n=1}^\infty \frac{1}{n^2} = \frac{\pi^2}{6}</div>
 /// ```
 ///
 
 #[derive(Debug, PartialEq, Default, Eq, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-pub struct Yamd {
-    pub metadata: Option<String>,
-    pub body: Vec<YamdNodes>,
+pub struct Yamd<S = String> {
+    pub metadata: Option<S>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata_delimiter: MetadataDelimiter,
+    pub body: Vec<YamdNodes<S>>,
+}
+
+/// Delimiter of [Yamd::metadata].
+#[derive(Debug, PartialEq, Default, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MetadataDelimiter {
+    /// `---`
+    #[default]
+    Dashes,
+    /// `+++`
+    Pluses,
+}
+
+impl Display for MetadataDelimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MetadataDelimiter::Dashes => write!(f, "---"),
+            MetadataDelimiter::Pluses => write!(f, "+++"),
+        }
+    }
 }
 
 impl Yamd {
+    /// Metadata is delimited by [Dashes](MetadataDelimiter::Dashes), change
+    /// [metadata_delimiter](Yamd::metadata_delimiter) to use `+++`.
     pub fn new(metadata: Option<String>, body: Vec<YamdNodes>) -> Self {
         let metadata = metadata.map(|m| m.trim_matches('\n').to_owned());
-        Self { metadata, body }
+        Self {
+            metadata,
+            metadata_delimiter: MetadataDelimiter::default(),
+            body,
+        }
+    }
+
+    /// Resolves [FootnoteReference]s to [FootnoteDefinition]s, including the ones nested in
+    /// [Collapsible]s and in [nodes](Custom::nodes) of [Custom] blocks. When several definitions
+    /// have the same label, references resolve to the first one.
+    ///
+    /// ```
+    /// let yamd = yamd::deserialize("a[^1] b[^2] c[^1]\n\n[^1]: one\n\n[^3]: three");
+    /// let footnotes = yamd.footnotes();
+    /// assert_eq!(footnotes.resolved.len(), 1);
+    /// assert_eq!(footnotes.number("1"), Some(1));
+    /// assert_eq!(footnotes.missing, vec!["2"]);
+    /// assert_eq!(footnotes.orphaned[0].label, "3");
+    /// ```
+    pub fn footnotes(&self) -> Footnotes<'_> {
+        let mut definitions = vec![];
+        collect_footnote_definitions(&self.body, &mut definitions);
+        let mut references = FootnoteReferences::default();
+        references.visit_yamd(self);
+
+        let mut footnotes = Footnotes::default();
+        for label in references.0 {
+            match definitions.iter().find(|d| d.label == label) {
+                Some(definition) => {
+                    if !footnotes
+                        .resolved
+                        .iter()
+                        .any(|r| std::ptr::eq(*r, *definition))
+                    {
+                        footnotes.resolved.push(definition);
+                    }
+                }
+                None => {
+                    if !footnotes.missing.contains(&label) {
+                        footnotes.missing.push(label);
+                    }
+                }
+            }
+        }
+        footnotes.orphaned = definitions
+            .into_iter()
+            .filter(|d| !footnotes.resolved.iter().any(|r| std::ptr::eq(*r, *d)))
+            .collect();
+        footnotes
+    }
+
+    /// Parses [metadata](Yamd::metadata), see [metadata](crate::metadata) for the supported syntax.
+    /// Returns `None` if the document has no metadata.
+    ///
+    /// Metadata delimited by [Pluses](MetadataDelimiter::Pluses) is TOML, which is not supported,
+    /// it is an error at its start.
+    ///
+    /// ```
+    /// let yamd = yamd::deserialize("+++\ntitle = 'Yamd'\n+++");
+    /// let error = yamd.parse_metadata().unwrap_err();
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "metadata delimited by `+++` is TOML, which is not supported at line 1, column 1"
+    /// );
+    /// ```
+    pub fn parse_metadata(&self) -> Result<Option<MetadataValue>, MetadataError> {
+        self.yaml_metadata()?.map(metadata::parse).transpose()
+    }
+
+    /// Deserializes [metadata](Yamd::metadata) into `T`, see [from_str](metadata::from_str).
+    /// Returns `None` if the document has no metadata. Like in [parse_metadata](Yamd::parse_metadata),
+    /// metadata delimited by [Pluses](MetadataDelimiter::Pluses) is an error.
+    ///
+    /// ```
+    /// #[derive(serde::Deserialize)]
+    /// struct Post {
+    ///     title: String,
+    /// }
+    ///
+    /// let yamd = yamd::deserialize("---\ntitle: Yamd\n---\n\n# Hello");
+    /// let post: Post = yamd.deserialize_metadata().unwrap().unwrap();
+    /// assert_eq!(post.title, "Yamd");
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn deserialize_metadata<T: serde::de::DeserializeOwned>(
+        &self,
+    ) -> Result<Option<T>, MetadataError> {
+        self.yaml_metadata()?.map(metadata::from_str).transpose()
+    }
+
+    fn yaml_metadata(&self) -> Result<Option<&str>, MetadataError> {
+        match (&self.metadata, self.metadata_delimiter) {
+            (Some(_), MetadataDelimiter::Pluses) => Err(MetadataError::new(
+                "metadata delimited by `+++` is TOML, which is not supported",
+                0,
+                0,
+            )),
+            (metadata, MetadataDelimiter::Dashes) | (metadata @ None, _) => Ok(metadata.as_deref()),
+        }
+    }
+
+    /// Counts task [ListItem]s, including the ones in nested lists.
+    ///
+    /// ```
+    /// let yamd = yamd::deserialize("- [x] one\n - [ ] two\n - three\n- [X] four");
+    /// let tasks = yamd.tasks();
+    /// assert_eq!((tasks.completed, tasks.total), (2, 3));
+    /// ```
+    pub fn tasks(&self) -> Tasks {
+        let mut counter = TaskCounter::default();
+        counter.visit_yamd(self);
+        counter.0
+    }
+
+    /// Returns every [Heading] in document order, including the ones nested in [Collapsible]s and
+    /// in [nodes](Custom::nodes) of [Custom] blocks, with an id that is unique in the document. Id is the [slug](Heading::slug) of the heading,
+    /// with `-1`, `-2`, ... suffix if the slug is already taken.
+    ///
+    /// ```
+    /// let yamd = yamd::deserialize("# Intro\n\n## Intro\n\n## Intro-1\n\n{% more\n## Intro\n%}");
+    /// let ids: Vec<String> = yamd.heading_ids().into_iter().map(|(_, id)| id).collect();
+    /// assert_eq!(ids, vec!["intro", "intro-1", "intro-1-1", "intro-2"]);
+    /// ```
+    pub fn heading_ids(&self) -> Vec<(&Heading, String)> {
+        let mut headings = vec![];
+        collect_headings(&self.body, &mut headings);
+        let mut taken = HashSet::new();
+        headings
+            .into_iter()
+            .map(|heading| {
+                let slug = heading.slug();
+                let mut id = slug.clone();
+                let mut suffix = 0;
+                while !taken.insert(id.clone()) {
+                    suffix += 1;
+                    id = format!("{slug}-{suffix}");
+                }
+                (heading, id)
+            })
+            .collect()
+    }
+
+    /// Builds an outline of headings with a level in `levels`, of the same headings as
+    /// [heading_ids](Yamd::heading_ids). A heading is nested under the closest previous heading
+    /// with a lower level, headings without one are top level entries.
+    ///
+    /// ```
+    /// let yamd = yamd::deserialize("# Title\n\n## Intro\n\n### Details\n\n{% more\n## Usage\n%}");
+    /// let toc = yamd.toc(2..=3);
+    /// assert_eq!(toc.entries.len(), 2);
+    /// assert_eq!(toc.entries[0].id, "intro");
+    /// assert_eq!(toc.entries[0].children[0].id, "details");
+    /// assert_eq!(toc.entries[1].id, "usage");
+    /// ```
+    pub fn toc(&self, levels: RangeInclusive<u8>) -> Toc<'_> {
+        let mut headings = self
+            .heading_ids()
+            .into_iter()
+            .filter(|(heading, _)| levels.contains(&heading.level))
+            .peekable();
+        Toc {
+            entries: toc_entries(&mut headings, 0),
+        }
+    }
+}
+
+/// Builds [TocEntry]s of headings with a level greater than `parent` until a heading with the
+/// same or a lower level.
+fn toc_entries<'a>(
+    headings: &mut Peekable<impl Iterator<Item = (&'a Heading, String)>>,
+    parent: u8,
+) -> Vec<TocEntry<'a>> {
+    let mut entries = vec![];
+    while let Some((heading, id)) = headings.next_if(|(heading, _)| heading.level > parent) {
+        let children = toc_entries(headings, heading.level);
+        entries.push(TocEntry {
+            heading,
+            id,
+            children,
+        });
+    }
+    entries
+}
+
+fn collect_headings<'a>(nodes: &'a [YamdNodes], headings: &mut Vec<&'a Heading>) {
+    for node in nodes {
+        match node {
+            YamdNodes::Heading(heading) => headings.push(heading),
+            YamdNodes::Collapsible(collapsible) => collect_headings(&collapsible.body, headings),
+            YamdNodes::Custom(custom) => collect_headings(&custom.nodes, headings),
+            _ => {}
+        }
+    }
+}
+
+fn collect_footnote_definitions<'a>(
+    nodes: &'a [YamdNodes],
+    definitions: &mut Vec<&'a FootnoteDefinition>,
+) {
+    for node in nodes {
+        match node {
+            YamdNodes::FootnoteDefinition(definition) => definitions.push(definition),
+            YamdNodes::Collapsible(collapsible) => {
+                collect_footnote_definitions(&collapsible.body, definitions)
+            }
+            YamdNodes::Custom(custom) => collect_footnote_definitions(&custom.nodes, definitions),
+            _ => {}
+        }
+    }
+}
+
+/// Labels of every [FootnoteReference] in document order.
+#[derive(Default)]
+struct FootnoteReferences(Vec<String>);
+
+impl Visit for FootnoteReferences {
+    fn visit_footnote_reference(&mut self, reference: &FootnoteReference) {
+        self.0.push(reference.label.clone());
+    }
+}
+
+#[derive(Default)]
+struct TaskCounter(Tasks);
+
+impl Visit for TaskCounter {
+    fn visit_list_item(&mut self, list_item: &ListItem) {
+        if let Some(checked) = list_item.checked {
+            self.0.total += 1;
+            self.0.completed += usize::from(checked);
+        }
+        walk_list_item(self, list_item);
     }
 }
 
@@ -277,7 +601,8 @@ impl Display for Yamd {
         match &self.metadata {
             Some(metadata) => {
                 let sep = if metadata.ends_with('\n') { "" } else { "\n" };
-                write!(f, "---\n{}{}---\n\n{}", metadata, sep, body)
+                let delimiter = self.metadata_delimiter;
+                write!(f, "{delimiter}\n{metadata}{sep}{delimiter}\n\n{body}")
             }
             None => write!(f, "{}", body),
         }
@@ -287,8 +612,9 @@ impl Display for Yamd {
 #[cfg(test)]
 mod tests {
     use crate::nodes::{
-        Code, Collapsible, Embed, Heading, Highlight, Image, Images, List, ListItem, ListTypes,
-        Paragraph, ThematicBreak, Yamd, YamdNodes,
+        Code, Collapsible, Custom, Embed, FootnoteDefinition, FootnoteReference, Heading,
+        Highlight, Image, Images, List, ListItem, ListTypes, MetadataDelimiter, Paragraph, Tasks,
+        ThematicBreak, TocEntry, Yamd, YamdNodes,
     };
 
     #[test]
@@ -357,6 +683,52 @@ mod tests {
         assert_eq!(yamd.to_string(), "---\n\n---\n\n");
     }
 
+    #[test]
+    fn test_yamd_with_pluses() {
+        let yamd = Yamd {
+            metadata_delimiter: MetadataDelimiter::Pluses,
+            ..Yamd::new(
+                Some("title = 'test'".to_string()),
+                vec![YamdNodes::Paragraph(Paragraph::new(vec![
+                    "Paragraph".to_string().into(),
+                ]))],
+            )
+        };
+
+        assert_eq!(yamd.to_string(), "+++\ntitle = 'test'\n+++\n\nParagraph");
+    }
+
+    #[test]
+    fn metadata_with_pluses_is_an_error() {
+        #[cfg(feature = "serde")]
+        #[derive(serde::Deserialize, Debug)]
+        struct Meta {
+            title: String,
+        }
+
+        let yamd = crate::deserialize("+++\ntitle = 'test'\nsub = 1\n+++");
+        let error = yamd.parse_metadata().unwrap_err();
+        assert_eq!(
+            error.message,
+            "metadata delimited by `+++` is TOML, which is not supported"
+        );
+        assert_eq!((error.row, error.column), (0, 0));
+        #[cfg(feature = "serde")]
+        assert_eq!(
+            yamd.deserialize_metadata::<Meta>().unwrap_err().message,
+            error.message
+        );
+
+        let yamd = crate::deserialize("---\ntitle: test\n---");
+        assert!(yamd.parse_metadata().unwrap().is_some());
+        #[cfg(feature = "serde")]
+        assert_eq!(
+            yamd.deserialize_metadata::<Meta>().unwrap().unwrap().title,
+            "test"
+        );
+        assert_eq!(crate::deserialize("a").parse_metadata(), Ok(None));
+    }
+
     #[test]
     fn paragraph_node() {
         let node = YamdNodes::Paragraph(Paragraph::new(vec!["Paragraph".to_string().into()]));
@@ -443,4 +815,134 @@ mod tests {
 
         assert_eq!(node.to_string(), "{% Or collapsible\nbody\n%}");
     }
+
+    #[test]
+    fn footnotes() {
+        let yamd = crate::deserialize(
+            "a[^2] [^1]\n\n[^1]: one[^3]\n\n{% t\n[^2]: two\n\n[^1]: again\n%}\n\n[^4]: four",
+        );
+        let footnotes = yamd.footnotes();
+        let labels =
+            |d: &[&FootnoteDefinition]| d.iter().map(|d| d.label.clone()).collect::<Vec<_>>();
+        assert_eq!(labels(&footnotes.resolved), vec!["2", "1"]);
+        assert_eq!(footnotes.number("1"), Some(2));
+        assert_eq!(footnotes.number("4"), None);
+        assert_eq!(footnotes.missing, vec!["3"]);
+        assert_eq!(labels(&footnotes.orphaned), vec!["1", "4"]);
+        assert_eq!(
+            footnotes.orphaned[0].body,
+            vec![String::from("again").into()]
+        );
+    }
+
+    #[test]
+    fn tasks() {
+        let yamd = crate::deserialize(
+            "- [x] a\n - [ ] b\n  + [x] c\n- d\n\n{% t\n- [ ] e\n%}\n\n!!\n- [x] f\n!!",
+        );
+        assert_eq!(
+            yamd.tasks(),
+            Tasks {
+                completed: 2,
+                total: 4
+            }
+        );
+        assert_eq!(crate::deserialize("- a").tasks(), Tasks::default());
+    }
+
+    #[test]
+    fn heading_ids() {
+        let yamd = crate::deserialize("# A\n\n## a\n\n{% t\n## [A](#a)\n%}\n\n## \n\n## $x$");
+        let ids: Vec<(u8, String)> = yamd
+            .heading_ids()
+            .into_iter()
+            .map(|(heading, id)| (heading.level, id))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                (1, "a".to_string()),
+                (2, "a-1".to_string()),
+                (2, "a-2".to_string()),
+                (2, "".to_string()),
+                (2, "x".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn custom_nodes_are_searched() {
+        let yamd = Yamd::new(
+            None,
+            vec![
+                Paragraph::new(vec![FootnoteReference::new("1").into()]).into(),
+                Custom::new("aside", "", "", "")
+                    .with_nodes(vec![
+                        Heading::new(1, vec!["A".to_string().into()]).into(),
+                        FootnoteDefinition::new("1", vec![]).into(),
+                    ])
+                    .into(),
+            ],
+        );
+        let ids: Vec<String> = yamd.heading_ids().into_iter().map(|(_, id)| id).collect();
+        assert_eq!(ids, vec!["a"]);
+        assert_eq!(yamd.toc(1..=6).entries[0].id, "a");
+        let footnotes = yamd.footnotes();
+        assert_eq!(footnotes.number("1"), Some(1));
+        assert!(footnotes.missing.is_empty());
+    }
+
+    #[test]
+    fn toc() {
+        let yamd = crate::deserialize(
+            "### a\n\n# b\n\n### c\n\n## d\n\n{% t\n#### e\n\n## [b](#)\n%}\n\n###### f",
+        );
+        let toc = yamd.toc(2..=4);
+        let outline = |entries: &[TocEntry]| {
+            entries
+                .iter()
+                .map(|e| (e.id.clone(), e.children.len()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            outline(&toc.entries),
+            vec![
+                ("a".to_string(), 0),
+                ("c".to_string(), 0),
+                ("d".to_string(), 1),
+                ("b-1".to_string(), 0)
+            ]
+        );
+        assert_eq!(
+            outline(&toc.entries[2].children),
+            vec![("e".to_string(), 0)]
+        );
+
+        let list = toc.to_list().unwrap();
+        assert_eq!(
+            list.to_string(),
+            "- [a](#a)\n- [c](#c)\n- [d](#d)\n - [e](#e)\n- [b](#b-1)"
+        );
+        assert_eq!(
+            crate::deserialize(&list.to_string()),
+            Yamd::new(None, vec![list.into()])
+        );
+        assert_eq!(yamd.toc(5..=5).to_list(), None);
+    }
+
+    #[test]
+    fn paragraph_that_looks_like_footnote_definition() {
+        let yamd = Yamd::new(
+            None,
+            vec![
+                Paragraph::new(vec![
+                    FootnoteReference::new("a").into(),
+                    String::from(": b").into(),
+                ])
+                .into(),
+            ],
+        );
+        assert_eq!(yamd.to_string(), "[^a]\\: b");
+        assert_eq!(crate::deserialize(&yamd.to_string()), yamd);
+    }
 }
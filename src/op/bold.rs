@@ -34,7 +34,8 @@ pub fn bold(p: &mut Parser) -> bool {
         } else {
             let before_pos = p.pos;
             let before_snap = p.ops.len();
-            let matched = strikethrough(p) || italic(p);
+            let matched =
+                (p.options.strikethrough && strikethrough(p)) || (p.options.italic && italic(p));
             if matched {
                 if let Some(s) = text_start.take() {
                     let content = p.span(s..before_pos);
@@ -51,6 +52,7 @@ pub fn bold(p: &mut Parser) -> bool {
         return true;
     }
 
+    p.unclosed(Node::Bold, start..start + 1, "**");
     p.pos = start;
     p.ops.truncate(snap);
     false
@@ -37,6 +37,7 @@ pub fn code(p: &mut Parser) -> bool {
     });
 
     let Some((body_range, close_range)) = scan else {
+        p.unclosed(Node::Code, start_pos..start_pos + 1, "```");
         p.pos = start_pos;
         p.ops.truncate(snap);
         return false;
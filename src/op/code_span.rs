@@ -13,6 +13,7 @@ pub fn code_span(p: &mut Parser) -> bool {
         return false;
     };
     let Some((body_range, end_range)) = p.eat_until(is_backtick) else {
+        p.unclosed(Node::CodeSpan, start_range, "`");
         p.pos = start;
         return false;
     };
@@ -52,6 +52,7 @@ pub fn collapsible(p: &mut Parser) -> bool {
     let end_range = eat_seq!(p, is_collapsible_end, is_eol).or_else(|| p.eat(is_collapsible_end));
 
     let Some(end_range) = end_range else {
+        p.unclosed(Node::Collapsible, start..start + 1, "%}");
         p.pos = start;
         p.ops.truncate(snap);
         p.flip_to_literal(start);
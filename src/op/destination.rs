@@ -22,19 +22,17 @@ pub fn destination(p: &mut Parser) -> bool {
         match token.kind {
             TokenKind::LeftParenthesis => {
                 paren_count += 1;
-                p.pos = i + 1;
+                p.next();
             }
             TokenKind::RightParenthesis => {
                 paren_count -= 1;
                 end_pos = i;
-                p.pos = i + 1;
+                p.next();
                 if paren_count == 0 {
                     break;
                 }
             }
-            _ => {
-                p.pos = i + 1;
-            }
+            _ => p.next(),
         }
     }
 
@@ -0,0 +1,166 @@
+use std::ops::Range;
+
+use crate::op::{LineIndex, Node, Op, OpKind, Span, op_ranges, parse_iter};
+
+/// Construct that the parser started but did not close, so its text was parsed as literal text
+/// instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnclosedConstruct {
+    /// Node that was not parsed.
+    pub node: Node,
+    /// Span of the opening delimiter.
+    pub opening: Span,
+    /// Delimiter that would close the node.
+    pub expected: &'static str,
+}
+
+/// Parses source text like [`parse`](crate::op::parse), and reports each construct that fell back
+/// to literal text because its closing delimiter is missing. Diagnostics are in source order.
+///
+/// [Inline math](Node::InlineMath) is not reported, a single `$` in text is most likely a price.
+///
+/// ```
+/// use yamd::op::{Node, parse_with_diagnostics};
+///
+/// let source = "!! Note\nno closing bangs\n\n**bold";
+/// let (ops, diagnostics) = parse_with_diagnostics(source);
+/// assert_eq!(ops, yamd::parse(source));
+/// assert_eq!(diagnostics.len(), 2);
+/// assert_eq!((diagnostics[0].node, diagnostics[0].expected), (Node::Highlight, "!!"));
+/// assert_eq!((diagnostics[1].node, diagnostics[1].expected), (Node::Bold, "**"));
+/// assert_eq!(diagnostics[1].opening.range, 26..28);
+/// assert_eq!(diagnostics[1].opening.start.row, 3);
+/// ```
+pub fn parse_with_diagnostics(input: &str) -> (Vec<Op>, Vec<UnclosedConstruct>) {
+    let mut iter = parse_iter(input);
+    iter.parser.diagnostics = Some(Vec::new());
+    let ops: Vec<Op> = iter.by_ref().collect();
+    let mut diagnostics = iter.parser.diagnostics.take().unwrap_or_default();
+
+    // Text can be parsed several times, e.g. as a table first and as a paragraph after that, so
+    // only attempts whose opening delimiter ended up as literal text are reported.
+    let text = text_ranges(&ops, input);
+    diagnostics.retain(|diagnostic| {
+        let start = diagnostic.opening.range.start;
+        let index = text.partition_point(|range| range.start <= start);
+        index > 0 && text[index - 1].end > start
+    });
+    diagnostics.sort_by_key(|diagnostic| (diagnostic.opening.range.start, diagnostic.expected));
+    diagnostics.dedup();
+
+    let lines = LineIndex::new(input);
+    for diagnostic in &mut diagnostics {
+        diagnostic.opening = lines.span(diagnostic.opening.range.clone());
+    }
+    (ops, diagnostics)
+}
+
+/// Returns byte ranges of the text in paragraphs, table cells included, headings and bold, in
+/// source order.
+fn text_ranges(ops: &[Op], source: &str) -> Vec<Range<usize>> {
+    let mut stack = Vec::new();
+    ops.iter()
+        .zip(op_ranges(ops, source))
+        .filter_map(|(op, range)| match &op.kind {
+            OpKind::Start(node) => {
+                stack.push(*node);
+                None
+            }
+            OpKind::End(_) => {
+                stack.pop();
+                None
+            }
+            OpKind::Value => matches!(
+                stack.last(),
+                Some(Node::Paragraph | Node::Heading | Node::Bold)
+            )
+            .then_some(range),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn unclosed(input: &str) -> Vec<(Node, Range<usize>, &'static str)> {
+        let (ops, diagnostics) = parse_with_diagnostics(input);
+        assert_eq!(ops, crate::op::parse(input));
+        diagnostics
+            .into_iter()
+            .map(|d| (d.node, d.opening.range, d.expected))
+            .collect()
+    }
+
+    #[test]
+    fn inline() {
+        assert_eq!(
+            unclosed("a **b _c ~~d `e $f *g"),
+            vec![
+                (Node::Bold, 2..4, "**"),
+                (Node::Italic, 6..7, "_"),
+                (Node::Strikethrough, 9..11, "~~"),
+                (Node::CodeSpan, 13..14, "`"),
+                (Node::Emphasis, 19..20, "*"),
+            ]
+        );
+        assert_eq!(unclosed("**a _b**"), vec![(Node::Italic, 4..5, "_")]);
+    }
+
+    #[test]
+    fn blocks() {
+        assert_eq!(unclosed("```\na"), vec![(Node::Code, 0..3, "```")]);
+        assert_eq!(unclosed("$$\na"), vec![(Node::DisplayMath, 0..2, "$$")]);
+        assert_eq!(unclosed("{{a|b"), vec![(Node::Embed, 0..2, "}}")]);
+        assert_eq!(unclosed("!! a\nb"), vec![(Node::Highlight, 0..2, "!!")]);
+        assert_eq!(unclosed("{% a\nb"), vec![(Node::Collapsible, 0..2, "%}")]);
+    }
+
+    #[test]
+    fn table_cells() {
+        assert_eq!(
+            unclosed("| a **b | c |\n|---|---|\n| ~~d | `e |"),
+            vec![
+                (Node::Bold, 4..6, "**"),
+                (Node::Strikethrough, 26..28, "~~"),
+                (Node::CodeSpan, 32..33, "`"),
+            ]
+        );
+    }
+
+    #[test]
+    fn dollars_in_prose_are_not_reported() {
+        for input in ["cost $5 and $10", "# $a", "- a $b", "$a and $ b"] {
+            assert_eq!(unclosed(input), vec![], "{input}");
+        }
+    }
+
+    #[test]
+    fn closed_constructs_are_not_reported() {
+        assert_eq!(
+            unclosed("**a** _b_ `c`\n\n```\nd\n```\n\n!!\ne\n!!\n\n{% f\ng\n%}"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn attempts_that_were_parsed_again_are_reported_once() {
+        // bold is tried in the highlight, and again in the paragraph after the highlight fails
+        assert_eq!(
+            unclosed("!!\n**a"),
+            vec![(Node::Highlight, 0..2, "!!"), (Node::Bold, 3..5, "**")]
+        );
+        // the cell ends before the closing stars, but the text is a paragraph where bold closes
+        assert_eq!(unclosed("| **a | b**"), vec![]);
+        assert_eq!(unclosed("```\na\n\n```"), vec![]);
+    }
+
+    #[test]
+    fn opening_position() {
+        let (_, diagnostics) = parse_with_diagnostics("a\n\n🤔 _b");
+        assert_eq!(diagnostics[0].opening.start.row, 2);
+        assert_eq!(diagnostics[0].opening.start.column, 2);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{
+    lexer::{Token, TokenKind},
+    op::{
+        Content, Node, Op, Parser,
+        parser::{eat_seq, eol},
+    },
+};
+
+fn is_dollar2(t: &Token) -> bool {
+    t.kind == TokenKind::Dollar && t.position.column == 0 && t.range.len() == 2
+}
+
+/// Body is taken from the source as is, escapes included.
+pub fn display_math(p: &mut Parser) -> bool {
+    let start = p.pos;
+    let Some(start_range) = eat_seq!(p, is_dollar2, eol) else {
+        return false;
+    };
+
+    let Some((_, close_range)) = p.with_no_stops(|p| p.eat_until(is_dollar2)) else {
+        p.unclosed(Node::DisplayMath, start..start + 1, "$$");
+        p.pos = start;
+        return false;
+    };
+
+    let end_range = if let Some(eol_range) = p.eat(eol) {
+        close_range.start..eol_range.end
+    } else if p.at_block_boundary() {
+        close_range.clone()
+    } else {
+        p.pos = start;
+        return false;
+    };
+
+    let body_start = p.get(start_range.end - 1).map_or(0, |t| t.range.end);
+    let body_end = p.get(close_range.start).map_or(0, |t| t.range.start);
+    let start_content = p.span(start_range);
+    let end_content = p.span(end_range);
+    p.ops.push(Op::new_start(Node::DisplayMath, start_content));
+    p.ops
+        .push(Op::new_value(Content::Span(body_start..body_end)));
+    p.ops.push(Op::new_end(Node::DisplayMath, end_content));
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::op::{Content, Node, Op, Parser, display_math::display_math};
+
+    #[test]
+    fn happy_path() {
+        let mut p: Parser = "$$\n\\alpha\n\n$b$\n$$\n".into();
+        assert!(display_math(&mut p));
+        assert_eq!(
+            p.ops,
+            vec![
+                Op::new_start(Node::DisplayMath, Content::Span(0..3)),
+                Op::new_value(Content::Span(3..15)),
+                Op::new_end(Node::DisplayMath, Content::Span(15..18)),
+            ]
+        );
+        assert!(p.at_eof());
+    }
+
+    #[test]
+    fn empty_body() {
+        let mut p: Parser = "$$\n$$".into();
+        assert!(display_math(&mut p));
+        assert_eq!(p.ops[1], Op::new_value(Content::Span(3..3)));
+    }
+
+    #[test]
+    fn not_display_math() {
+        for input in ["$$ a\n$$", "$$\na", "$$\na\n$$b", "$$\na $$", "$$$\na\n$$$"] {
+            let mut p: Parser = input.into();
+            assert!(!display_math(&mut p), "{input}");
+            assert!(p.ops.is_empty());
+            assert_eq!(p.pos, 0);
+        }
+    }
+}
@@ -1,9 +1,11 @@
 use crate::{
     lexer::{Token, TokenKind},
     op::{
-        Content, Node, Op, Parser, code::code, collapsible::collapsible, embed::embed,
-        heading::heading, highlight::highlight, images::images, list::list, paragraph::paragraph,
-        parser::StopCondition, thematic_break::thematic_break,
+        Content, Node, Op, Parser, code::code, collapsible::collapsible,
+        display_math::display_math, embed::embed, extension::custom,
+        footnote_definition::footnote_definition, heading::heading, highlight::highlight,
+        images::images, list::list, metadata::metadata, paragraph::paragraph,
+        parser::StopCondition, table::table, thematic_break::thematic_break,
     },
 };
 
@@ -18,7 +20,10 @@ use crate::{
 fn block_fixture(node: &Node) -> Option<&'static str> {
     match node {
         Node::Code => Some("```\ncode\n```"),
+        // parsed only with an extension, see `extension` tests
+        Node::Custom => None,
         Node::Collapsible => Some("{% Title\ntext\n%}"),
+        Node::DisplayMath => Some("$$\nmath\n$$"),
         Node::Embed => Some("{{a|b}}"),
         Node::Highlight => Some("!! Title\ntext\n!!"),
         Node::Anchor
@@ -27,10 +32,13 @@ fn block_fixture(node: &Node) -> Option<&'static str> {
         | Node::Destination
         | Node::Document
         | Node::Emphasis
+        | Node::FootnoteDefinition
+        | Node::FootnoteReference
         | Node::Heading
         | Node::Icon
         | Node::Image
         | Node::Images
+        | Node::InlineMath
         | Node::Italic
         | Node::ListItem
         | Node::Metadata
@@ -38,6 +46,9 @@ fn block_fixture(node: &Node) -> Option<&'static str> {
         | Node::OrderedList
         | Node::Paragraph
         | Node::Strikethrough
+        | Node::Table
+        | Node::TableCell
+        | Node::TableRow
         | Node::ThematicBreak
         | Node::Title
         | Node::UnorderedList => None,
@@ -51,28 +62,107 @@ fn is_terminator(t: &Token) -> bool {
 pub fn document(p: &mut Parser) {
     p.ops
         .push(Op::new_start(Node::Document, Content::Span(0..0)));
-
     while !p.at_eof() {
-        let before = p.pos;
-
-        if let Some(range) = p.eat(is_terminator) {
-            let content = p.span(range);
-            p.ops.push(Op::new_value(content));
-        } else if code(p) || collapsible(p) || embed(p) || highlight(p) {
-        } else {
-            p.with_eof(StopCondition::Terminator, |p| {
-                if !list(p, 0) && !images(p) && !thematic_break(p) && !heading(p) {
-                    paragraph(p);
-                }
-            });
+        block(p);
+    }
+    p.ops.push(Op::new_end(Node::Document, Content::Span(0..0)));
+}
+
+/// Parses one top level block, or the [Terminator](TokenKind::Terminator) between blocks.
+pub fn block(p: &mut Parser) {
+    let before = p.pos;
+    let o = p.options;
+
+    if let Some(range) = p.eat(is_terminator) {
+        let content = p.span(range);
+        p.ops.push(Op::new_value(content));
+    } else if o.extensions.iter().any(|extension| custom(p, *extension))
+        || (o.code && code(p))
+        || (o.display_math && display_math(p))
+        || (o.collapsible && collapsible(p))
+        || (o.embed && embed(p))
+        || (o.highlight && highlight(p))
+    {
+    } else {
+        p.with_eof(StopCondition::Terminator, |p| {
+            let matched = (o.list && list(p, 0))
+                || (o.image && images(p))
+                || (o.thematic_break && thematic_break(p))
+                || (o.heading && heading(p))
+                || (o.table && table(p))
+                || (o.footnote_definition && footnote_definition(p));
+            if !matched {
+                paragraph(p);
+            }
+        });
+    }
+
+    debug_assert!(
+        p.pos > before,
+        "document loop made no progress at token {before}"
+    );
+}
+
+/// Top level blocks that end where a line ends. The rest of the input is lexed and parsed the
+/// same way when it is parsed on its own, so a document can be parsed unit by unit.
+pub(crate) struct Unit {
+    /// Metadata ops, only in the first unit of a document.
+    pub(crate) metadata: Vec<Op>,
+    pub(crate) body: Vec<Op>,
+    /// End of the unit in the input.
+    pub(crate) end: usize,
+    /// End of the text the unit was parsed from, see [`Parser::take_read`].
+    pub(crate) read: Option<usize>,
+}
+
+/// Iterator over [`Unit`]s of the input.
+pub(crate) struct Units<'a> {
+    parser: Parser<'a>,
+    /// `true` if the input is the beginning of a document, which can start with metadata.
+    at_start: bool,
+}
+
+impl<'a> Units<'a> {
+    pub(crate) fn new(input: &'a str, at_start: bool) -> Self {
+        Self {
+            parser: Parser::from(input),
+            at_start,
         }
+    }
+}
 
-        debug_assert!(
-            p.pos > before,
-            "document loop made no progress at token {before}"
-        );
+impl Iterator for Units<'_> {
+    type Item = Unit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let p = &mut self.parser;
+        if std::mem::take(&mut self.at_start) && p.options.metadata {
+            metadata(p);
+        }
+        let metadata = std::mem::take(&mut p.ops);
+        let mut end = None;
+        while !p.at_eof() {
+            block(p);
+            end = p
+                .get(p.pos - 1)
+                .filter(|t| matches!(t.kind, TokenKind::Eol | TokenKind::Terminator))
+                .map(|t| t.range.end);
+            if end.is_some() {
+                break;
+            }
+        }
+        if metadata.is_empty() && p.ops.is_empty() {
+            return None;
+        }
+        let read = p.take_read();
+        p.release();
+        Some(Unit {
+            metadata,
+            body: std::mem::take(&mut p.ops),
+            end: end.unwrap_or(p.source.len()),
+            read,
+        })
     }
-    p.ops.push(Op::new_end(Node::Document, Content::Span(0..0)));
 }
 
 #[cfg(test)]
@@ -1,6 +1,8 @@
+use std::ops::Range;
+
 use crate::{
     lexer::{Token, TokenKind},
-    op::{Node, Op, Parser},
+    op::{Content, Node, Op, Parser},
 };
 
 fn is_left_curly2(t: &Token) -> bool {
@@ -31,6 +33,7 @@ pub fn embed(p: &mut Parser) -> bool {
     };
 
     let Some((rhs_range, close_range)) = p.eat_until(is_right_curly2) else {
+        p.unclosed(Node::Embed, start_range, "}}");
         p.pos = start;
         return false;
     };
@@ -47,7 +50,7 @@ pub fn embed(p: &mut Parser) -> bool {
     let start_content = p.span(start_range);
     let lhs_content = p.span(lhs_range);
     let sep_content = p.span(sep_range);
-    let rhs_content = p.span(rhs_range);
+    let rhs_content = args(p, rhs_range);
     let end_content = p.span(end_range);
     p.ops.push(Op::new_start(Node::Embed, start_content));
     p.ops.push(Op::new_value(lhs_content));
@@ -57,6 +60,40 @@ pub fn embed(p: &mut Parser) -> bool {
     true
 }
 
+/// Unescaped args, except that `\` before `|` and `=` is kept, so that
+/// [Embed::arguments](crate::nodes::Embed::arguments) can tell them from separators. A run of `\`
+/// before `|` or `=` is doubled, and one more is added if the `|` or `=` is escaped.
+fn args(p: &Parser, range: Range<usize>) -> Content {
+    let tokens = range.clone().filter_map(|i| p.get(i)).collect::<Vec<_>>();
+    if !tokens.iter().any(|t| t.escaped) {
+        return p.span(range);
+    }
+    let mut args = String::new();
+    let mut backslashes = 0;
+    for t in tokens {
+        for (i, c) in p.source[t.range.clone()].char_indices() {
+            match c {
+                '\\' => {
+                    backslashes += 1;
+                    continue;
+                }
+                '|' | '=' => {
+                    backslashes *= 2;
+                    if t.escaped && i == 0 {
+                        backslashes += 1;
+                    }
+                }
+                _ => {}
+            }
+            args.extend(std::iter::repeat_n('\\', backslashes));
+            args.push(c);
+            backslashes = 0;
+        }
+    }
+    args.extend(std::iter::repeat_n('\\', backslashes));
+    Content::Materialized(args)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -13,6 +13,7 @@ pub fn emphasis(p: &mut Parser) -> bool {
         return false;
     };
     let Some((body_range, end_range)) = p.eat_until(is_star) else {
+        p.unclosed(Node::Emphasis, start_range, "*");
         p.pos = start;
         return false;
     };
@@ -0,0 +1,593 @@
+use std::ops::Range;
+
+use crate::{
+    lexer::TokenKind,
+    op::{Content, Node, Op, OpKind, ParseOptions, Parser, document::document, parser::eol},
+};
+
+/// Parser of a custom block, registered in [`ParseOptions::extensions`](crate::op::ParseOptions::extensions).
+///
+/// The block starts at the beginning of a line where [`start`](BlockExtension::start) finds its
+/// opening delimiter, and ends where [`end`](BlockExtension::end) finds its closing delimiter,
+/// which must be followed by a line end or the end of the document. [`ops`](BlockExtension::ops)
+/// returns the ops of the block. By default they are [`Start`](crate::op::OpKind::Start)`(`[`Node::Custom`]`)`
+/// with the opening delimiter, a [`Value`](crate::op::OpKind::Value) with the
+/// [`name`](BlockExtension::name), a [`Value`](crate::op::OpKind::Value) with the body, and
+/// [`End`](crate::op::OpKind::End)`(`[`Node::Custom`]`)` with the closing delimiter, which
+/// [`to_yamd`](crate::op::to_yamd) turns into [`Custom`](crate::nodes::Custom).
+///
+/// Extensions are tried in order before the built in blocks, so they can take over their syntax.
+/// A block that is not closed, or whose closing delimiter is after the end of the enclosing block,
+/// like a [`Collapsible`](crate::nodes::Collapsible), is parsed as the built in blocks would parse
+/// it.
+///
+/// ```
+/// use std::ops::Range;
+/// use yamd::{
+///     nodes::{Custom, Paragraph, Yamd},
+///     op::{BlockExtension, ParseOptions},
+/// };
+///
+/// struct Note;
+///
+/// impl BlockExtension for Note {
+///     fn name(&self) -> &str {
+///         "note"
+///     }
+///
+///     fn start(&self, input: &str) -> Option<usize> {
+///         input.starts_with(":::").then_some(3)
+///     }
+///
+///     fn end(&self, input: &str) -> Option<Range<usize>> {
+///         let start = input.find("\n:::")? + 1;
+///         Some(start..start + 3)
+///     }
+/// }
+///
+/// let options = ParseOptions { extensions: &[&Note], ..ParseOptions::default() };
+/// let input = "::: warning\ntext\n:::\n\n:::";
+/// let yamd = yamd::deserialize_with(input, options);
+/// assert_eq!(
+///     yamd,
+///     Yamd::new(
+///         None,
+///         vec![
+///             Custom::new("note", ":::", " warning\ntext\n", ":::").into(),
+///             Paragraph::new(vec![String::from(":::").into()]).into(),
+///         ]
+///     )
+/// );
+/// assert_eq!(yamd.to_string(), input);
+/// ```
+pub trait BlockExtension {
+    /// Name of the blocks, stored in [`Custom::name`](crate::nodes::Custom::name).
+    fn name(&self) -> &str;
+
+    /// Returns the length of the opening delimiter if a block starts at the beginning of `input`,
+    /// the rest of the document from the start of a line.
+    fn start(&self, input: &str) -> Option<usize>;
+
+    /// Returns the byte range of the closing delimiter in `input`, the rest of the document after
+    /// the opening delimiter, or `None` if the block is not closed.
+    fn end(&self, input: &str) -> Option<Range<usize>>;
+
+    /// Returns the ops of the block, [`Custom`](crate::nodes::Custom) with the body as text by
+    /// default, see [`ExtensionBlock::custom`].
+    ///
+    /// Ops can be any blocks, in the same shape as the built in parsers produce them, and
+    /// [`ExtensionBlock::parse`] parses a part of the block into nested blocks. Start and End ops
+    /// must be balanced, the outermost ones must be blocks, and spans must be inside of the block,
+    /// otherwise the text is parsed as if the extension did not find a block, the same as with no
+    /// ops.
+    ///
+    /// ```
+    /// use std::ops::Range;
+    /// use yamd::{
+    ///     nodes::{Custom, Heading, Yamd},
+    ///     op::{BlockExtension, Content, ExtensionBlock, Node, Op, ParseOptions},
+    /// };
+    ///
+    /// /// `:::` fenced block with YAMD inside.
+    /// struct Aside;
+    ///
+    /// impl BlockExtension for Aside {
+    ///     fn name(&self) -> &str {
+    ///         "aside"
+    ///     }
+    ///
+    ///     fn start(&self, input: &str) -> Option<usize> {
+    ///         input.starts_with(":::\n").then_some(4)
+    ///     }
+    ///
+    ///     fn end(&self, input: &str) -> Option<Range<usize>> {
+    ///         let start = input.find("\n:::")? + 1;
+    ///         Some(start..start + 3)
+    ///     }
+    ///
+    ///     fn ops(&self, block: &ExtensionBlock) -> Vec<Op> {
+    ///         block.custom(self.name(), true)
+    ///     }
+    /// }
+    ///
+    /// let options = ParseOptions { extensions: &[&Aside], ..ParseOptions::default() };
+    /// let yamd = yamd::deserialize_with(":::\n# a\n:::", options);
+    /// assert_eq!(
+    ///     yamd,
+    ///     Yamd::new(
+    ///         None,
+    ///         vec![
+    ///             Custom::new("aside", ":::\n", "# a\n", ":::")
+    ///                 .with_nodes(vec![Heading::new(1, vec!["a".to_string().into()]).into()])
+    ///                 .into()
+    ///         ]
+    ///     )
+    /// );
+    /// ```
+    fn ops(&self, block: &ExtensionBlock) -> Vec<Op> {
+        block.custom(self.name(), false)
+    }
+}
+
+impl std::fmt::Debug for dyn BlockExtension + '_ {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("BlockExtension").field(&self.name()).finish()
+    }
+}
+
+/// Block found by a [`BlockExtension`], see [`BlockExtension::ops`]. Ranges are byte ranges in
+/// the [source](ExtensionBlock::source).
+#[derive(Debug)]
+pub struct ExtensionBlock<'a> {
+    /// Whole document.
+    pub source: &'a str,
+    /// Opening delimiter.
+    pub opening: Range<usize>,
+    /// Text between the delimiters.
+    pub body: Range<usize>,
+    /// Closing delimiter.
+    pub closing: Range<usize>,
+    options: ParseOptions<'a>,
+}
+
+impl ExtensionBlock<'_> {
+    /// Parses `range` of the [source](ExtensionBlock::source) as a document of its own, with the
+    /// same [`ParseOptions`] except for metadata. Returns ops from
+    /// [`Start`](crate::op::OpKind::Start)`(`[`Node::Document`]`)` to
+    /// [`End`](crate::op::OpKind::End)`(`[`Node::Document`]`)`, which can be nested in a
+    /// [`Custom`](crate::nodes::Custom) or a [`Collapsible`](crate::nodes::Collapsible).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is not in the source or not on char boundaries.
+    pub fn parse(&self, range: Range<usize>) -> Vec<Op> {
+        let mut p = Parser::from(&self.source[range.clone()]);
+        p.options = ParseOptions {
+            metadata: false,
+            ..self.options
+        };
+        document(&mut p);
+        p.ops
+            .into_iter()
+            .map(|op| match op.content {
+                Content::Span(span) if !span.is_empty() => Op {
+                    kind: op.kind,
+                    content: Content::Span(span.start + range.start..span.end + range.start),
+                },
+                _ => op,
+            })
+            .collect()
+    }
+
+    /// Returns ops of a [`Custom`](crate::nodes::Custom) named `name`. With `nested`, the body,
+    /// without the newline before the closing delimiter, is also [parsed](ExtensionBlock::parse)
+    /// into [nodes](crate::nodes::Custom::nodes).
+    pub fn custom(&self, name: &str, nested: bool) -> Vec<Op> {
+        let mut ops = vec![
+            Op::new_start(Node::Custom, Content::Span(self.opening.clone())),
+            Op::new_value(Content::Materialized(name.to_string())),
+            Op::new_value(Content::Span(self.body.clone())),
+        ];
+        if nested {
+            let body = &self.source[self.body.clone()];
+            let len = body.strip_suffix('\n').unwrap_or(body).len();
+            ops.extend(self.parse(self.body.start..self.body.start + len));
+        }
+        ops.push(Op::new_end(
+            Node::Custom,
+            Content::Span(self.closing.clone()),
+        ));
+        ops
+    }
+}
+
+pub fn custom(p: &mut Parser, extension: &dyn BlockExtension) -> bool {
+    let start = p.pos;
+    let Some(from) = p
+        .get(p.pos)
+        .filter(|t| t.position.column == 0)
+        .map(|t| t.range.start)
+    else {
+        return false;
+    };
+    let input = &p.source[from..];
+    let Some(opening) = extension
+        .start(input)
+        .filter(|opening| *opening > 0 && input.is_char_boundary(*opening))
+    else {
+        return false;
+    };
+    let rest = &input[opening..];
+    let Some(closing) = extension.end(rest).filter(|closing| {
+        closing.start <= closing.end
+            && rest.is_char_boundary(closing.start)
+            && rest.is_char_boundary(closing.end)
+    }) else {
+        return false;
+    };
+    let block = ExtensionBlock {
+        source: p.source,
+        opening: from..from + opening,
+        body: from + opening..from + opening + closing.start,
+        closing: from + opening + closing.start..from + opening + closing.end,
+        options: p.options,
+    };
+    let end = block.closing.end;
+
+    // the closing delimiter must end where a token starts, before the end of the enclosing block
+    while let Some((_, token)) = p.peek() {
+        if token.range.start >= end {
+            break;
+        }
+        if p.at_eof() {
+            p.pos = start;
+            return false;
+        }
+        p.next();
+    }
+    let at_line_end = match p.peek() {
+        Some((_, token)) => {
+            token.range.start == end && matches!(token.kind, TokenKind::Eol | TokenKind::Terminator)
+        }
+        None => true,
+    };
+    let ops = extension.ops(&block);
+    if !at_line_end || !is_well_formed(&ops, from..end) {
+        p.pos = start;
+        return false;
+    }
+    p.eat(eol);
+    p.ops.extend(ops);
+    true
+}
+
+/// Returns `true` if Start and End `ops` are balanced, the outermost ones are blocks, and spans
+/// are inside of `block`.
+fn is_well_formed(ops: &[Op], block: Range<usize>) -> bool {
+    let mut open = vec![];
+    for op in ops {
+        if let Content::Span(span) = &op.content {
+            if !span.is_empty() && (span.start < block.start || span.end > block.end) {
+                return false;
+            }
+        }
+        match op.kind {
+            OpKind::Start(node) if open.is_empty() && !is_block(node) => return false,
+            OpKind::Start(node) => open.push(node),
+            OpKind::End(node) if open.pop() != Some(node) => return false,
+            OpKind::End(_) => {}
+            OpKind::Value if open.is_empty() => return false,
+            OpKind::Value => {}
+        }
+    }
+    !ops.is_empty() && open.is_empty()
+}
+
+fn is_block(node: Node) -> bool {
+    matches!(
+        node,
+        Node::Code
+            | Node::Collapsible
+            | Node::Custom
+            | Node::DisplayMath
+            | Node::Embed
+            | Node::FootnoteDefinition
+            | Node::Heading
+            | Node::Highlight
+            | Node::Image
+            | Node::Images
+            | Node::OrderedList
+            | Node::Paragraph
+            | Node::Table
+            | Node::ThematicBreak
+            | Node::UnorderedList
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        deserialize_with,
+        nodes::{
+            Bold, Collapsible, Custom, Embed, Highlight, List, ListItem, ListTypes, Paragraph, Yamd,
+        },
+        op::{ParseOptions, parse_with, to_spans},
+    };
+
+    /// `{{name|args}}` on one line.
+    struct Youtube;
+
+    impl BlockExtension for Youtube {
+        fn name(&self) -> &str {
+            "youtube"
+        }
+
+        fn start(&self, input: &str) -> Option<usize> {
+            input.starts_with("{{youtube|").then_some(10)
+        }
+
+        fn end(&self, input: &str) -> Option<Range<usize>> {
+            let line = &input[..input.find('\n').unwrap_or(input.len())];
+            let start = line.find("}}")?;
+            Some(start..start + 2)
+        }
+    }
+
+    /// `:::` fenced block parsed into a [Highlight](crate::nodes::Highlight) titled with the rest
+    /// of the opening line.
+    struct Admonition;
+
+    impl BlockExtension for Admonition {
+        fn name(&self) -> &str {
+            "admonition"
+        }
+
+        fn start(&self, input: &str) -> Option<usize> {
+            input.starts_with("::: ").then_some(4)
+        }
+
+        fn end(&self, input: &str) -> Option<Range<usize>> {
+            let start = input.find("\n:::")? + 1;
+            Some(start..start + 3)
+        }
+
+        fn ops(&self, block: &ExtensionBlock) -> Vec<Op> {
+            let body = &block.source[block.body.clone()];
+            let Some(title_end) = body.find('\n').map(|eol| block.body.start + eol) else {
+                return vec![];
+            };
+            let mut ops = vec![
+                Op::new_start(Node::Highlight, Content::Span(block.opening.clone())),
+                Op::new_start(Node::Modifier, Content::Span(0..0)),
+                Op::new_value(Content::Span(block.body.start..title_end)),
+                Op::new_end(Node::Modifier, Content::Span(title_end..title_end + 1)),
+            ];
+            // paragraphs of the document, the rest is dropped
+            let mut depth = 0;
+            for op in block.parse(title_end + 1..block.body.end) {
+                match op.kind {
+                    OpKind::Start(Node::Document) | OpKind::End(Node::Document) => {}
+                    OpKind::Start(Node::Paragraph) => {
+                        depth += 1;
+                        ops.push(op);
+                    }
+                    OpKind::End(Node::Paragraph) => {
+                        depth -= 1;
+                        ops.push(op);
+                    }
+                    _ if depth > 0 => ops.push(op),
+                    _ => {}
+                }
+            }
+            ops.push(Op::new_end(
+                Node::Highlight,
+                Content::Span(block.closing.clone()),
+            ));
+            ops
+        }
+    }
+
+    /// Block whose ops are not well-formed.
+    struct Broken;
+
+    impl BlockExtension for Broken {
+        fn name(&self) -> &str {
+            "broken"
+        }
+
+        fn start(&self, input: &str) -> Option<usize> {
+            input.starts_with("??").then_some(2)
+        }
+
+        fn end(&self, input: &str) -> Option<Range<usize>> {
+            let start = input.find("??")?;
+            Some(start..start + 2)
+        }
+
+        fn ops(&self, block: &ExtensionBlock) -> Vec<Op> {
+            match &block.source[block.body.clone()] {
+                "unbalanced" => vec![Op::new_start(Node::Custom, Content::Span(0..0))],
+                "inline" => vec![
+                    Op::new_start(Node::Bold, Content::Span(0..0)),
+                    Op::new_end(Node::Bold, Content::Span(0..0)),
+                ],
+                _ => vec![
+                    Op::new_start(Node::Paragraph, Content::Span(0..0)),
+                    Op::new_value(Content::Span(0..block.source.len())),
+                    Op::new_end(Node::Paragraph, Content::Span(0..0)),
+                ],
+            }
+        }
+    }
+
+    fn options() -> ParseOptions<'static> {
+        ParseOptions {
+            extensions: &[&Youtube],
+            ..ParseOptions::default()
+        }
+    }
+
+    fn youtube(id: &str) -> Custom {
+        Custom::new("youtube", "{{youtube|", id, "}}")
+    }
+
+    #[test]
+    fn extension_takes_precedence() {
+        let input = "{{youtube|123}}\n{{vimeo|456}}";
+        assert_eq!(
+            deserialize_with(input, options()),
+            Yamd::new(
+                None,
+                vec![youtube("123").into(), Embed::new("vimeo", "456").into()]
+            )
+        );
+    }
+
+    #[test]
+    fn block_must_end_at_line_end() {
+        let input = "{{youtube|123}} text\n\n{{youtube|123}}\n\n{{youtube|";
+        assert_eq!(
+            deserialize_with(input, options()),
+            Yamd::new(
+                None,
+                vec![
+                    Paragraph::new(vec!["{{youtube|123}} text".to_string().into()]).into(),
+                    youtube("123").into(),
+                    Paragraph::new(vec!["{{youtube|".to_string().into()]).into(),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn nested_documents() {
+        let input = "{% a\n{{youtube|123}}\n%}";
+        assert_eq!(
+            deserialize_with(input, options()),
+            Yamd::new(
+                None,
+                vec![Collapsible::new("a", vec![youtube("123").into()]).into()]
+            )
+        );
+    }
+
+    #[test]
+    fn own_ops() {
+        let options = ParseOptions {
+            extensions: &[&Admonition],
+            ..ParseOptions::default()
+        };
+        let input = "::: Note\n**a** b\n\n# c\n\nd\n:::\ne";
+        assert_eq!(
+            deserialize_with(input, options),
+            Yamd::new(
+                None,
+                vec![
+                    Highlight::new(
+                        Some("Note"),
+                        None::<String>,
+                        vec![
+                            Paragraph::new(vec![
+                                Bold::new(vec!["a".to_string().into()]).into(),
+                                " b".to_string().into()
+                            ]),
+                            Paragraph::new(vec!["d".to_string().into()]),
+                        ]
+                    )
+                    .into(),
+                    Paragraph::new(vec!["e".to_string().into()]).into(),
+                ]
+            )
+        );
+        let spans = to_spans(&parse_with(input, options), input);
+        assert_eq!(spans.body[0].span.range, 0..28);
+        assert_eq!(spans.body[0].children[1].span.range, 23..25);
+        assert_eq!(spans.body[0].children[1].span.start.row, 5);
+    }
+
+    #[test]
+    fn nested_custom() {
+        struct Aside;
+
+        impl BlockExtension for Aside {
+            fn name(&self) -> &str {
+                "aside"
+            }
+
+            fn start(&self, input: &str) -> Option<usize> {
+                input.starts_with("<<\n").then_some(3)
+            }
+
+            fn end(&self, input: &str) -> Option<Range<usize>> {
+                let start = input.find("\n>>")? + 1;
+                Some(start..start + 2)
+            }
+
+            fn ops(&self, block: &ExtensionBlock) -> Vec<Op> {
+                block.custom(self.name(), true)
+            }
+        }
+
+        let options = ParseOptions {
+            extensions: &[&Aside],
+            ..ParseOptions::default()
+        };
+        let input = "<<\n---\na: b\n---\n\n- a\n>>";
+        let yamd = deserialize_with(input, options);
+        assert_eq!(
+            yamd,
+            Yamd::new(
+                None,
+                vec![
+                    Custom::new("aside", "<<\n", "---\na: b\n---\n\n- a\n", ">>")
+                        .with_nodes(vec![
+                            // nested documents have no metadata
+                            Paragraph::new(vec!["---\na: b\n---".to_string().into()]).into(),
+                            List::new(
+                                ListTypes::Unordered,
+                                0,
+                                vec![ListItem::new(vec!["a".to_string().into()], None)],
+                            )
+                            .into(),
+                        ])
+                        .into()
+                ]
+            )
+        );
+        assert_eq!(yamd.to_string(), input);
+        let spans = to_spans(&parse_with(input, options), input);
+        assert_eq!(spans.body[0].span.range, 0..23);
+        assert_eq!(spans.body[0].children[1].span.range, 17..20);
+        assert_eq!(spans.body[0].children[1].span.start.row, 5);
+    }
+
+    #[test]
+    fn ops_must_be_well_formed() {
+        let options = ParseOptions {
+            extensions: &[&Broken],
+            ..ParseOptions::default()
+        };
+        for input in ["??unbalanced??", "??inline??", "??outside??"] {
+            assert_eq!(
+                deserialize_with(input, options),
+                Yamd::new(
+                    None,
+                    vec![Paragraph::new(vec![input.to_string().into()]).into()]
+                ),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn spans() {
+        let input = "a\n\n{{youtube|123}}\nb";
+        let ops = parse_with(input, options());
+        let spans = to_spans(&ops, input);
+        assert_eq!(spans.body[1].span.range, 3..18);
+        assert_eq!(spans.body[2].span.range, 19..20);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{
+    lexer::{Token, TokenKind},
+    op::{
+        Content, Node, Op, OpKind, Parser,
+        footnote_reference::{Label, label},
+        paragraph::paragraph,
+    },
+};
+
+pub fn footnote_definition(p: &mut Parser) -> bool {
+    let start = p.pos;
+    if !p.at(|t| t.position.column == 0) {
+        return false;
+    }
+    let Some(Label {
+        start: label_start,
+        label,
+        end,
+    }) = label(p)
+    else {
+        return false;
+    };
+    let source = p.source;
+    let is_colon = |t: &Token| {
+        t.kind == TokenKind::Literal && !t.escaped && source[t.range.clone()].starts_with(": ")
+    };
+    let Some(colon) = p.get(p.pos).filter(|t| is_colon(t)).map(|t| t.range.start) else {
+        p.pos = start;
+        return false;
+    };
+
+    p.ops
+        .push(Op::new_start(Node::FootnoteDefinition, Content::Span(0..0)));
+    p.ops
+        .push(Op::new_start(Node::Title, Content::Span(label_start)));
+    p.ops.push(Op::new_value(Content::Span(label)));
+    p.ops.push(Op::new_end(
+        Node::Title,
+        Content::Span(end.start..colon + 2),
+    ));
+    let body = p.ops.len() + 1;
+    paragraph(p);
+    strip_colon(&mut p.ops, body);
+    p.ops
+        .push(Op::new_end(Node::FootnoteDefinition, Content::Span(0..0)));
+    true
+}
+
+/// Removes `: ` that goes to the [Title](Node::Title) op from the start of the first value op of
+/// the body, and removes the op if nothing is left.
+fn strip_colon(ops: &mut Vec<Op>, index: usize) {
+    let Some(op) = ops.get_mut(index).filter(|op| op.kind == OpKind::Value) else {
+        return;
+    };
+    match &mut op.content {
+        Content::Span(range) => range.start += 2,
+        Content::Materialized(text) => {
+            text.drain(..2);
+        }
+    }
+    if op.content.is_empty() {
+        ops.remove(index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::op::{
+        Content, Node, Op, Parser, footnote_definition::footnote_definition, parser::StopCondition,
+    };
+
+    #[test]
+    fn happy_path() {
+        let mut p: Parser = "[^a]: b [^c]".into();
+        assert!(footnote_definition(&mut p));
+        assert_eq!(
+            p.ops,
+            vec![
+                Op::new_start(Node::FootnoteDefinition, Content::Span(0..0)),
+                Op::new_start(Node::Title, Content::Span(0..2)),
+                Op::new_value(Content::Span(2..3)),
+                Op::new_end(Node::Title, Content::Span(3..6)),
+                Op::new_start(Node::Paragraph, Content::Span(0..0)),
+                Op::new_value(Content::Span(6..8)),
+                Op::new_start(Node::FootnoteReference, Content::Span(8..10)),
+                Op::new_value(Content::Span(10..11)),
+                Op::new_end(Node::FootnoteReference, Content::Span(11..12)),
+                Op::new_end(Node::Paragraph, Content::Span(0..0)),
+                Op::new_end(Node::FootnoteDefinition, Content::Span(0..0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn escaped_body() {
+        let mut p: Parser = "[^a]: \\*".into();
+        assert!(footnote_definition(&mut p));
+        assert_eq!(
+            p.ops[5],
+            Op::new_value(Content::Materialized("*".to_string()))
+        );
+    }
+
+    #[test]
+    fn empty_body() {
+        let mut p: Parser = "[^a]: \n\nb".into();
+        p.with_eof(StopCondition::Terminator, |p| {
+            assert!(footnote_definition(p));
+        });
+        assert_eq!(
+            p.ops[4..],
+            [
+                Op::new_start(Node::Paragraph, Content::Span(0..0)),
+                Op::new_end(Node::Paragraph, Content::Span(0..0)),
+                Op::new_end(Node::FootnoteDefinition, Content::Span(0..0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn requires_colon_and_space() {
+        for input in ["[^a]:b", "[^a] b", "[^a]", "[^a]\\: b", "a [^a]: b"] {
+            let mut p: Parser = input.into();
+            if input.starts_with('a') {
+                p.next();
+            }
+            let pos = p.pos;
+            assert!(!footnote_definition(&mut p), "{input:?}");
+            assert!(p.ops.is_empty());
+            assert_eq!(p.pos, pos);
+        }
+    }
+}
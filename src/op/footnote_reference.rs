@@ -0,0 +1,99 @@
+use std::ops::Range;
+
+use crate::{
+    lexer::{Token, TokenKind},
+    nodes::is_footnote_label,
+    op::{Content, Node, Op, Parser},
+};
+
+fn is_left_bracket(t: &Token) -> bool {
+    t.kind == TokenKind::LeftSquareBracket && t.range.len() == 1
+}
+
+fn is_right_bracket(t: &Token) -> bool {
+    t.kind == TokenKind::RightSquareBracket && t.range.len() == 1
+}
+
+/// Byte ranges of `[^`, label and `]`.
+pub(crate) struct Label {
+    pub start: Range<usize>,
+    pub label: Range<usize>,
+    pub end: Range<usize>,
+}
+
+/// Eats `[^label]`, restores position if there is no valid label.
+pub(crate) fn label(p: &mut Parser) -> Option<Label> {
+    let start = p.pos;
+    let source = p.source;
+    let open = p.eat(is_left_bracket)?;
+    let is_caret = |t: &Token| {
+        t.kind == TokenKind::Literal && !t.escaped && source[t.range.clone()].starts_with('^')
+    };
+    if !p.at(is_caret) {
+        p.pos = start;
+        return None;
+    }
+    let Some((body, close)) = p.eat_until(is_right_bracket) else {
+        p.pos = start;
+        return None;
+    };
+    let (Content::Span(body), Some(open), Some(close)) =
+        (p.span(body), p.get(open.start), p.get(close.start))
+    else {
+        p.pos = start;
+        return None;
+    };
+    let label = body.start + 1..body.end;
+    if !is_footnote_label(&source[label.clone()]) {
+        p.pos = start;
+        return None;
+    }
+    Some(Label {
+        start: open.range.start..label.start,
+        label,
+        end: close.range.clone(),
+    })
+}
+
+pub fn footnote_reference(p: &mut Parser) -> bool {
+    let Some(Label { start, label, end }) = label(p) else {
+        return false;
+    };
+    p.ops
+        .push(Op::new_start(Node::FootnoteReference, Content::Span(start)));
+    p.ops.push(Op::new_value(Content::Span(label)));
+    p.ops
+        .push(Op::new_end(Node::FootnoteReference, Content::Span(end)));
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::op::{Content, Node, Op, Parser, footnote_reference::footnote_reference};
+
+    #[test]
+    fn happy_path() {
+        let mut p: Parser = "[^note-1]".into();
+        assert!(footnote_reference(&mut p));
+        assert_eq!(
+            p.ops,
+            vec![
+                Op::new_start(Node::FootnoteReference, Content::Span(0..2)),
+                Op::new_value(Content::Span(2..8)),
+                Op::new_end(Node::FootnoteReference, Content::Span(8..9)),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_labels() {
+        for input in [
+            "[^]", "[^a b]", "[^a", "[\\^a]", "[^a\\]]", "[^[a]", "[a]", "[^a\nb]",
+        ] {
+            let mut p: Parser = input.into();
+            assert!(!footnote_reference(&mut p), "{input:?}");
+            assert!(p.ops.is_empty());
+            assert_eq!(p.pos, 0);
+        }
+    }
+}
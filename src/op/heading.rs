@@ -1,6 +1,6 @@
 use crate::{
     lexer::{Token, TokenKind},
-    op::{Content, Node, Op, Parser, anchor::anchor, parser::eat_seq},
+    op::{Content, Node, Op, Parser, anchor::anchor, inline_math::inline_math, parser::eat_seq},
 };
 
 fn is_hash(t: &Token) -> bool {
@@ -25,7 +25,7 @@ pub fn heading(p: &mut Parser) -> bool {
         } else {
             let before_pos = p.pos;
             let before_snap = p.ops.len();
-            if anchor(p) {
+            if (p.options.anchor && anchor(p)) || (p.options.inline_math && inline_math(p)) {
                 if let Some(s) = text_start.take() {
                     let content = p.span(s..before_pos);
                     p.ops.insert(before_snap, Op::new_value(content));
@@ -106,6 +106,7 @@ pub fn highlight(p: &mut Parser) -> bool {
         );
     }
 
+    p.unclosed(Node::Highlight, start..start + 1, "!!");
     p.pos = start;
     p.ops.truncate(snap);
     false
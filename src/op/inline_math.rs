@@ -0,0 +1,142 @@
+use crate::{
+    lexer::{Token, TokenKind},
+    op::{Content, Node, Op, Parser},
+};
+
+fn is_dollar(t: &Token) -> bool {
+    t.kind == TokenKind::Dollar && t.range.len() == 1
+}
+
+/// Body is taken from the source as is, escapes included.
+///
+/// Like in pandoc, the opening `$` must not be followed by whitespace, and the closing `$` must not
+/// be preceded by whitespace or followed by a digit, so prices like `$5 or $10` stay text.
+pub fn inline_math(p: &mut Parser) -> bool {
+    let start = p.pos;
+    let Some(start_range) = p.eat(is_dollar) else {
+        return false;
+    };
+    let body_start = p.get(start_range.start).map_or(0, |t| t.range.end);
+    if p.source[body_start..].starts_with(char::is_whitespace) {
+        p.pos = start;
+        return false;
+    }
+    // a `$` without a closing one is usually a price, so it is not reported as unclosed
+    let Some((body_range, end_range)) = p.eat_until(is_dollar) else {
+        p.pos = start;
+        return false;
+    };
+    if body_range.is_empty() {
+        p.pos = start;
+        return false;
+    }
+    let (body_end, after) = p
+        .get(end_range.start)
+        .map_or((0, 0), |t| (t.range.start, t.range.end));
+    if p.source[..body_end].ends_with(char::is_whitespace)
+        || p.source[after..].starts_with(|c: char| c.is_ascii_digit())
+    {
+        p.pos = start;
+        return false;
+    }
+    let start_content = p.span(start_range);
+    let end_content = p.span(end_range);
+    p.ops.push(Op::new_start(Node::InlineMath, start_content));
+    p.ops
+        .push(Op::new_value(Content::Span(body_start..body_end)));
+    p.ops.push(Op::new_end(Node::InlineMath, end_content));
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        nodes::{Heading, InlineMath, Paragraph, Yamd},
+        op::{Content, Node, Op, Parser, inline_math::inline_math, parser::StopCondition},
+    };
+
+    #[test]
+    fn happy_path() {
+        let mut p: Parser = "$\\frac{a}{b}$".into();
+        assert!(inline_math(&mut p));
+        assert_eq!(
+            p.ops,
+            vec![
+                Op::new_start(Node::InlineMath, Content::Span(0..1)),
+                Op::new_value(Content::Span(1..12)),
+                Op::new_end(Node::InlineMath, Content::Span(12..13)),
+            ]
+        );
+    }
+
+    #[test]
+    fn escaped_dollar_does_not_close() {
+        let mut p: Parser = "$a\\$ b$".into();
+        assert!(inline_math(&mut p));
+        assert_eq!(p.ops[1], Op::new_value(Content::Span(1..6)));
+    }
+
+    #[test]
+    fn not_math() {
+        for input in ["$$", "$a", "$a$$", "\\$a$"] {
+            let mut p: Parser = input.into();
+            assert!(!inline_math(&mut p), "{input}");
+            assert!(p.ops.is_empty());
+            assert_eq!(p.pos, 0);
+        }
+    }
+
+    #[test]
+    fn flanking() {
+        for input in ["$ a$", "$a $", "$a$5", "$\na$"] {
+            let mut p: Parser = input.into();
+            assert!(!inline_math(&mut p), "{input:?}");
+            assert!(p.ops.is_empty());
+            assert_eq!(p.pos, 0);
+        }
+        let mut p: Parser = "$a$b".into();
+        assert!(inline_math(&mut p));
+    }
+
+    #[test]
+    fn currency() {
+        assert_eq!(
+            crate::deserialize("It costs $5 or $10 total."),
+            Yamd::new(
+                None,
+                vec![Paragraph::new(vec!["It costs $5 or $10 total.".to_string().into()]).into()]
+            )
+        );
+        assert_eq!(
+            crate::deserialize("# Price $5 to $10"),
+            Yamd::new(
+                None,
+                vec![Heading::new(1, vec!["Price $5 to $10".to_string().into()]).into()]
+            )
+        );
+        assert_eq!(
+            crate::deserialize("$5 and $x$"),
+            Yamd::new(
+                None,
+                vec![
+                    Paragraph::new(vec![
+                        "$5 and ".to_string().into(),
+                        InlineMath::new("x").into()
+                    ])
+                    .into()
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn terminator() {
+        let mut p: Parser = "$a\n\nb$".into();
+        p.with_eof(StopCondition::Terminator, |p| {
+            assert!(!inline_math(p));
+            assert!(p.ops.is_empty());
+        });
+    }
+}
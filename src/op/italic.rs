@@ -14,6 +14,7 @@ pub fn italic(p: &mut Parser) -> bool {
         return false;
     };
     let Some((body_range, end_range)) = p.eat_until(is_underscore) else {
+        p.unclosed(Node::Italic, start_range, "_");
         p.pos = start;
         return false;
     };
@@ -11,6 +11,38 @@ fn is_space(t: &Token) -> bool {
     t.kind == TokenKind::Space && t.range.len() == 1
 }
 
+fn is_checkbox(t: &Token, source: &str) -> bool {
+    !t.escaped
+        && match t.kind {
+            TokenKind::Space => t.range.len() == 1,
+            TokenKind::Literal => matches!(&source[t.range.clone()], "x" | "X"),
+            _ => false,
+        }
+}
+
+/// Parses `[ ] ` or `[x] ` right after the list marker as [Modifier](Node::Modifier).
+fn task(p: &mut Parser) -> bool {
+    let source = p.source;
+    let Some(checkbox) = eat_seq!(
+        p,
+        |t: &Token| t.kind == TokenKind::LeftSquareBracket && !t.escaped,
+        |t: &Token| is_checkbox(t, source),
+        |t: &Token| t.kind == TokenKind::RightSquareBracket
+    ) else {
+        return false;
+    };
+    let Some(space) = p.eat(is_space) else {
+        p.pos = checkbox.start;
+        return false;
+    };
+    let (checkbox, space) = (p.span(checkbox), p.span(space));
+    p.ops
+        .push(Op::new_start(Node::Modifier, Content::Span(0..0)));
+    p.ops.push(Op::new_value(checkbox));
+    p.ops.push(Op::new_end(Node::Modifier, space));
+    true
+}
+
 fn list_item(p: &mut Parser, level: usize, kind: Option<ListKind>) -> Option<ListKind> {
     if !p.at(|t: &Token| t.position.column == 0) {
         return None;
@@ -32,6 +64,7 @@ fn list_item(p: &mut Parser, level: usize, kind: Option<ListKind>) -> Option<Lis
 
     let start_content = p.span(start_range);
     p.ops.push(Op::new_start(Node::ListItem, start_content));
+    task(p);
 
     p.with_eof(
         StopCondition::ListBoundary {
@@ -368,6 +401,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn task() {
+        let mut p: Parser = "- [x] a\n- [ ] \n- [x]b\n- \\[ ] c".into();
+
+        assert!(list(&mut p, 0));
+        assert_eq!(
+            p.ops,
+            vec![
+                Op::new_start(Node::UnorderedList, Content::Span(0..0)),
+                Op::new_start(Node::ListItem, p.span(0..2)),
+                Op::new_start(Node::Modifier, Content::Span(0..0)),
+                Op::new_value(p.span(2..5)),
+                Op::new_end(Node::Modifier, p.span(5..6)),
+                Op::new_start(Node::Paragraph, Content::Span(0..0)),
+                Op::new_value(p.span(6..8)),
+                Op::new_end(Node::Paragraph, Content::Span(0..0)),
+                Op::new_end(Node::ListItem, Content::Span(0..0)),
+                Op::new_start(Node::ListItem, p.span(8..10)),
+                Op::new_start(Node::Modifier, Content::Span(0..0)),
+                Op::new_value(p.span(10..13)),
+                Op::new_end(Node::Modifier, p.span(13..14)),
+                Op::new_start(Node::Paragraph, Content::Span(0..0)),
+                Op::new_value(p.span(14..15)),
+                Op::new_end(Node::Paragraph, Content::Span(0..0)),
+                Op::new_end(Node::ListItem, Content::Span(0..0)),
+                Op::new_start(Node::ListItem, p.span(15..17)),
+                Op::new_start(Node::Paragraph, Content::Span(0..0)),
+                Op::new_value(p.span(17..22)),
+                Op::new_end(Node::Paragraph, Content::Span(0..0)),
+                Op::new_end(Node::ListItem, Content::Span(0..0)),
+                Op::new_start(Node::ListItem, p.span(22..24)),
+                Op::new_start(Node::Paragraph, Content::Span(0..0)),
+                Op::new_value(p.span(24..28)),
+                Op::new_end(Node::Paragraph, Content::Span(0..0)),
+                Op::new_end(Node::ListItem, Content::Span(0..0)),
+                Op::new_end(Node::UnorderedList, Content::Span(0..0)),
+            ]
+        );
+    }
+
     #[test]
     fn empty_body() {
         let mut p: Parser = "- ".into();
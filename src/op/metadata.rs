@@ -3,17 +3,23 @@ use crate::{
     op::{Node, Op, Parser},
 };
 
-fn is_three_dashes(t: &Token) -> bool {
-    t.kind == TokenKind::Minus && t.position.column == 0 && t.range.len() == 3
+fn is_delimiter(t: &Token) -> bool {
+    matches!(t.kind, TokenKind::Minus | TokenKind::Plus)
+        && t.position.column == 0
+        && t.range.len() == 3
 }
 
+/// Metadata is delimited by `---` or `+++`, closing delimiter must be the same as the opening one.
 pub fn metadata(p: &mut Parser) -> bool {
     let start = p.pos;
-    let Some(start_range) = p.eat(is_three_dashes) else {
+    let Some(start_range) = p.eat(is_delimiter) else {
         return false;
     };
+    let kind = p.get(start_range.start).map(|t| t.kind.clone());
 
-    let Some((body_range, end_range)) = p.eat_until(is_three_dashes) else {
+    let Some((body_range, end_range)) =
+        p.eat_until(|t| is_delimiter(t) && Some(&t.kind) == kind.as_ref())
+    else {
         p.pos = start;
         return false;
     };
@@ -73,6 +79,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn pluses() {
+        let mut p = "+++\ncontent\n---\n+++".into();
+        assert!(metadata(&mut p));
+        assert_eq!(
+            p.ops,
+            vec![
+                Op::new_start(Node::Metadata, p.span(0..1)),
+                Op::new_value(p.span(1..6)),
+                Op::new_end(Node::Metadata, p.span(6..7)),
+            ]
+        );
+    }
+
+    #[test]
+    fn mismatched_delimiters() {
+        let mut p = "+++\ncontent\n---".into();
+        assert!(!metadata(&mut p));
+        assert!(p.ops.is_empty());
+        assert_eq!(p.pos, 0);
+    }
+
     #[test]
     fn only_opening_token() {
         let mut p = "---".into();
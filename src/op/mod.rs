@@ -1,5 +1,6 @@
 //! A flat, streaming alternative to [`deserialize`](crate::deserialize): [`parse`] turns source
 //! text into a `Vec<`[`Op`]`>` of Start/End/Value events instead of a nested tree.
+//! [`parse_iter`] yields the same events lazily, one top level block at a time.
 //!
 //! - [`Op`] pairs an [`OpKind`] with its [`Content`].
 //! - [`OpKind::Start`]`(`[`Node`]`)` / [`OpKind::End`]`(`[`Node`]`)` bracket a node; everything
@@ -18,6 +19,7 @@ use std::ops::Range;
 use serde::{Deserialize, Serialize};
 
 use crate::lexer::Token;
+pub(crate) use crate::op::document::Units;
 pub(crate) use crate::op::parser::Parser;
 
 mod anchor;
@@ -26,24 +28,38 @@ mod code;
 mod code_span;
 mod collapsible;
 mod destination;
+mod diagnostics;
+mod display_math;
 mod document;
 mod embed;
 mod emphasis;
+mod extension;
+mod footnote_definition;
+mod footnote_reference;
 mod heading;
 mod highlight;
 mod image;
 mod images;
+mod inline_math;
 mod italic;
 mod list;
 mod metadata;
 mod modifier;
+mod options;
 mod paragraph;
 mod parser;
 mod strikethrough;
+mod table;
 mod thematic_break;
 mod title;
+mod to_spans;
 mod to_yamd;
-pub use to_yamd::{UnbalancedOpStream, to_yamd, try_to_yamd};
+pub use diagnostics::{UnclosedConstruct, parse_with_diagnostics};
+pub use extension::{BlockExtension, ExtensionBlock};
+pub use options::ParseOptions;
+pub(crate) use to_spans::{LineIndex, op_ranges};
+pub use to_spans::{Span, SpanNode, Spans, to_spans};
+pub use to_yamd::{UnbalancedOpStream, to_borrowed_yamd, to_yamd, try_to_yamd};
 
 /// Text content extracted from the source input.
 ///
@@ -137,7 +153,7 @@ impl From<Vec<Token>> for Content {
 ///
 /// Used in [`OpKind::Start`] and [`OpKind::End`] to mark the boundaries of nested structures
 /// in the flat operation stream.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Node {
     Anchor,
@@ -145,15 +161,20 @@ pub enum Node {
     Code,
     CodeSpan,
     Collapsible,
+    Custom,
     Destination,
+    DisplayMath,
     Document,
     Embed,
     Emphasis,
+    FootnoteDefinition,
+    FootnoteReference,
     Heading,
     Highlight,
     Icon,
     Image,
     Images,
+    InlineMath,
     Italic,
     ListItem,
     Modifier,
@@ -161,6 +182,9 @@ pub enum Node {
     OrderedList,
     Paragraph,
     Strikethrough,
+    Table,
+    TableCell,
+    TableRow,
     ThematicBreak,
     Title,
     UnorderedList,
@@ -235,10 +259,141 @@ impl Op {
 /// assert!(!ops.is_empty());
 /// ```
 pub fn parse(input: &str) -> Vec<Op> {
+    parse_iter_with(input, ParseOptions::default()).collect()
+}
+
+/// Parses markdown source text lazily, yielding the same [`Op`]s as [`parse`].
+///
+/// Source is tokenized and parsed one top level block at a time, and tokens of a block are dropped
+/// once its ops are yielded, so memory stays proportional to the largest block rather than the
+/// whole document. Stop iterating to skip parsing the rest of the input.
+///
+/// ```
+/// use yamd::op::{Node, OpKind};
+///
+/// let source = "# Title\n\nFirst paragraph.\n\nA very long rest of the document.";
+/// let title: String = yamd::op::parse_iter(source)
+///     .take_while(|op| op.kind != OpKind::End(Node::Heading))
+///     .filter(|op| op.kind == OpKind::Value)
+///     .map(|op| op.content.to_string(source))
+///     .collect();
+/// assert_eq!(title, "Title");
+/// ```
+pub fn parse_iter(input: &str) -> OpIter<'_> {
+    parse_iter_with(input, ParseOptions::default())
+}
+
+/// Parses markdown source text like [`parse`], recognizing only the constructs enabled in
+/// `options`. Syntax of a disabled construct stays literal text.
+///
+/// ```
+/// use yamd::op::{Node, OpKind, ParseOptions};
+///
+/// let options = ParseOptions { bold: false, ..ParseOptions::default() };
+/// let ops = yamd::op::parse_with("**text**", options);
+/// assert!(ops.iter().all(|op| op.kind != OpKind::Start(Node::Bold)));
+/// ```
+pub fn parse_with(input: &str, options: ParseOptions) -> Vec<Op> {
+    parse_iter_with(input, options).collect()
+}
+
+/// Parses markdown source text lazily like [`parse_iter`], recognizing only the constructs
+/// enabled in `options` like [`parse_with`].
+///
+/// ```
+/// use yamd::op::{Node, OpKind, ParseOptions};
+///
+/// let options = ParseOptions { bold: false, ..ParseOptions::default() };
+/// let mut ops = yamd::op::parse_iter_with("**text**\n\nrest", options);
+/// assert!(ops.all(|op| op.kind != OpKind::Start(Node::Bold)));
+/// ```
+pub fn parse_iter_with<'a>(input: &'a str, options: ParseOptions<'a>) -> OpIter<'a> {
     let mut parser = Parser::from(input);
-    metadata::metadata(&mut parser);
-    document::document(&mut parser);
-    parser.into_ops()
+    parser.options = options;
+    OpIter {
+        parser,
+        state: OpIterState::Metadata,
+        pending: Vec::new().into_iter(),
+    }
+}
+
+/// Lazy iterator over the [`Op`]s of a document, see [`parse_iter`] and [`parse_iter_with`].
+pub struct OpIter<'a> {
+    parser: Parser<'a>,
+    state: OpIterState,
+    /// Ops of the last parsed block that are not yielded yet.
+    pending: std::vec::IntoIter<Op>,
+}
+
+enum OpIterState {
+    Metadata,
+    Body,
+    Done,
+}
+
+impl Iterator for OpIter<'_> {
+    type Item = Op;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(op) = self.pending.next() {
+                return Some(op);
+            }
+            let p = &mut self.parser;
+            match self.state {
+                OpIterState::Metadata => {
+                    if p.options.metadata {
+                        metadata::metadata(p);
+                    }
+                    p.ops
+                        .push(Op::new_start(Node::Document, Content::Span(0..0)));
+                    self.state = OpIterState::Body;
+                }
+                OpIterState::Body if p.at_eof() => {
+                    p.ops.push(Op::new_end(Node::Document, Content::Span(0..0)));
+                    self.state = OpIterState::Done;
+                }
+                OpIterState::Body => {
+                    document::block(p);
+                    p.release();
+                }
+                OpIterState::Done => return None,
+            }
+            self.pending = std::mem::take(&mut p.ops).into_iter();
+        }
+    }
+}
+
+/// Parses the top level blocks at the beginning of `input` that stay the same when more text is
+/// appended to it, or all blocks if `input` is `complete`. With `at_start`, `input` is the
+/// beginning of the document and can start with metadata.
+///
+/// Returns ops of a document that consists of those blocks and the length of the text they were
+/// parsed from, or `None` if there is no such block yet.
+pub(crate) fn parse_final(input: &str, at_start: bool, complete: bool) -> Option<(Vec<Op>, usize)> {
+    let mut metadata = vec![];
+    let mut body = vec![];
+    let mut len = 0;
+    for mut unit in Units::new(input, at_start) {
+        if !complete && unit.read.is_none() {
+            break;
+        }
+        metadata.append(&mut unit.metadata);
+        body.append(&mut unit.body);
+        len = unit.end;
+    }
+    if metadata.is_empty() && body.is_empty() {
+        return None;
+    }
+    Some((document_ops(metadata, body), len))
+}
+
+/// Wraps ops of top level blocks into a document.
+pub(crate) fn document_ops(mut metadata: Vec<Op>, mut body: Vec<Op>) -> Vec<Op> {
+    metadata.push(Op::new_start(Node::Document, Content::Span(0..0)));
+    metadata.append(&mut body);
+    metadata.push(Op::new_end(Node::Document, Content::Span(0..0)));
+    metadata
 }
 
 #[cfg(test)]
@@ -534,6 +689,26 @@ end
         assert_eq!(content, Content::Span(0..0));
     }
 
+    #[test]
+    fn parse_iter_matches_whole_document_parse() {
+        let mut p = Parser::from(TEST_CASE);
+        metadata::metadata(&mut p);
+        document::document(&mut p);
+        assert_eq!(parse_iter(TEST_CASE).collect::<Vec<_>>(), p.ops);
+
+        let mut iter = parse_iter("# a\n\nb");
+        assert_eq!(
+            iter.next(),
+            Some(Op::new_start(Node::Document, Content::Span(0..0)))
+        );
+        assert_eq!(
+            iter.next(),
+            Some(Op::new_start(Node::Heading, Content::Span(0..2)))
+        );
+        assert_eq!(iter.by_ref().count(), 7);
+        assert_eq!(iter.next(), None);
+    }
+
     #[test]
     fn content_from_token_array() {
         let tokens = [Token::new(TokenKind::Literal, 0..3, Position::default())];
@@ -0,0 +1,340 @@
+use crate::op::BlockExtension;
+
+/// Constructs the parser recognizes, see [`parse_with`](crate::op::parse_with).
+///
+/// A disabled construct is not parsed, so its syntax stays literal text of the node around it, the
+/// same as any other text that does not form a node. [`Paragraph`](crate::nodes::Paragraph) is
+/// always enabled.
+///
+/// ```
+/// use yamd::op::ParseOptions;
+///
+/// let options = ParseOptions {
+///     embed: false,
+///     collapsible: false,
+///     ..ParseOptions::default()
+/// };
+/// let yamd = yamd::deserialize_with("{{youtube|123}}", options);
+/// assert_eq!(yamd, yamd::deserialize("\\{\\{youtube|123}}"));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ParseOptions<'a> {
+    /// [Metadata](crate::nodes::Yamd::metadata) at the start of the document.
+    pub metadata: bool,
+    /// [Heading](crate::nodes::Heading).
+    pub heading: bool,
+    /// [Image](crate::nodes::Image) and [Images](crate::nodes::Images).
+    pub image: bool,
+    /// [Code](crate::nodes::Code).
+    pub code: bool,
+    /// [DisplayMath](crate::nodes::DisplayMath).
+    pub display_math: bool,
+    /// [Collapsible](crate::nodes::Collapsible).
+    pub collapsible: bool,
+    /// [Embed](crate::nodes::Embed).
+    pub embed: bool,
+    /// [Highlight](crate::nodes::Highlight).
+    pub highlight: bool,
+    /// [List](crate::nodes::List).
+    pub list: bool,
+    /// [ThematicBreak](crate::nodes::ThematicBreak).
+    pub thematic_break: bool,
+    /// [Table](crate::nodes::Table).
+    pub table: bool,
+    /// [FootnoteDefinition](crate::nodes::FootnoteDefinition).
+    pub footnote_definition: bool,
+    /// [Anchor](crate::nodes::Anchor).
+    pub anchor: bool,
+    /// [Bold](crate::nodes::Bold).
+    pub bold: bool,
+    /// [Italic](crate::nodes::Italic).
+    pub italic: bool,
+    /// [Strikethrough](crate::nodes::Strikethrough).
+    pub strikethrough: bool,
+    /// [Emphasis](crate::nodes::Emphasis).
+    pub emphasis: bool,
+    /// [CodeSpan](crate::nodes::CodeSpan).
+    pub code_span: bool,
+    /// [InlineMath](crate::nodes::InlineMath).
+    pub inline_math: bool,
+    /// [FootnoteReference](crate::nodes::FootnoteReference).
+    pub footnote_reference: bool,
+    /// Parsers of custom blocks, see [BlockExtension]. Options are equal only if they have the same
+    /// extension instances.
+    pub extensions: &'a [&'a dyn BlockExtension],
+}
+
+impl ParseOptions<'_> {
+    /// Enables only inline constructs, every block is a [Paragraph](crate::nodes::Paragraph).
+    ///
+    /// ```
+    /// use yamd::{nodes::YamdNodes, op::ParseOptions};
+    ///
+    /// let yamd = yamd::deserialize_with("# not a **heading**", ParseOptions::inline());
+    /// assert!(matches!(&yamd.body[..], [YamdNodes::Paragraph(_)]));
+    /// ```
+    pub fn inline() -> Self {
+        Self {
+            metadata: false,
+            heading: false,
+            image: false,
+            code: false,
+            display_math: false,
+            collapsible: false,
+            embed: false,
+            highlight: false,
+            list: false,
+            thematic_break: false,
+            table: false,
+            footnote_definition: false,
+            ..Self::default()
+        }
+    }
+}
+
+impl PartialEq for ParseOptions<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        let Self {
+            metadata,
+            heading,
+            image,
+            code,
+            display_math,
+            collapsible,
+            embed,
+            highlight,
+            list,
+            thematic_break,
+            table,
+            footnote_definition,
+            anchor,
+            bold,
+            italic,
+            strikethrough,
+            emphasis,
+            code_span,
+            inline_math,
+            footnote_reference,
+            extensions,
+        } = *self;
+        (
+            metadata,
+            heading,
+            image,
+            code,
+            display_math,
+            collapsible,
+            embed,
+            highlight,
+            list,
+            thematic_break,
+            table,
+            footnote_definition,
+        ) == (
+            other.metadata,
+            other.heading,
+            other.image,
+            other.code,
+            other.display_math,
+            other.collapsible,
+            other.embed,
+            other.highlight,
+            other.list,
+            other.thematic_break,
+            other.table,
+            other.footnote_definition,
+        ) && (
+            anchor,
+            bold,
+            italic,
+            strikethrough,
+            emphasis,
+            code_span,
+            inline_math,
+            footnote_reference,
+        ) == (
+            other.anchor,
+            other.bold,
+            other.italic,
+            other.strikethrough,
+            other.emphasis,
+            other.code_span,
+            other.inline_math,
+            other.footnote_reference,
+        ) && extensions.len() == other.extensions.len()
+            && extensions
+                .iter()
+                .zip(other.extensions)
+                .all(|(a, b)| std::ptr::addr_eq(*a, *b))
+    }
+}
+
+impl Eq for ParseOptions<'_> {}
+
+impl Default for ParseOptions<'_> {
+    /// Enables every construct, the same as [`parse`](crate::op::parse).
+    fn default() -> Self {
+        Self {
+            metadata: true,
+            heading: true,
+            image: true,
+            code: true,
+            display_math: true,
+            collapsible: true,
+            embed: true,
+            highlight: true,
+            list: true,
+            thematic_break: true,
+            table: true,
+            footnote_definition: true,
+            anchor: true,
+            bold: true,
+            italic: true,
+            strikethrough: true,
+            emphasis: true,
+            code_span: true,
+            inline_math: true,
+            footnote_reference: true,
+            extensions: &[],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        deserialize_with,
+        nodes::{Bold, Heading, Paragraph, Yamd},
+    };
+
+    fn paragraph(text: &str) -> Paragraph {
+        Paragraph::new(vec![text.to_string().into()])
+    }
+
+    #[test]
+    fn disabled_blocks_are_paragraphs() {
+        let input = "---\na: b\n---\n\n```\ncode\n```\n\n- item\n\n| a |\n|---|\n| b |";
+        let yamd = deserialize_with(input, ParseOptions::inline());
+        assert_eq!(
+            yamd,
+            Yamd::new(
+                None,
+                vec![
+                    paragraph("---\na: b\n---").into(),
+                    paragraph("```\ncode\n```").into(),
+                    paragraph("- item").into(),
+                    paragraph("| a |\n|---|\n| b |").into(),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn disabled_inlines_are_text() {
+        let options = ParseOptions {
+            italic: false,
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            deserialize_with("**a _b_** _c_", options),
+            Yamd::new(
+                None,
+                vec![
+                    Paragraph::new(vec![
+                        Bold::new(vec!["a _b_".to_string().into()]).into(),
+                        " _c_".to_string().into(),
+                    ])
+                    .into()
+                ]
+            )
+        );
+
+        let options = ParseOptions {
+            anchor: false,
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            deserialize_with("# [a](b)", options),
+            Yamd::new(
+                None,
+                vec![Heading::new(1, vec!["[a](b)".to_string().into()]).into()]
+            )
+        );
+    }
+
+    #[test]
+    fn disabled_syntax_round_trips() {
+        let input = "---\na: b\n---\n\n# [a](b) $c$\n\n###### a\n\n**a _b_ ~~c~~** *d* `e` [f](g) $h$ [^i]\n\n\
+            - [x] a\n\n```rust\nlet a;\n```\n\n$$\na\n$$\n\n{% a\nb\n%}\n\n{{a|b}}\n\n\
+            !! a\nb\n!!\n\n![a](b)\n\n-----\n\n| a |\n|---|\n| b |\n\n[^i]: a";
+        let disabled = [
+            ParseOptions::inline(),
+            ParseOptions {
+                anchor: false,
+                inline_math: false,
+                ..ParseOptions::default()
+            },
+            ParseOptions {
+                bold: false,
+                italic: false,
+                strikethrough: false,
+                emphasis: false,
+                code_span: false,
+                footnote_reference: false,
+                ..ParseOptions::default()
+            },
+        ];
+        for options in disabled {
+            let yamd = deserialize_with(input, options);
+            assert_eq!(crate::deserialize(&yamd.to_string()), yamd, "{options:?}");
+        }
+    }
+
+    #[test]
+    fn equality() {
+        struct A(#[allow(dead_code)] u8);
+        impl BlockExtension for A {
+            fn name(&self) -> &str {
+                "a"
+            }
+            fn start(&self, _: &str) -> Option<usize> {
+                None
+            }
+            fn end(&self, _: &str) -> Option<std::ops::Range<usize>> {
+                None
+            }
+        }
+        let (a, b) = (A(0), A(0));
+        let one: [&dyn BlockExtension; 1] = [&a];
+        let same: [&dyn BlockExtension; 1] = [&a];
+        let other: [&dyn BlockExtension; 1] = [&b];
+        let two: [&dyn BlockExtension; 2] = [&a, &a];
+        let with = |extensions| ParseOptions {
+            extensions,
+            ..ParseOptions::default()
+        };
+        assert_eq!(ParseOptions::default(), ParseOptions::default());
+        assert_ne!(ParseOptions::default(), ParseOptions::inline());
+        // extensions are compared by identity
+        assert_eq!(with(&one), with(&same));
+        assert_ne!(with(&one), with(&other));
+        assert_ne!(with(&one), with(&two));
+        assert_ne!(with(&one), with(&[]));
+    }
+
+    #[test]
+    fn nested_documents_use_the_same_options() {
+        let options = ParseOptions {
+            heading: false,
+            ..ParseOptions::default()
+        };
+        let yamd = deserialize_with("{% a\n# b\n%}", options);
+        let crate::nodes::YamdNodes::Collapsible(collapsible) = &yamd.body[0] else {
+            panic!("expected collapsible, got {:?}", yamd.body[0]);
+        };
+        assert_eq!(collapsible.body, vec![paragraph("# b").into()]);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::op::{
     Content, Node, Op, anchor::anchor, bold::bold, code_span::code_span, emphasis::emphasis,
-    italic::italic, parser::Parser, strikethrough::strikethrough,
+    footnote_reference::footnote_reference, inline_math::inline_math, italic::italic,
+    parser::Parser, strikethrough::strikethrough,
 };
 
 pub fn paragraph(p: &mut Parser) {
@@ -10,8 +11,15 @@ pub fn paragraph(p: &mut Parser) {
     while !p.at_eof() {
         let pos = p.pos;
         let snap = p.ops.len();
-        let matched =
-            strikethrough(p) || italic(p) || bold(p) || anchor(p) || code_span(p) || emphasis(p);
+        let o = p.options;
+        let matched = (o.strikethrough && strikethrough(p))
+            || (o.italic && italic(p))
+            || (o.bold && bold(p))
+            || (o.footnote_reference && footnote_reference(p))
+            || (o.anchor && anchor(p))
+            || (o.code_span && code_span(p))
+            || (o.inline_math && inline_math(p))
+            || (o.emphasis && emphasis(p));
         if matched {
             if let Some(start) = text_start.take() {
                 let content = p.span(start..pos);
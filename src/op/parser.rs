@@ -1,7 +1,7 @@
-use std::ops::Range;
+use std::{cell::Cell, ops::Range};
 
 use crate::lexer::{Lexer, Token, TokenKind};
-use crate::op::{Content, Node, Op};
+use crate::op::{Content, Node, Op, ParseOptions, Span, UnclosedConstruct};
 
 /// Distinguishes unordered (`-`) from ordered (`+`) lists during parsing.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -50,6 +50,8 @@ pub(crate) enum StopCondition {
     HighlightEnd,
     /// A list marker at or below the given nesting level — signals a sibling or parent item.
     ListBoundary { level: usize, kind: ListKind },
+    /// `|` or end of line — ends a table cell.
+    TableCellEnd,
 }
 
 pub(crate) fn is_list_marker(t: &Token, kind: Option<ListKind>) -> bool {
@@ -69,24 +71,23 @@ fn at_list_boundary(p: &Parser, current_level: usize, max_level: usize, kind: Li
         };
         let mut offset = p.pos;
         let matched = if level == 0 {
-            p.tokens.get(offset).is_some_and(|t| t.position.column == 0) && {
+            p.get(offset).is_some_and(|t| t.position.column == 0) && {
                 // check: list_marker, space_1
-                p.tokens.get(offset).is_some_and(|t| is_list_marker(t, k)) && {
+                p.get(offset).is_some_and(|t| is_list_marker(t, k)) && {
                     offset += 1;
-                    p.tokens.get(offset).is_some_and(is_space_1)
+                    p.get(offset).is_some_and(is_space_1)
                 }
             }
         } else {
-            p.tokens.get(offset).is_some_and(|t| t.position.column == 0) && {
+            p.get(offset).is_some_and(|t| t.position.column == 0) && {
                 // check: space of len==level, list_marker, space_1
-                p.tokens
-                    .get(offset)
+                p.get(offset)
                     .is_some_and(|t| t.kind == TokenKind::Space && t.range.len() == level)
                     && {
                         offset += 1;
-                        p.tokens.get(offset).is_some_and(|t| is_list_marker(t, k)) && {
+                        p.get(offset).is_some_and(|t| is_list_marker(t, k)) && {
                             offset += 1;
-                            p.tokens.get(offset).is_some_and(is_space_1)
+                            p.get(offset).is_some_and(is_space_1)
                         }
                     }
             }
@@ -105,7 +106,7 @@ impl StopCondition {
             Self::CollapsibleEnd => {
                 (token.kind == TokenKind::CollapsibleEnd && token.position.column == 0)
                     || (token.kind == TokenKind::Eol
-                        && parser.tokens.get(parser.pos + 1).is_some_and(|t| {
+                        && parser.get(parser.pos + 1).is_some_and(|t| {
                             t.kind == TokenKind::CollapsibleEnd && t.position.column == 0
                         }))
             }
@@ -117,10 +118,15 @@ impl StopCondition {
             Self::ListBoundary { level, kind } => {
                 token.position.column == 0 && at_list_boundary(parser, *level, *level + 1, *kind)
             }
+            Self::TableCellEnd => token.kind == TokenKind::Pipe || token.kind == TokenKind::Eol,
         }
     }
 }
 
+/// Number of tokens after [`pos`](Parser::pos) that are always buffered, so lookahead such as
+/// [`at_eof`](Parser::at_eof) can read them without advancing.
+const LOOKAHEAD: usize = 3;
+
 /// Token-stream parser that produces a flat [`Op`] sequence.
 ///
 /// Wraps the lexer output with a position cursor, a [`StopCondition`] stack for context-sensitive
@@ -129,54 +135,122 @@ impl StopCondition {
 /// Node-specific parsing functions (e.g., `heading`, `paragraph`) receive `&mut Parser`, use
 /// [`eat`](Parser::eat)/[`at`](Parser::at) to match tokens, and push results to [`ops`](Parser::ops).
 /// On mismatch they restore [`pos`](Parser::pos) and truncate `ops` to backtrack.
+///
+/// Tokens are pulled from the [`Lexer`] as the cursor advances, and
+/// [`release`](Parser::release) drops the ones that were already consumed. Token indices are
+/// positions in the whole token stream, not in the buffer.
 pub(crate) struct Parser<'a> {
     pub(crate) source: &'a str,
+    lexer: Lexer<'a>,
+    /// `true` once the lexer returned its last token.
+    lexed: bool,
     tokens: Vec<Token>,
+    /// Index of the first buffered token.
+    offset: usize,
+    /// One past the furthest token index read by [`get`](Parser::get).
+    reach: Cell<usize>,
     pub(crate) pos: usize,
     eof_stack: Vec<StopCondition>,
     pub(crate) ops: Vec<Op>,
+    /// Constructs that were not closed, recorded only when `Some`, see
+    /// [`parse_with_diagnostics`](crate::op::parse_with_diagnostics).
+    pub(crate) diagnostics: Option<Vec<UnclosedConstruct>>,
+    pub(crate) options: ParseOptions<'a>,
 }
 
 impl<'a> From<&'a str> for Parser<'a> {
     fn from(input: &'a str) -> Self {
-        Self {
+        let mut parser = Self {
             source: input,
-            tokens: Lexer::new(input).collect(),
+            lexer: Lexer::new(input),
+            lexed: false,
+            tokens: Vec::new(),
+            offset: 0,
+            reach: Cell::new(0),
             pos: 0,
             eof_stack: Vec::new(),
             ops: Vec::new(),
-        }
+            diagnostics: None,
+            options: ParseOptions::default(),
+        };
+        parser.fill();
+        parser
     }
 }
 
 impl Parser<'_> {
+    /// Lexes tokens until [`LOOKAHEAD`] tokens after the cursor are buffered.
+    fn fill(&mut self) {
+        self.fill_to(self.pos + LOOKAHEAD);
+    }
+
+    /// Lexes tokens until the token at `index` is buffered or the input ends.
+    fn fill_to(&mut self, index: usize) {
+        while !self.lexed && self.offset + self.tokens.len() <= index {
+            match self.lexer.next() {
+                Some(token) => self.tokens.push(token),
+                None => self.lexed = true,
+            }
+        }
+    }
+
+    /// Returns the end of the text that was read since the last call, or `None` if the parser read
+    /// the last token of the input or looked past it.
+    ///
+    /// Ops produced since the last call depend only on that text and on the character after it,
+    /// which can turn a line end into a [Terminator](TokenKind::Terminator). The last token can
+    /// change when text is appended, so with `None` they depend on the rest of the input.
+    pub(crate) fn take_read(&mut self) -> Option<usize> {
+        let reach = self.reach.replace(self.pos);
+        self.fill_to(reach);
+        if reach >= self.offset + self.tokens.len() {
+            return None;
+        }
+        self.tokens
+            .get(reach.checked_sub(self.offset + 1)?)
+            .map(|t| t.range.end)
+    }
+
+    /// Drops the tokens before the cursor. Their indices must not be used afterwards.
+    pub(crate) fn release(&mut self) {
+        self.tokens.drain(..self.pos - self.offset);
+        self.offset = self.pos;
+    }
+
     /// Returns the token at `index`, or `None` if out of bounds.
     #[inline]
     pub(crate) fn get(&self, index: usize) -> Option<&Token> {
-        self.tokens.get(index)
+        debug_assert!(index >= self.offset, "token {index} was released");
+        debug_assert!(
+            self.lexed || index <= self.pos + LOOKAHEAD,
+            "token {index} is past the lookahead"
+        );
+        self.reach.set(self.reach.get().max(index + 1));
+        self.tokens.get(index.checked_sub(self.offset)?)
     }
 
     /// Returns the current position and token, or `None` at end-of-stream.
     #[inline]
     pub(crate) fn peek(&self) -> Option<(usize, &Token)> {
-        Some((self.pos, self.tokens.get(self.pos)?))
+        Some((self.pos, self.get(self.pos)?))
     }
 
     /// Advances the cursor by one. Does nothing at end-of-stream.
     #[inline]
     pub(crate) fn next(&mut self) {
-        if self.pos < self.tokens.len() {
+        if self.get(self.pos).is_some() {
             self.pos += 1;
+            self.fill();
         }
     }
 
     /// Consumes the current token if `pred` returns `true`. Returns the token index range on match, or `None` (without advancing) on mismatch.
     #[inline]
     pub(crate) fn eat(&mut self, pred: impl Fn(&Token) -> bool) -> Option<Range<usize>> {
-        let token = self.tokens.get(self.pos)?;
+        let token = self.get(self.pos)?;
         if pred(token) {
             let start = self.pos;
-            self.pos += 1;
+            self.next();
             Some(start..self.pos)
         } else {
             None
@@ -238,11 +312,31 @@ impl Parser<'_> {
     /// Used during backtracking to prevent a special character from being re-interpreted
     /// as a delimiter on the next parse attempt.
     pub(crate) fn flip_to_literal(&mut self, pos: usize) {
-        if let Some(token) = self.tokens.get_mut(pos) {
+        if let Some(token) = self.tokens.get_mut(pos - self.offset) {
             token.kind = TokenKind::Literal;
         }
     }
 
+    /// Records that `node`, opened by the tokens in `opening`, was not closed by `expected`.
+    /// The construct can still be parsed when the text is parsed again in another context.
+    pub(crate) fn unclosed(&mut self, node: Node, opening: Range<usize>, expected: &'static str) {
+        if self.diagnostics.is_none() || opening.is_empty() {
+            return;
+        }
+        let start = self.get(opening.start).map_or(0, |t| t.range.start);
+        let end = self.get(opening.end - 1).map_or(start, |t| t.range.end);
+        if let Some(diagnostics) = &mut self.diagnostics {
+            diagnostics.push(UnclosedConstruct {
+                node,
+                opening: Span {
+                    range: start..end,
+                    ..Default::default()
+                },
+                expected,
+            });
+        }
+    }
+
     /// Returns `true` if at a block boundary — either at logical EOF or at a [`Terminator`](StopCondition::Terminator) token.
     #[inline]
     pub(crate) fn at_block_boundary(&self) -> bool {
@@ -258,17 +352,16 @@ impl Parser<'_> {
         matcher: impl Fn(&Token) -> bool,
     ) -> Option<(Range<usize>, Range<usize>)> {
         let start = self.pos;
-        while let Some(token) = self.tokens.get(self.pos) {
+        while let Some(token) = self.get(self.pos) {
             if self.at_eof() {
                 break;
             }
-            if matcher(token) {
-                let before = start..self.pos;
-                let match_start = self.pos;
-                self.pos += 1;
-                return Some((before, match_start..self.pos));
+            let matched = matcher(token);
+            let match_start = self.pos;
+            self.next();
+            if matched {
+                return Some((start..match_start, match_start..self.pos));
             }
-            self.pos += 1;
         }
         self.pos = start;
         None
@@ -282,7 +375,7 @@ impl Parser<'_> {
         if range.is_empty() {
             return Content::Span(0..0);
         }
-        let tokens = &self.tokens[range];
+        let tokens = &self.tokens[range.start - self.offset..range.end - self.offset];
         if tokens.iter().any(|t| t.escaped) {
             Content::from_tokens(tokens, self.source)
         } else {
@@ -291,11 +384,6 @@ impl Parser<'_> {
             Content::Span(byte_start..byte_end)
         }
     }
-
-    /// Consumes the parser and returns the accumulated operations.
-    pub(crate) fn into_ops(self) -> Vec<Op> {
-        self.ops
-    }
 }
 
 /// Token predicate that matches an end-of-line token. Intended for use with [`Parser::eat`]
@@ -335,6 +423,33 @@ mod tests {
         assert_eq!(p.pos, 1);
     }
 
+    #[test]
+    fn release_keeps_token_indices() {
+        let mut p = Parser::from("a\nb\nc\nd\ne\nf");
+        assert_eq!(p.tokens.len(), LOOKAHEAD + 1);
+        p.next();
+        p.next();
+        p.release();
+        assert_eq!(p.offset, 2);
+        assert_eq!(p.get(2).map(|t| t.range.clone()), Some(2..3));
+        assert_eq!(p.eat(|t| t.kind == TokenKind::Literal), Some(2..3));
+        assert_eq!(p.eat_until(|t| t.range == (6..7)), Some((3..6, 6..7)));
+        assert_eq!(p.span(2..7), Content::Span(2..7));
+        assert_eq!(p.tokens.len(), p.pos - p.offset + LOOKAHEAD + 1);
+    }
+
+    #[test]
+    fn take_read() {
+        let mut p = Parser::from("a\nb");
+        p.eat(|t| t.kind == TokenKind::Literal);
+        assert_eq!(p.take_read(), Some(1));
+        p.eat(eol);
+        assert_eq!(p.take_read(), Some(2));
+        assert_eq!(p.take_read(), Some(2));
+        assert!(!p.at_eof());
+        assert_eq!(p.take_read(), None);
+    }
+
     #[test]
     fn eat_no_match_does_not_advance() {
         let mut p = Parser::from("hello world");
@@ -13,6 +13,7 @@ pub fn strikethrough(p: &mut Parser) -> bool {
         return false;
     };
     let Some((body_range, end_range)) = p.eat_until(is_tilde) else {
+        p.unclosed(Node::Strikethrough, start_range, "~~");
         p.pos = start;
         return false;
     };
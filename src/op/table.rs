@@ -0,0 +1,342 @@
+use crate::{
+    lexer::{Token, TokenKind},
+    nodes::{TableAlignment, is_alignment_cell},
+    op::{
+        Content, Node, Op, OpKind, Parser,
+        paragraph::paragraph,
+        parser::{StopCondition, eol},
+    },
+};
+
+fn is_row_start(t: &Token) -> bool {
+    t.kind == TokenKind::Pipe && t.position.column == 0
+}
+
+fn is_pipe(t: &Token) -> bool {
+    t.kind == TokenKind::Pipe
+}
+
+fn is_space(t: &Token) -> bool {
+    t.kind == TokenKind::Space
+}
+
+pub fn table(p: &mut Parser) -> bool {
+    let start = p.pos;
+    let snap = p.ops.len();
+    p.ops.push(Op::new_start(Node::Table, Content::Span(0..0)));
+
+    if !row(p) {
+        p.pos = start;
+        p.ops.truncate(snap);
+        return false;
+    }
+
+    if !alignment_row(p) {
+        p.pos = start;
+        p.ops.truncate(snap);
+        return false;
+    }
+
+    while !p.at_block_boundary() {
+        if !row(p) {
+            p.pos = start;
+            p.ops.truncate(snap);
+            return false;
+        }
+    }
+
+    p.ops.push(Op::new_end(Node::Table, Content::Span(0..0)));
+    true
+}
+
+fn row(p: &mut Parser) -> bool {
+    let start = p.pos;
+    let snap = p.ops.len();
+    let Some(mut pipe) = p.eat(is_row_start) else {
+        return false;
+    };
+    p.ops
+        .push(Op::new_start(Node::TableRow, Content::Span(0..0)));
+
+    while !p.at(eol) && !p.at_block_boundary() {
+        cell(p, pipe.start);
+        let Some(next) = p.eat(is_pipe) else {
+            p.pos = start;
+            p.ops.truncate(snap);
+            return false;
+        };
+        pipe = next;
+    }
+
+    if p.ops.len() == snap + 1 {
+        p.pos = start;
+        p.ops.truncate(snap);
+        return false;
+    }
+
+    let end_range = match p.eat(eol) {
+        Some(eol_range) => pipe.start..eol_range.end,
+        None => pipe,
+    };
+    let end_content = p.span(end_range);
+    p.ops.push(Op::new_end(Node::TableRow, end_content));
+    true
+}
+
+/// Parses cell body as a paragraph. One space right after the opening pipe and one space right
+/// before the closing pipe are padding: they go to the cell ops instead of the text.
+fn cell(p: &mut Parser, pipe: usize) {
+    let body_start = p.pos;
+    let pipe_range = p.get(pipe).map(|t| t.range.clone()).unwrap_or_default();
+    let lead = p.at(is_space);
+    let start_content = Content::Span(pipe_range.start..pipe_range.end + usize::from(lead));
+    p.ops.push(Op::new_start(Node::TableCell, start_content));
+
+    let paragraph_start = p.ops.len();
+    p.with_eof(StopCondition::TableCellEnd, paragraph);
+
+    let last = (p.pos > body_start)
+        .then(|| p.get(p.pos - 1))
+        .flatten()
+        .filter(|t| ends_with_padding(t, p.source))
+        .filter(|t| !(lead && p.pos - 1 == body_start && t.range.len() == 1))
+        .map(|t| t.range.end);
+
+    if lead {
+        unpad(&mut p.ops, paragraph_start + 1, true);
+    }
+    let end_content = match last {
+        Some(end) => {
+            let index = p.ops.len() - 2;
+            unpad(&mut p.ops, index, false);
+            Content::Span(end - 1..end)
+        }
+        None => Content::Span(0..0),
+    };
+    p.ops.push(Op::new_end(Node::TableCell, end_content));
+}
+
+/// Returns `true` if the token ends with a space that is not escaped.
+fn ends_with_padding(t: &Token, source: &str) -> bool {
+    source[t.range.clone()].ends_with(' ') && !(t.escaped && t.range.len() == 1)
+}
+
+/// Removes one byte of padding from the start or the end of the value op at `index`, and removes
+/// the op if nothing is left.
+fn unpad(ops: &mut Vec<Op>, index: usize, from_start: bool) {
+    let Some(op) = ops.get_mut(index) else {
+        return;
+    };
+    if op.kind != OpKind::Value {
+        return;
+    }
+    match &mut op.content {
+        Content::Span(range) if from_start => range.start += 1,
+        Content::Span(range) => range.end -= 1,
+        Content::Materialized(text) if from_start => {
+            text.remove(0);
+        }
+        Content::Materialized(text) => {
+            text.pop();
+        }
+    }
+    if op.content.is_empty() {
+        ops.remove(index);
+    }
+}
+
+fn alignment_row(p: &mut Parser) -> bool {
+    let start = p.pos;
+    if !p.at(is_row_start) {
+        return false;
+    }
+    while !p.at(eol) && !p.at_block_boundary() {
+        p.next();
+    }
+    let content = p.span(start..p.pos);
+    if !matches!(content, Content::Span(_)) || alignments(content.as_str(p.source)).is_none() {
+        p.pos = start;
+        return false;
+    }
+    let end_content = match p.eat(eol) {
+        Some(range) => p.span(range),
+        None => Content::Span(0..0),
+    };
+    p.ops
+        .push(Op::new_start(Node::Modifier, Content::Span(0..0)));
+    p.ops.push(Op::new_value(content));
+    p.ops.push(Op::new_end(Node::Modifier, end_content));
+    true
+}
+
+/// Parses the alignment row, `None` if `row` is not one.
+pub(crate) fn alignments(row: &str) -> Option<Vec<TableAlignment>> {
+    let cells = row.strip_prefix('|')?.strip_suffix('|')?;
+    cells
+        .split('|')
+        .map(|cell| {
+            if !is_alignment_cell(cell) {
+                return None;
+            }
+            let cell = cell.trim_matches(' ');
+            Some(match (cell.starts_with(':'), cell.ends_with(':')) {
+                (true, true) => TableAlignment::Center,
+                (true, false) => TableAlignment::Left,
+                (false, true) => TableAlignment::Right,
+                _ => TableAlignment::None,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        nodes::TableAlignment,
+        op::{
+            Content, Node, Op, Parser,
+            table::{alignments, table},
+        },
+    };
+
+    #[test]
+    fn happy_path() {
+        let input = "| a | b |\n| :-- | --: |\n| c |";
+        let mut p: Parser = input.into();
+        assert!(table(&mut p));
+        assert_eq!(
+            p.ops,
+            vec![
+                Op::new_start(Node::Table, Content::Span(0..0)),
+                Op::new_start(Node::TableRow, Content::Span(0..0)),
+                Op::new_start(Node::TableCell, Content::Span(0..2)),
+                Op::new_start(Node::Paragraph, Content::Span(0..0)),
+                Op::new_value(Content::Span(2..3)),
+                Op::new_end(Node::Paragraph, Content::Span(0..0)),
+                Op::new_end(Node::TableCell, Content::Span(3..4)),
+                Op::new_start(Node::TableCell, Content::Span(4..6)),
+                Op::new_start(Node::Paragraph, Content::Span(0..0)),
+                Op::new_value(Content::Span(6..7)),
+                Op::new_end(Node::Paragraph, Content::Span(0..0)),
+                Op::new_end(Node::TableCell, Content::Span(7..8)),
+                Op::new_end(Node::TableRow, Content::Span(8..10)),
+                Op::new_start(Node::Modifier, Content::Span(0..0)),
+                Op::new_value(Content::Span(10..23)),
+                Op::new_end(Node::Modifier, Content::Span(23..24)),
+                Op::new_start(Node::TableRow, Content::Span(0..0)),
+                Op::new_start(Node::TableCell, Content::Span(24..26)),
+                Op::new_start(Node::Paragraph, Content::Span(0..0)),
+                Op::new_value(Content::Span(26..27)),
+                Op::new_end(Node::Paragraph, Content::Span(0..0)),
+                Op::new_end(Node::TableCell, Content::Span(27..28)),
+                Op::new_end(Node::TableRow, Content::Span(28..29)),
+                Op::new_end(Node::Table, Content::Span(0..0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn cell_without_padding() {
+        let mut p: Parser = "|a||\n|-|-|".into();
+        assert!(table(&mut p));
+        assert_eq!(
+            p.ops,
+            vec![
+                Op::new_start(Node::Table, Content::Span(0..0)),
+                Op::new_start(Node::TableRow, Content::Span(0..0)),
+                Op::new_start(Node::TableCell, Content::Span(0..1)),
+                Op::new_start(Node::Paragraph, Content::Span(0..0)),
+                Op::new_value(Content::Span(1..2)),
+                Op::new_end(Node::Paragraph, Content::Span(0..0)),
+                Op::new_end(Node::TableCell, Content::Span(0..0)),
+                Op::new_start(Node::TableCell, Content::Span(2..3)),
+                Op::new_start(Node::Paragraph, Content::Span(0..0)),
+                Op::new_end(Node::Paragraph, Content::Span(0..0)),
+                Op::new_end(Node::TableCell, Content::Span(0..0)),
+                Op::new_end(Node::TableRow, Content::Span(3..5)),
+                Op::new_start(Node::Modifier, Content::Span(0..0)),
+                Op::new_value(Content::Span(5..10)),
+                Op::new_end(Node::Modifier, Content::Span(0..0)),
+                Op::new_end(Node::Table, Content::Span(0..0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn padding_is_removed_once() {
+        let mut p: Parser = "|   | |\n|-|-|".into();
+        assert!(table(&mut p));
+        assert_eq!(
+            p.ops,
+            vec![
+                Op::new_start(Node::Table, Content::Span(0..0)),
+                Op::new_start(Node::TableRow, Content::Span(0..0)),
+                Op::new_start(Node::TableCell, Content::Span(0..2)),
+                Op::new_start(Node::Paragraph, Content::Span(0..0)),
+                Op::new_value(Content::Span(2..3)),
+                Op::new_end(Node::Paragraph, Content::Span(0..0)),
+                Op::new_end(Node::TableCell, Content::Span(3..4)),
+                Op::new_start(Node::TableCell, Content::Span(4..6)),
+                Op::new_start(Node::Paragraph, Content::Span(0..0)),
+                Op::new_end(Node::Paragraph, Content::Span(0..0)),
+                Op::new_end(Node::TableCell, Content::Span(0..0)),
+                Op::new_end(Node::TableRow, Content::Span(6..8)),
+                Op::new_start(Node::Modifier, Content::Span(0..0)),
+                Op::new_value(Content::Span(8..13)),
+                Op::new_end(Node::Modifier, Content::Span(0..0)),
+                Op::new_end(Node::Table, Content::Span(0..0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn row_must_end_with_pipe() {
+        let mut p: Parser = "| a | b".into();
+        assert!(!table(&mut p));
+        assert!(p.ops.is_empty());
+        assert_eq!(p.pos, 0);
+    }
+
+    #[test]
+    fn every_line_must_be_a_row() {
+        let mut p: Parser = "| a |\ntext".into();
+        assert!(!table(&mut p));
+        assert!(p.ops.is_empty());
+        assert_eq!(p.pos, 0);
+    }
+
+    #[test]
+    fn row_must_have_a_cell() {
+        let mut p: Parser = "|\n| a |".into();
+        assert!(!table(&mut p));
+        assert!(p.ops.is_empty());
+    }
+
+    #[test]
+    fn alignment_row_is_required() {
+        for input in ["| a |", "| a |\n| b |", "| a |\n| \\--- |"] {
+            let mut p: Parser = input.into();
+            assert!(!table(&mut p), "{input}");
+            assert!(p.ops.is_empty());
+            assert_eq!(p.pos, 0);
+        }
+    }
+
+    #[test]
+    fn parse_alignments() {
+        assert_eq!(
+            alignments("|---| :-- |:-:| --: |"),
+            Some(vec![
+                TableAlignment::None,
+                TableAlignment::Left,
+                TableAlignment::Center,
+                TableAlignment::Right,
+            ])
+        );
+        assert_eq!(alignments("|:|"), None);
+        assert_eq!(alignments("|--|a|"), None);
+        assert_eq!(alignments("|--"), None);
+    }
+}
@@ -0,0 +1,556 @@
+use std::ops::Range;
+
+use crate::lexer::Position;
+use crate::op::{Content, Node, Op, OpKind};
+
+/// Location of a node in the source.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Span {
+    /// Byte range in the source.
+    pub range: Range<usize>,
+    /// Position of the first byte. [Column](Position::column) is counted in chars from the start
+    /// of the [row](Position::row).
+    pub start: Position,
+    /// Position right after the last byte.
+    pub end: Position,
+}
+
+/// [Span] of a node and of its children.
+///
+/// Children follow the order of child nodes of the corresponding AST node:
+///
+/// | Node                                                                      | Children                                               |
+/// |---------------------------------------------------------------------------|--------------------------------------------------------|
+/// | [Paragraph](crate::nodes::Paragraph), [Heading](crate::nodes::Heading), [Bold](crate::nodes::Bold) | body nodes, text included              |
+/// | [List](crate::nodes::List)                                                | list items                                             |
+/// | [ListItem](crate::nodes::ListItem)                                        | text nodes followed by the nested list, if any         |
+/// | [Images](crate::nodes::Images)                                            | images                                                 |
+/// | [Highlight](crate::nodes::Highlight)                                      | paragraphs                                             |
+/// | [Collapsible](crate::nodes::Collapsible)                                  | body nodes                                             |
+/// | [Custom](crate::nodes::Custom)                                            | [nodes](crate::nodes::Custom::nodes)                   |
+/// | [Table](crate::nodes::Table)                                              | header row followed by body rows                       |
+/// | [TableRow](crate::nodes::TableRow)                                        | cells                                                  |
+/// | [TableCell](crate::nodes::TableCell)                                      | body nodes, text included                              |
+/// | [FootnoteDefinition](crate::nodes::FootnoteDefinition)                    | body nodes, text included                              |
+///
+/// Every other node has no children.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SpanNode {
+    pub span: Span,
+    pub children: Vec<SpanNode>,
+}
+
+/// Source locations of a [`Yamd`](crate::nodes::Yamd) document, mirroring its tree.
+///
+/// [Body](Spans::body) has one [SpanNode] per node in [`Yamd::body`](crate::nodes::Yamd::body).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Spans {
+    /// Span of the metadata block, including fences.
+    pub metadata: Option<Span>,
+    pub body: Vec<SpanNode>,
+}
+
+impl Spans {
+    /// Returns span node by its path of child indexes, starting from [body](Spans::body).
+    ///
+    /// ```
+    /// let (_, spans) = yamd::deserialize_with_spans("text\n\n- one\n - two");
+    /// let two = spans.get(&[1, 0, 1, 0]).unwrap();
+    /// assert_eq!(two.span.range, 12..18);
+    /// assert_eq!((two.span.start.row, two.span.start.column), (3, 0));
+    /// ```
+    pub fn get(&self, path: &[usize]) -> Option<&SpanNode> {
+        let (first, rest) = path.split_first()?;
+        rest.iter().try_fold(self.body.get(*first)?, |node, index| {
+            node.children.get(*index)
+        })
+    }
+}
+
+#[derive(PartialEq)]
+enum Role {
+    Node,
+    /// Node whose [Value](OpKind::Value) ops are text nodes.
+    Text,
+    Paragraph,
+    ListItem,
+    /// Node whose children are the body nodes of its only paragraph.
+    ParagraphBody,
+    /// Op that is not a node on its own; its content and children belong to the parent.
+    Transparent,
+    Metadata,
+}
+
+struct Frame {
+    role: Role,
+    range: Option<Range<usize>>,
+    children: Vec<SpanNode>,
+    /// Trailing newline count of the last child, if it is a text node, and whether the text
+    /// consists of newlines only.
+    trailing_newlines: Option<(usize, bool)>,
+}
+
+impl Frame {
+    fn new(role: Role) -> Self {
+        Self {
+            role,
+            range: None,
+            children: Vec::new(),
+            trailing_newlines: None,
+        }
+    }
+
+    fn extend(&mut self, range: &Range<usize>) {
+        if range.is_empty() {
+            return;
+        }
+        self.range = Some(match &self.range {
+            Some(r) => r.start.min(range.start)..r.end.max(range.end),
+            None => range.clone(),
+        });
+    }
+
+    fn push(&mut self, child: SpanNode) {
+        self.extend(&child.span.range);
+        self.children.push(child);
+        self.trailing_newlines = None;
+    }
+}
+
+fn role(node: &Node) -> Role {
+    match node {
+        Node::Paragraph => Role::Paragraph,
+        Node::Heading | Node::Bold => Role::Text,
+        Node::ListItem => Role::ListItem,
+        Node::TableCell | Node::FootnoteDefinition => Role::ParagraphBody,
+        Node::Document | Node::Title | Node::Destination | Node::Modifier | Node::Icon => {
+            Role::Transparent
+        }
+        Node::Metadata => Role::Metadata,
+        _ => Role::Node,
+    }
+}
+
+/// Computes [Spans] of the AST that [`to_yamd`](crate::op::to_yamd) builds from the same `ops`.
+///
+/// ```
+/// let source = "# hello\n\nworld";
+/// let ops = yamd::parse(source);
+/// let spans = yamd::op::to_spans(&ops, source);
+/// assert_eq!(spans.body[1].span.range, 9..14);
+/// assert_eq!(spans.body[1].span.start.row, 2);
+/// ```
+pub fn to_spans(ops: &[Op], source: &str) -> Spans {
+    let ranges = op_ranges(ops, source);
+    let mut metadata = None;
+    let mut stack = vec![Frame::new(Role::Node)];
+
+    for (op, range) in ops.iter().zip(ranges) {
+        match &op.kind {
+            OpKind::Start(node) => {
+                let mut frame = Frame::new(role(node));
+                frame.extend(&range);
+                stack.push(frame);
+            }
+            OpKind::Value => {
+                let Some(top) = stack.last_mut() else {
+                    break;
+                };
+                if matches!(top.role, Role::Paragraph | Role::Text) {
+                    let text = op.content.as_str(source);
+                    let trimmed = text.trim_end_matches('\n');
+                    top.push(SpanNode {
+                        span: Span {
+                            range,
+                            ..Default::default()
+                        },
+                        children: Vec::new(),
+                    });
+                    top.trailing_newlines = Some((text.len() - trimmed.len(), trimmed.is_empty()));
+                } else {
+                    top.extend(&range);
+                }
+            }
+            OpKind::End(_) => {
+                let (Some(mut frame), Some(parent)) = (stack.pop(), stack.last_mut()) else {
+                    break;
+                };
+                frame.extend(&range);
+                let range = frame.range.clone().unwrap_or(range);
+                match frame.role {
+                    Role::Transparent => {
+                        parent.extend(&range);
+                        parent.children.append(&mut frame.children);
+                    }
+                    Role::Metadata => {
+                        metadata = Some(Span {
+                            range,
+                            ..Default::default()
+                        });
+                    }
+                    Role::Paragraph if parent.role == Role::ParagraphBody => {
+                        parent.extend(&range);
+                        parent.children.append(&mut frame.children);
+                    }
+                    Role::Paragraph if parent.role != Role::Transparent => {
+                        trim_trailing_newline(&mut frame);
+                        if parent.role == Role::ListItem {
+                            parent.extend(&range);
+                            parent.children.append(&mut frame.children);
+                        } else {
+                            parent.push(SpanNode {
+                                span: Span {
+                                    range,
+                                    ..Default::default()
+                                },
+                                children: frame.children,
+                            });
+                        }
+                    }
+                    _ => parent.push(SpanNode {
+                        span: Span {
+                            range,
+                            ..Default::default()
+                        },
+                        children: frame.children,
+                    }),
+                }
+            }
+        }
+    }
+
+    let lines = LineIndex::new(source);
+    if let Some(span) = metadata.as_mut() {
+        lines.resolve(span);
+    }
+    let mut body = stack
+        .into_iter()
+        .next()
+        .map(|f| f.children)
+        .unwrap_or_default();
+    body.iter_mut().for_each(|node| lines.resolve_node(node));
+    Spans { metadata, body }
+}
+
+/// Mirrors trimming of the last text node that [`to_yamd`](crate::op::to_yamd) does for
+/// paragraphs inside highlights and list items.
+fn trim_trailing_newline(frame: &mut Frame) {
+    match frame.trailing_newlines {
+        Some((_, true)) => {
+            frame.children.pop();
+        }
+        Some((newlines, false)) => {
+            if let Some(last) = frame.children.last_mut() {
+                last.span.range.end -= newlines;
+            }
+        }
+        None => {}
+    }
+}
+
+/// Returns the byte range each op occupies in `source`.
+///
+/// Placeholder ops (empty [Span](Content::Span)) get an empty range at the current position.
+/// [Materialized](Content::Materialized) content is matched against the source, accounting for
+/// escape characters.
+pub(crate) fn op_ranges(ops: &[Op], source: &str) -> Vec<Range<usize>> {
+    let mut cursor = 0;
+    ops.iter()
+        .map(|op| {
+            let range = match &op.content {
+                Content::Span(range) if !range.is_empty() => range.clone(),
+                Content::Span(_) => cursor..cursor,
+                Content::Materialized(text) => cursor..materialized_end(source, cursor, text),
+            };
+            cursor = range.end;
+            range
+        })
+        .collect()
+}
+
+fn materialized_end(source: &str, start: usize, text: &str) -> usize {
+    let mut end = start;
+    for c in text.chars() {
+        let rest = &source[end..];
+        if rest.starts_with('\\') && rest[1..].starts_with(c) {
+            end += 1 + c.len_utf8();
+        } else if rest.starts_with(c) {
+            end += c.len_utf8();
+        } else {
+            break;
+        }
+    }
+    end
+}
+
+/// Resolves byte offsets to [Position]s.
+pub(crate) struct LineIndex<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub(crate) fn new(source: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    pub(crate) fn position(&self, byte_index: usize) -> Position {
+        let row = self
+            .line_starts
+            .partition_point(|&start| start <= byte_index)
+            - 1;
+        let line_start = self.line_starts[row];
+        Position {
+            byte_index,
+            column: self.source[line_start..byte_index].chars().count(),
+            row,
+        }
+    }
+
+    pub(crate) fn span(&self, range: Range<usize>) -> Span {
+        Span {
+            start: self.position(range.start),
+            end: self.position(range.end),
+            range,
+        }
+    }
+
+    fn resolve(&self, span: &mut Span) {
+        span.start = self.position(span.range.start);
+        span.end = self.position(span.range.end);
+    }
+
+    fn resolve_node(&self, node: &mut SpanNode) {
+        self.resolve(&mut node.span);
+        node.children
+            .iter_mut()
+            .for_each(|child| self.resolve_node(child));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        deserialize_with_spans,
+        lexer::Position,
+        op::{Span, SpanNode, parse, to_spans},
+    };
+
+    fn span(source: &str, range: std::ops::Range<usize>) -> Span {
+        let lines = super::LineIndex::new(source);
+        let mut span = Span {
+            range,
+            ..Default::default()
+        };
+        lines.resolve(&mut span);
+        span
+    }
+
+    fn leaf(source: &str, range: std::ops::Range<usize>) -> SpanNode {
+        SpanNode {
+            span: span(source, range),
+            children: vec![],
+        }
+    }
+
+    #[test]
+    fn heading_with_anchor() {
+        let input = "## a [b](c) d";
+        let (_, spans) = deserialize_with_spans(input);
+        assert_eq!(
+            spans.body,
+            vec![SpanNode {
+                span: span(input, 0..13),
+                children: vec![leaf(input, 3..5), leaf(input, 5..11), leaf(input, 11..13)],
+            }]
+        );
+    }
+
+    #[test]
+    fn positions() {
+        let input = "# a\n\n🤔 **b**";
+        let (_, spans) = deserialize_with_spans(input);
+        let bold = &spans.body[1].children[1].span;
+        assert_eq!(bold.range, 10..15);
+        assert_eq!(
+            bold.start,
+            Position {
+                byte_index: 10,
+                column: 2,
+                row: 2
+            }
+        );
+        assert_eq!(
+            bold.end,
+            Position {
+                byte_index: 15,
+                column: 7,
+                row: 2
+            }
+        );
+    }
+
+    #[test]
+    fn escaped_text() {
+        let input = "a \\*b\\* **c**";
+        let (_, spans) = deserialize_with_spans(input);
+        assert_eq!(
+            spans.body[0].children,
+            vec![
+                leaf(input, 0..8),
+                SpanNode {
+                    span: span(input, 8..13),
+                    children: vec![leaf(input, 10..11)],
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn list() {
+        let input = "- one\n - two [a](b)\n- three";
+        let (_, spans) = deserialize_with_spans(input);
+        assert_eq!(
+            spans.body,
+            vec![SpanNode {
+                span: span(input, 0..27),
+                children: vec![
+                    SpanNode {
+                        span: span(input, 0..20),
+                        children: vec![
+                            leaf(input, 2..5),
+                            SpanNode {
+                                span: span(input, 6..20),
+                                children: vec![SpanNode {
+                                    span: span(input, 6..20),
+                                    children: vec![leaf(input, 9..13), leaf(input, 13..19)],
+                                }],
+                            },
+                        ],
+                    },
+                    SpanNode {
+                        span: span(input, 20..27),
+                        children: vec![leaf(input, 22..27)],
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn highlight_and_collapsible() {
+        let input = "{% t\n!! h\na\n\nb\n!!\n%}";
+        let (_, spans) = deserialize_with_spans(input);
+        assert_eq!(
+            spans.body,
+            vec![SpanNode {
+                span: span(input, 0..20),
+                children: vec![SpanNode {
+                    span: span(input, 5..18),
+                    children: vec![
+                        SpanNode {
+                            span: span(input, 10..11),
+                            children: vec![leaf(input, 10..11)],
+                        },
+                        SpanNode {
+                            span: span(input, 13..15),
+                            children: vec![leaf(input, 13..14)],
+                        },
+                    ],
+                }],
+            }]
+        );
+    }
+
+    #[test]
+    fn table() {
+        let input = "| a | **b** |\n| --- | :-: |\n|c|  |";
+        let (_, spans) = deserialize_with_spans(input);
+        assert_eq!(
+            spans.body,
+            vec![SpanNode {
+                span: span(input, 0..34),
+                children: vec![
+                    SpanNode {
+                        span: span(input, 0..14),
+                        children: vec![
+                            SpanNode {
+                                span: span(input, 0..4),
+                                children: vec![leaf(input, 2..3)],
+                            },
+                            SpanNode {
+                                span: span(input, 4..12),
+                                children: vec![SpanNode {
+                                    span: span(input, 6..11),
+                                    children: vec![leaf(input, 8..9)],
+                                }],
+                            },
+                        ],
+                    },
+                    SpanNode {
+                        span: span(input, 28..34),
+                        children: vec![
+                            SpanNode {
+                                span: span(input, 28..30),
+                                children: vec![leaf(input, 29..30)],
+                            },
+                            leaf(input, 30..33),
+                        ],
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn footnotes() {
+        let input = "a[^1]\n\n[^1]: \\*b [^2]";
+        let (_, spans) = deserialize_with_spans(input);
+        assert_eq!(
+            spans.body,
+            vec![
+                SpanNode {
+                    span: span(input, 0..5),
+                    children: vec![leaf(input, 0..1), leaf(input, 1..5)],
+                },
+                SpanNode {
+                    span: span(input, 7..21),
+                    children: vec![leaf(input, 13..17), leaf(input, 17..21)],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn images_and_metadata() {
+        let input = "---\na: b\n---\n\n![a](b)\n![c](d)";
+        let ops = parse(input);
+        let spans = to_spans(&ops, input);
+        assert_eq!(spans.metadata, Some(span(input, 0..12)));
+        assert_eq!(
+            spans.body,
+            vec![SpanNode {
+                span: span(input, 14..29),
+                children: vec![leaf(input, 14..22), leaf(input, 22..29)],
+            }]
+        );
+    }
+
+    #[test]
+    fn get() {
+        let input = "a\n\n# b";
+        let (_, spans) = deserialize_with_spans(input);
+        assert_eq!(spans.get(&[1]).map(|n| n.span.range.clone()), Some(3..6));
+        assert_eq!(spans.get(&[1, 0]).map(|n| n.span.range.clone()), Some(5..6));
+        assert_eq!(spans.get(&[2]), None);
+        assert_eq!(spans.get(&[]), None);
+    }
+}
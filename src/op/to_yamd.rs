@@ -1,9 +1,12 @@
+use std::borrow::Cow;
+
 use crate::nodes::{
-    Anchor, Bold, BoldNodes, Code, CodeSpan, Collapsible, Embed, Emphasis, Heading, HeadingNodes,
-    Highlight, Image, Images, Italic, List, ListItem, ListTypes, Paragraph, ParagraphNodes,
-    Strikethrough, ThematicBreak, Yamd, YamdNodes,
+    Anchor, Bold, BoldNodes, Code, CodeSpan, Collapsible, Custom, DisplayMath, Embed, Emphasis,
+    FootnoteDefinition, FootnoteReference, Heading, HeadingNodes, Highlight, Image, Images,
+    InlineMath, Italic, List, ListItem, ListTypes, MetadataDelimiter, Paragraph, ParagraphNodes,
+    Strikethrough, Table, TableAlignment, TableCell, TableRow, ThematicBreak, Yamd, YamdNodes,
 };
-use crate::op::{Content, Node, Op, OpKind};
+use crate::op::{Content, Node, Op, OpKind, table::alignments};
 
 /// Error returned by [`try_to_yamd`] when the op stream is not well-formed
 /// (unbalanced Start/End pairs, or an End that doesn't match the frame it closes).
@@ -18,94 +21,195 @@ impl std::fmt::Display for UnbalancedOpStream {
 
 impl std::error::Error for UnbalancedOpStream {}
 
-enum Frame {
+/// Text of the nodes that [`to_yamd`] and [`to_borrowed_yamd`] build.
+trait Text<'a>: Default {
+    fn new(content: &Content, source: &'a str) -> Self;
+    /// Appends `text` that follows this one.
+    fn push(&mut self, text: Self, source: &'a str);
+    fn as_str(&self) -> &str;
+    /// Keeps the part of the text that `trim` returns.
+    fn trim(self, trim: impl FnOnce(&str) -> &str) -> Self;
+}
+
+impl<'a> Text<'a> for String {
+    fn new(content: &Content, source: &'a str) -> Self {
+        content.to_string(source)
+    }
+
+    fn push(&mut self, text: Self, _: &'a str) {
+        self.push_str(&text);
+    }
+
+    fn as_str(&self) -> &str {
+        self
+    }
+
+    fn trim(self, trim: impl FnOnce(&str) -> &str) -> Self {
+        trim(&self).to_owned()
+    }
+}
+
+impl<'a> Text<'a> for Cow<'a, str> {
+    fn new(content: &Content, source: &'a str) -> Self {
+        match content {
+            Content::Span(range) if range.is_empty() => Cow::Borrowed(""),
+            Content::Span(range) => Cow::Borrowed(&source[range.clone()]),
+            Content::Materialized(text) => Cow::Owned(text.clone()),
+        }
+    }
+
+    fn push(&mut self, text: Self, source: &'a str) {
+        if self.is_empty() {
+            *self = text;
+            return;
+        }
+        // text that continues the borrowed one in the source is borrowed as well
+        if let (Cow::Borrowed(head), Cow::Borrowed(tail)) = (&*self, &text) {
+            let start = (head.as_ptr() as usize).wrapping_sub(source.as_ptr() as usize);
+            let end = start + head.len();
+            if source
+                .get(end..)
+                .is_some_and(|rest| rest.as_ptr() == tail.as_ptr())
+            {
+                *self = Cow::Borrowed(&source[start..end + tail.len()]);
+                return;
+            }
+        }
+        self.to_mut().push_str(&text);
+    }
+
+    fn as_str(&self) -> &str {
+        self
+    }
+
+    fn trim(self, trim: impl FnOnce(&str) -> &str) -> Self {
+        match self {
+            Cow::Borrowed(text) => Cow::Borrowed(trim(text)),
+            Cow::Owned(text) => Cow::Owned(trim(&text).to_owned()),
+        }
+    }
+}
+
+enum Frame<S> {
     Yamd {
-        metadata: Option<String>,
-        body: Vec<YamdNodes>,
+        metadata: Option<S>,
+        metadata_delimiter: MetadataDelimiter,
+        body: Vec<YamdNodes<S>>,
     },
     Document {
-        children: Vec<YamdNodes>,
+        children: Vec<YamdNodes<S>>,
     },
     Heading {
         level: u8,
-        body: Vec<HeadingNodes>,
+        body: Vec<HeadingNodes<S>>,
     },
     Paragraph {
-        body: Vec<ParagraphNodes>,
+        body: Vec<ParagraphNodes<S>>,
     },
     Bold {
-        body: Vec<BoldNodes>,
+        body: Vec<BoldNodes<S>>,
     },
     Italic {
-        text: String,
+        text: S,
     },
     Strikethrough {
-        text: String,
+        text: S,
     },
     CodeSpan {
-        text: String,
+        text: S,
+    },
+    InlineMath {
+        text: S,
     },
     Emphasis {
-        text: String,
+        text: S,
     },
     Anchor {
-        text: String,
-        url: String,
+        text: S,
+        url: S,
     },
     Title {
-        text: String,
+        text: S,
     },
     Destination {
-        text: String,
+        text: S,
     },
     Image {
-        alt: String,
-        src: String,
+        alt: S,
+        src: S,
     },
     Images {
-        images: Vec<Image>,
+        images: Vec<Image<S>>,
     },
     Code {
-        lang: String,
-        code: String,
+        lang: S,
+        code: S,
+    },
+    DisplayMath {
+        body: S,
     },
     Modifier {
-        text: String,
+        text: S,
     },
     Embed {
-        values: Vec<String>,
+        values: Vec<S>,
+    },
+    Custom {
+        start: S,
+        values: Vec<S>,
+        nodes: Vec<YamdNodes<S>>,
     },
     ThematicBreak,
     Highlight {
-        title: Option<String>,
-        icon: Option<String>,
-        paragraphs: Vec<Paragraph>,
+        title: Option<S>,
+        icon: Option<S>,
+        paragraphs: Vec<Paragraph<S>>,
     },
     Icon {
-        text: String,
+        text: S,
     },
     Collapsible {
-        title: String,
-        body: Vec<YamdNodes>,
+        title: S,
+        body: Vec<YamdNodes<S>>,
     },
     UnorderedList {
         level: usize,
-        items: Vec<ListItem>,
+        items: Vec<ListItem<S>>,
     },
     OrderedList {
         level: usize,
-        items: Vec<ListItem>,
+        items: Vec<ListItem<S>>,
     },
     ListItem {
-        text: Vec<ParagraphNodes>,
-        nested_list: Option<List>,
+        checked: Option<bool>,
+        text: Vec<ParagraphNodes<S>>,
+        nested_list: Option<List<S>>,
     },
     Metadata {
-        text: String,
+        delimiter: MetadataDelimiter,
+        text: S,
+    },
+    Table {
+        header: Option<TableRow<S>>,
+        alignments: Option<Vec<TableAlignment>>,
+        body: Vec<TableRow<S>>,
+    },
+    TableRow {
+        cells: Vec<TableCell<S>>,
+    },
+    TableCell {
+        body: Vec<ParagraphNodes<S>>,
+    },
+    FootnoteReference {
+        label: S,
+    },
+    FootnoteDefinition {
+        label: S,
+        body: Vec<ParagraphNodes<S>>,
     },
 }
 
-impl Frame {
+impl<S: Default> Frame<S> {
     fn from_node(node: &Node) -> Self {
         match node {
             Node::Document => Frame::Document {
@@ -113,40 +217,28 @@ impl Frame {
             },
             Node::Paragraph => Frame::Paragraph { body: Vec::new() },
             Node::Bold => Frame::Bold { body: Vec::new() },
-            Node::Italic => Frame::Italic {
-                text: String::new(),
-            },
-            Node::Strikethrough => Frame::Strikethrough {
-                text: String::new(),
-            },
-            Node::CodeSpan => Frame::CodeSpan {
-                text: String::new(),
-            },
-            Node::Emphasis => Frame::Emphasis {
-                text: String::new(),
-            },
+            Node::Italic => Frame::Italic { text: S::default() },
+            Node::Strikethrough => Frame::Strikethrough { text: S::default() },
+            Node::CodeSpan => Frame::CodeSpan { text: S::default() },
+            Node::InlineMath => Frame::InlineMath { text: S::default() },
+            Node::Emphasis => Frame::Emphasis { text: S::default() },
             Node::Anchor => Frame::Anchor {
-                text: String::new(),
-                url: String::new(),
-            },
-            Node::Title => Frame::Title {
-                text: String::new(),
-            },
-            Node::Destination => Frame::Destination {
-                text: String::new(),
+                text: S::default(),
+                url: S::default(),
             },
+            Node::Title => Frame::Title { text: S::default() },
+            Node::Destination => Frame::Destination { text: S::default() },
             Node::Image => Frame::Image {
-                alt: String::new(),
-                src: String::new(),
+                alt: S::default(),
+                src: S::default(),
             },
             Node::Images => Frame::Images { images: Vec::new() },
             Node::Code => Frame::Code {
-                lang: String::new(),
-                code: String::new(),
-            },
-            Node::Modifier => Frame::Modifier {
-                text: String::new(),
+                lang: S::default(),
+                code: S::default(),
             },
+            Node::DisplayMath => Frame::DisplayMath { body: S::default() },
+            Node::Modifier => Frame::Modifier { text: S::default() },
             Node::Embed => Frame::Embed { values: Vec::new() },
             Node::ThematicBreak => Frame::ThematicBreak,
             Node::Highlight => Frame::Highlight {
@@ -154,29 +246,43 @@ impl Frame {
                 icon: None,
                 paragraphs: Vec::new(),
             },
-            Node::Icon => Frame::Icon {
-                text: String::new(),
-            },
+            Node::Icon => Frame::Icon { text: S::default() },
             Node::Collapsible => Frame::Collapsible {
-                title: String::new(),
+                title: S::default(),
                 body: Vec::new(),
             },
             Node::ListItem => Frame::ListItem {
+                checked: None,
                 text: Vec::new(),
                 nested_list: None,
             },
-            Node::Metadata => Frame::Metadata {
-                text: String::new(),
+            Node::Table => Frame::Table {
+                header: None,
+                alignments: None,
+                body: Vec::new(),
+            },
+            Node::TableRow => Frame::TableRow { cells: Vec::new() },
+            Node::TableCell => Frame::TableCell { body: Vec::new() },
+            Node::FootnoteReference => Frame::FootnoteReference {
+                label: S::default(),
             },
-            Node::Heading | Node::UnorderedList | Node::OrderedList => {
-                // coverage: these three node kinds are handled by dedicated branches in the Start match; reaching this means a programmer error
+            Node::FootnoteDefinition => Frame::FootnoteDefinition {
+                label: S::default(),
+                body: Vec::new(),
+            },
+            Node::Heading
+            | Node::UnorderedList
+            | Node::OrderedList
+            | Node::Metadata
+            | Node::Custom => {
+                // coverage: these node kinds are handled by dedicated branches in the Start match; reaching this means a programmer error
                 unreachable!("use dedicated push logic for {node:?}")
             }
         }
     }
 }
 
-fn count_list_depth(stack: &[Frame]) -> usize {
+fn count_list_depth<S>(stack: &[Frame<S>]) -> usize {
     stack
         .iter()
         .filter(|f| matches!(f, Frame::UnorderedList { .. } | Frame::OrderedList { .. }))
@@ -205,8 +311,34 @@ pub fn to_yamd(ops: &[Op], source: &str) -> Yamd {
 
 /// Fallible variant of [`to_yamd`]. Returns [`UnbalancedOpStream`] if `ops` is not well-formed.
 pub fn try_to_yamd(ops: &[Op], source: &str) -> Result<Yamd, UnbalancedOpStream> {
-    let mut stack: Vec<Frame> = vec![Frame::Yamd {
+    build(ops, source)
+}
+
+/// Same as [`to_yamd`], but text of the nodes borrows from `source` unless it was assembled from
+/// several parts of it, e.g. when escape characters were removed.
+///
+/// ```
+/// use std::borrow::Cow;
+/// use yamd::nodes::{Paragraph, ParagraphNodes, YamdNodes};
+///
+/// let source = "plain text, \\*escaped\\*";
+/// let ops = yamd::parse(source);
+/// let yamd = yamd::op::to_borrowed_yamd(&ops, source);
+/// let YamdNodes::Paragraph(Paragraph { body }) = &yamd.body[0] else { unreachable!() };
+/// assert!(matches!(&body[0], ParagraphNodes::Text(Cow::Owned(text)) if text == "plain text, *escaped*"));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `ops` is not well-formed, see [`to_yamd`].
+pub fn to_borrowed_yamd<'a>(ops: &[Op], source: &'a str) -> Yamd<Cow<'a, str>> {
+    build(ops, source).expect("op stream produced by parse() is always well-formed")
+}
+
+fn build<'a, S: Text<'a>>(ops: &[Op], source: &'a str) -> Result<Yamd<S>, UnbalancedOpStream> {
+    let mut stack: Vec<Frame<S>> = vec![Frame::Yamd {
         metadata: None,
+        metadata_delimiter: MetadataDelimiter::default(),
         body: Vec::new(),
     }];
 
@@ -234,10 +366,26 @@ pub fn try_to_yamd(ops: &[Op], source: &str) -> Result<Yamd, UnbalancedOpStream>
                         items: Vec::new(),
                     });
                 }
+                Node::Metadata => {
+                    let delimiter = if op.content.as_str(source).starts_with('+') {
+                        MetadataDelimiter::Pluses
+                    } else {
+                        MetadataDelimiter::Dashes
+                    };
+                    stack.push(Frame::Metadata {
+                        delimiter,
+                        text: S::default(),
+                    });
+                }
+                Node::Custom => stack.push(Frame::Custom {
+                    start: S::new(&op.content, source),
+                    values: Vec::new(),
+                    nodes: Vec::new(),
+                }),
                 _ => stack.push(Frame::from_node(node)),
             },
             OpKind::Value => {
-                let text = op.content.to_string(source);
+                let text = S::new(&op.content, source);
                 let top = stack.last_mut().ok_or(UnbalancedOpStream)?;
                 match top {
                     Frame::Heading { body, .. } => {
@@ -249,17 +397,20 @@ pub fn try_to_yamd(ops: &[Op], source: &str) -> Result<Yamd, UnbalancedOpStream>
                     Frame::Bold { body } => {
                         body.push(BoldNodes::Text(text));
                     }
-                    Frame::Italic { text: t } => t.push_str(&text),
-                    Frame::Strikethrough { text: t } => t.push_str(&text),
-                    Frame::CodeSpan { text: t } => t.push_str(&text),
-                    Frame::Emphasis { text: t } => t.push_str(&text),
-                    Frame::Title { text: t } => t.push_str(&text),
-                    Frame::Destination { text: t } => t.push_str(&text),
-                    Frame::Modifier { text: t } => t.push_str(&text),
-                    Frame::Icon { text: t } => t.push_str(&text),
-                    Frame::Code { code, .. } => code.push_str(&text),
-                    Frame::Embed { values } => values.push(text),
-                    Frame::Metadata { text: t } => t.push_str(&text),
+                    Frame::Italic { text: t } => t.push(text, source),
+                    Frame::Strikethrough { text: t } => t.push(text, source),
+                    Frame::CodeSpan { text: t } => t.push(text, source),
+                    Frame::InlineMath { text: t } => t.push(text, source),
+                    Frame::Emphasis { text: t } => t.push(text, source),
+                    Frame::Title { text: t } => t.push(text, source),
+                    Frame::Destination { text: t } => t.push(text, source),
+                    Frame::Modifier { text: t } => t.push(text, source),
+                    Frame::Icon { text: t } => t.push(text, source),
+                    Frame::Code { code, .. } => code.push(text, source),
+                    Frame::DisplayMath { body } => body.push(text, source),
+                    Frame::Embed { values } | Frame::Custom { values, .. } => values.push(text),
+                    Frame::Metadata { text: t, .. } => t.push(text, source),
+                    Frame::FootnoteReference { label } => label.push(text, source),
                     Frame::ThematicBreak => {}
                     Frame::Highlight { .. } | Frame::Document { .. } => {}
                     // coverage: defensive catchall; every frame that accepts Value is enumerated above
@@ -275,45 +426,62 @@ pub fn try_to_yamd(ops: &[Op], source: &str) -> Result<Yamd, UnbalancedOpStream>
                             Frame::Collapsible { body, .. } => {
                                 *body = children;
                             }
+                            Frame::Custom { nodes, .. } => {
+                                *nodes = children;
+                            }
                             Frame::Yamd { body, .. } => {
                                 body.extend(children);
                             }
-                            // coverage: defensive catchall; a Document's parent is always Yamd or Collapsible under the grammar
+                            // coverage: defensive catchall; a Document's parent is always Yamd, Collapsible or Custom under the grammar
                             _ => {}
                         }
                     }
-                    (Node::Metadata, Frame::Metadata { text }) => {
-                        let metadata = text.trim_matches('\n').to_owned();
-                        if let Some(Frame::Yamd { metadata: m, .. }) = stack.last_mut() {
+                    (Node::Metadata, Frame::Metadata { delimiter, text }) => {
+                        let metadata = text.trim(|text| text.trim_matches('\n'));
+                        if let Some(Frame::Yamd {
+                            metadata: m,
+                            metadata_delimiter: d,
+                            ..
+                        }) = stack.last_mut()
+                        {
                             *m = Some(metadata);
+                            *d = delimiter;
                         }
                     }
                     (Node::Heading, Frame::Heading { level, body }) => {
-                        push_yamd_node(&mut stack, Heading::new(level, body).into());
+                        push_yamd_node(&mut stack, Heading { level, body }.into());
                     }
                     (Node::Paragraph, Frame::Paragraph { mut body }) => {
                         let top = stack.last_mut().ok_or(UnbalancedOpStream)?;
                         match top {
                             Frame::Highlight { paragraphs, .. } => {
                                 trim_trailing_newline_from_text(&mut body);
-                                paragraphs.push(Paragraph::new(body));
+                                paragraphs.push(Paragraph { body });
                             }
                             Frame::ListItem { text, .. } => {
                                 trim_trailing_newline_from_text(&mut body);
                                 *text = body;
                             }
+                            Frame::TableCell { body: cell } => {
+                                *cell = body;
+                            }
+                            Frame::FootnoteDefinition {
+                                body: definition, ..
+                            } => {
+                                *definition = body;
+                            }
                             Frame::Document { children } => {
-                                children.push(Paragraph::new(body).into());
+                                children.push(Paragraph { body }.into());
                             }
                             // coverage: defensive catchall; a Paragraph's parent is always one of the arms above
                             _ => {}
                         }
                     }
                     (Node::Bold, Frame::Bold { body }) => {
-                        push_into_paragraph(&mut stack, Bold::new(body).into());
+                        push_into_paragraph(&mut stack, Bold { body }.into());
                     }
                     (Node::Italic, Frame::Italic { text }) => {
-                        let italic = Italic::new(text);
+                        let italic = Italic(text);
                         match stack.last_mut().ok_or(UnbalancedOpStream)? {
                             Frame::Paragraph { body } => body.push(italic.into()),
                             Frame::Bold { body } => body.push(italic.into()),
@@ -322,7 +490,7 @@ pub fn try_to_yamd(ops: &[Op], source: &str) -> Result<Yamd, UnbalancedOpStream>
                         }
                     }
                     (Node::Strikethrough, Frame::Strikethrough { text }) => {
-                        let st = Strikethrough::new(text);
+                        let st = Strikethrough(text);
                         match stack.last_mut().ok_or(UnbalancedOpStream)? {
                             Frame::Paragraph { body } => body.push(st.into()),
                             Frame::Bold { body } => body.push(st.into()),
@@ -331,17 +499,27 @@ pub fn try_to_yamd(ops: &[Op], source: &str) -> Result<Yamd, UnbalancedOpStream>
                         }
                     }
                     (Node::CodeSpan, Frame::CodeSpan { text }) => {
-                        push_into_paragraph(&mut stack, CodeSpan::new(text).into());
+                        push_into_paragraph(&mut stack, CodeSpan(text).into());
+                    }
+                    (Node::InlineMath, Frame::InlineMath { text }) => {
+                        let math = InlineMath(text);
+                        match stack.last_mut().ok_or(UnbalancedOpStream)? {
+                            Frame::Paragraph { body } => body.push(math.into()),
+                            Frame::Heading { body, .. } => body.push(math.into()),
+                            // coverage: defensive catchall; InlineMath's parent is always Paragraph or Heading
+                            _ => {}
+                        }
                     }
                     (Node::Emphasis, Frame::Emphasis { text }) => {
-                        push_into_paragraph(&mut stack, Emphasis::new(text).into());
+                        push_into_paragraph(&mut stack, Emphasis(text).into());
                     }
                     (Node::Title, Frame::Title { text }) => {
                         let top = stack.last_mut().ok_or(UnbalancedOpStream)?;
                         match top {
                             Frame::Anchor { text: t, .. } => *t = text,
                             Frame::Image { alt, .. } => *alt = text,
-                            // coverage: defensive catchall; Title's parent is always Anchor or Image
+                            Frame::FootnoteDefinition { label, .. } => *label = text,
+                            // coverage: defensive catchall; Title's parent is always Anchor, Image, or FootnoteDefinition
                             _ => {}
                         }
                     }
@@ -355,7 +533,7 @@ pub fn try_to_yamd(ops: &[Op], source: &str) -> Result<Yamd, UnbalancedOpStream>
                         }
                     }
                     (Node::Anchor, Frame::Anchor { text, url }) => {
-                        let anchor = Anchor::new(text, url);
+                        let anchor = Anchor { text, url };
                         let top = stack.last_mut().ok_or(UnbalancedOpStream)?;
                         match top {
                             Frame::Paragraph { body } => body.push(anchor.into()),
@@ -365,7 +543,7 @@ pub fn try_to_yamd(ops: &[Op], source: &str) -> Result<Yamd, UnbalancedOpStream>
                         }
                     }
                     (Node::Image, Frame::Image { alt, src }) => {
-                        let image = Image::new(alt, src);
+                        let image = Image { alt, src };
                         let top = stack.last_mut().ok_or(UnbalancedOpStream)?;
                         match top {
                             Frame::Images { images } => images.push(image),
@@ -373,11 +551,15 @@ pub fn try_to_yamd(ops: &[Op], source: &str) -> Result<Yamd, UnbalancedOpStream>
                         }
                     }
                     (Node::Images, Frame::Images { images }) => {
-                        push_yamd_node(&mut stack, Images::new(images).into());
+                        push_yamd_node(&mut stack, Images { body: images }.into());
                     }
                     (Node::Code, Frame::Code { lang, code }) => {
-                        let code = code.trim_end_matches('\n').to_owned();
-                        push_yamd_node(&mut stack, Code::new(lang, code).into());
+                        let code = code.trim(|code| code.trim_end_matches('\n'));
+                        push_yamd_node(&mut stack, Code { lang, code }.into());
+                    }
+                    (Node::DisplayMath, Frame::DisplayMath { body }) => {
+                        let body = body.trim(|body| body.trim_end_matches('\n'));
+                        push_yamd_node(&mut stack, DisplayMath { body }.into());
                     }
                     (Node::Modifier, Frame::Modifier { text }) => {
                         let top = stack.last_mut().ok_or(UnbalancedOpStream)?;
@@ -385,7 +567,11 @@ pub fn try_to_yamd(ops: &[Op], source: &str) -> Result<Yamd, UnbalancedOpStream>
                             Frame::Code { lang, .. } => *lang = text,
                             Frame::Highlight { title, .. } => *title = Some(text),
                             Frame::Collapsible { title: t, .. } => *t = text,
-                            // coverage: defensive catchall; Modifier's parent is always Code, Highlight, or Collapsible
+                            Frame::Table { alignments: a, .. } => *a = alignments(text.as_str()),
+                            Frame::ListItem { checked, .. } => {
+                                *checked = Some(text.as_str() != "[ ]")
+                            }
+                            // coverage: defensive catchall; Modifier's parent is always Code, Highlight, Collapsible, Table, or ListItem
                             _ => {}
                         }
                     }
@@ -395,9 +581,34 @@ pub fn try_to_yamd(ops: &[Op], source: &str) -> Result<Yamd, UnbalancedOpStream>
                         }
                     }
                     (Node::Embed, Frame::Embed { values }) => {
-                        let kind = values.first().cloned().unwrap_or_default();
-                        let args = values.get(2).cloned().unwrap_or_default();
-                        push_yamd_node(&mut stack, Embed::new(kind, args).into());
+                        let mut values = values.into_iter();
+                        let kind = values.next().unwrap_or_default();
+                        let args = values.nth(1).unwrap_or_default();
+                        push_yamd_node(&mut stack, Embed { kind, args }.into());
+                    }
+                    (
+                        Node::Custom,
+                        Frame::Custom {
+                            start,
+                            values,
+                            nodes,
+                        },
+                    ) => {
+                        let mut values = values.into_iter();
+                        let name = values.next().unwrap_or_default();
+                        let body = values.next().unwrap_or_default();
+                        let end = S::new(&op.content, source);
+                        push_yamd_node(
+                            &mut stack,
+                            Custom {
+                                name,
+                                start,
+                                body,
+                                end,
+                                nodes,
+                            }
+                            .into(),
+                        );
                     }
                     (Node::ThematicBreak, Frame::ThematicBreak) => {
                         push_yamd_node(&mut stack, ThematicBreak::new().into());
@@ -410,19 +621,52 @@ pub fn try_to_yamd(ops: &[Op], source: &str) -> Result<Yamd, UnbalancedOpStream>
                             paragraphs,
                         },
                     ) => {
-                        push_yamd_node(&mut stack, Highlight::new(title, icon, paragraphs).into());
+                        push_yamd_node(
+                            &mut stack,
+                            Highlight {
+                                title,
+                                icon,
+                                body: paragraphs,
+                            }
+                            .into(),
+                        );
                     }
                     (Node::Collapsible, Frame::Collapsible { title, body }) => {
-                        push_yamd_node(&mut stack, Collapsible::new(title, body).into());
+                        push_yamd_node(&mut stack, Collapsible { title, body }.into());
                     }
                     (Node::UnorderedList, Frame::UnorderedList { level, items }) => {
-                        finish_list(&mut stack, List::new(ListTypes::Unordered, level, items))?;
+                        finish_list(
+                            &mut stack,
+                            List {
+                                list_type: ListTypes::Unordered,
+                                level,
+                                body: items,
+                            },
+                        )?;
                     }
                     (Node::OrderedList, Frame::OrderedList { level, items }) => {
-                        finish_list(&mut stack, List::new(ListTypes::Ordered, level, items))?;
+                        finish_list(
+                            &mut stack,
+                            List {
+                                list_type: ListTypes::Ordered,
+                                level,
+                                body: items,
+                            },
+                        )?;
                     }
-                    (Node::ListItem, Frame::ListItem { text, nested_list }) => {
-                        let item = ListItem::new(text, nested_list);
+                    (
+                        Node::ListItem,
+                        Frame::ListItem {
+                            checked,
+                            text,
+                            nested_list,
+                        },
+                    ) => {
+                        let item = ListItem {
+                            checked,
+                            text,
+                            nested_list,
+                        };
                         if let Some(
                             Frame::UnorderedList { items, .. } | Frame::OrderedList { items, .. },
                         ) = stack.last_mut()
@@ -430,6 +674,45 @@ pub fn try_to_yamd(ops: &[Op], source: &str) -> Result<Yamd, UnbalancedOpStream>
                             items.push(item);
                         }
                     }
+                    (Node::TableCell, Frame::TableCell { body }) => {
+                        if let Some(Frame::TableRow { cells }) = stack.last_mut() {
+                            cells.push(TableCell { body });
+                        }
+                    }
+                    (Node::TableRow, Frame::TableRow { cells }) => {
+                        if let Some(Frame::Table { header, body, .. }) = stack.last_mut() {
+                            let row = TableRow { cells };
+                            match header {
+                                Some(_) => body.push(row),
+                                None => *header = Some(row),
+                            }
+                        }
+                    }
+                    (
+                        Node::Table,
+                        Frame::Table {
+                            header,
+                            alignments,
+                            body,
+                        },
+                    ) => {
+                        let header = header.unwrap_or_else(|| TableRow { cells: Vec::new() });
+                        push_yamd_node(
+                            &mut stack,
+                            Table {
+                                header,
+                                alignments: alignments.unwrap_or_default(),
+                                body,
+                            }
+                            .into(),
+                        );
+                    }
+                    (Node::FootnoteReference, Frame::FootnoteReference { label }) => {
+                        push_into_paragraph(&mut stack, FootnoteReference { label }.into());
+                    }
+                    (Node::FootnoteDefinition, Frame::FootnoteDefinition { label, body }) => {
+                        push_yamd_node(&mut stack, FootnoteDefinition { label, body }.into());
+                    }
                     // coverage: defensive catchall for any (Node, Frame) mismatch; indicates a programmer or parser error
                     _ => {}
                 }
@@ -438,7 +721,15 @@ pub fn try_to_yamd(ops: &[Op], source: &str) -> Result<Yamd, UnbalancedOpStream>
     }
 
     match stack.pop().ok_or(UnbalancedOpStream)? {
-        Frame::Yamd { metadata, body } => Ok(Yamd::new(metadata, body)),
+        Frame::Yamd {
+            metadata,
+            metadata_delimiter,
+            body,
+        } => Ok(Yamd {
+            metadata,
+            metadata_delimiter,
+            body,
+        }),
         // coverage: the root frame is invariantly Yamd; reaching this means stack corruption
         _ => Err(UnbalancedOpStream),
     }
@@ -449,30 +740,30 @@ fn extract_heading_level(content: &Content, source: &str) -> u8 {
     s.chars().take_while(|&c| c == '#').count() as u8
 }
 
-fn trim_trailing_newline_from_text(body: &mut Vec<ParagraphNodes>) {
+fn trim_trailing_newline_from_text<'a, S: Text<'a>>(body: &mut Vec<ParagraphNodes<S>>) {
     if let Some(ParagraphNodes::Text(t)) = body.last_mut() {
-        let trimmed = t.trim_end_matches('\n');
-        if trimmed.is_empty() {
+        let trimmed = std::mem::take(t).trim(|t| t.trim_end_matches('\n'));
+        if trimmed.as_str().is_empty() {
             body.pop();
         } else {
-            *t = trimmed.to_owned();
+            *t = trimmed;
         }
     }
 }
 
-fn push_yamd_node(stack: &mut [Frame], node: YamdNodes) {
+fn push_yamd_node<S>(stack: &mut [Frame<S>], node: YamdNodes<S>) {
     if let Some(Frame::Document { children }) = stack.last_mut() {
         children.push(node);
     }
 }
 
-fn push_into_paragraph(stack: &mut [Frame], node: ParagraphNodes) {
+fn push_into_paragraph<S>(stack: &mut [Frame<S>], node: ParagraphNodes<S>) {
     if let Some(Frame::Paragraph { body }) = stack.last_mut() {
         body.push(node);
     }
 }
 
-fn finish_list(stack: &mut [Frame], list: List) -> Result<(), UnbalancedOpStream> {
+fn finish_list<S>(stack: &mut [Frame<S>], list: List<S>) -> Result<(), UnbalancedOpStream> {
     match stack.last_mut().ok_or(UnbalancedOpStream)? {
         Frame::ListItem { nested_list, .. } => *nested_list = Some(list),
         _ => push_yamd_node(stack, list.into()),
@@ -482,10 +773,12 @@ fn finish_list(stack: &mut [Frame], list: List) -> Result<(), UnbalancedOpStream
 
 #[cfg(test)]
 mod tests {
+    use std::borrow::Cow;
+
     use pretty_assertions::assert_eq;
 
     use crate::nodes::*;
-    use crate::op::{Node, Op, UnbalancedOpStream, parse, to_yamd, try_to_yamd};
+    use crate::op::{Node, Op, UnbalancedOpStream, parse, to_borrowed_yamd, to_yamd, try_to_yamd};
 
     #[test]
     fn single_paragraph() {
@@ -631,6 +924,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn task_list() {
+        let input = "- [x] one\n + [ ] two\n- [x]three";
+        let ops = parse(input);
+        let result = to_yamd(&ops, input);
+        assert_eq!(
+            result,
+            Yamd::new(
+                None,
+                vec![
+                    List::new(
+                        ListTypes::Unordered,
+                        0,
+                        vec![
+                            ListItem::task(
+                                true,
+                                vec![String::from("one").into()],
+                                Some(List::new(
+                                    ListTypes::Ordered,
+                                    1,
+                                    vec![ListItem::task(
+                                        false,
+                                        vec![String::from("two").into()],
+                                        None
+                                    )]
+                                ))
+                            ),
+                            ListItem::new(vec![String::from("[x]three").into()], None)
+                        ]
+                    )
+                    .into()
+                ]
+            )
+        );
+    }
+
     #[test]
     fn three_level_nested_list() {
         let input = "- L0\n - L1\n  - L2";
@@ -710,6 +1039,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn metadata_with_pluses() {
+        let input = "+++\ntitle = 'test'\n+++\n\nhello";
+        let ops = parse(input);
+        let result = to_yamd(&ops, input);
+        assert_eq!(
+            result,
+            Yamd {
+                metadata_delimiter: MetadataDelimiter::Pluses,
+                ..Yamd::new(
+                    Some(String::from("title = 'test'")),
+                    vec![Paragraph::new(vec![String::from("hello").into()]).into()]
+                )
+            }
+        );
+        assert_eq!(result.to_string(), input);
+    }
+
     #[test]
     fn code_span_and_emphasis() {
         let input = "`code` *em*";
@@ -1020,6 +1367,84 @@ end"#;
         );
     }
 
+    #[test]
+    fn table() {
+        let input = "| a | **b** |\n| --- | :-: |\n|c|  |\n\nd";
+        let ops = parse(input);
+        let result = to_yamd(&ops, input);
+        assert_eq!(
+            result,
+            Yamd::new(
+                None,
+                vec![
+                    Table::new(
+                        TableRow::new(vec![
+                            TableCell::new(vec![String::from("a").into()]),
+                            TableCell::new(vec![Bold::new(vec![String::from("b").into()]).into()]),
+                        ]),
+                        vec![TableAlignment::None, TableAlignment::Center],
+                        vec![TableRow::new(vec![
+                            TableCell::new(vec![String::from("c").into()]),
+                            TableCell::new(vec![]),
+                        ])],
+                    )
+                    .into(),
+                    Paragraph::new(vec![String::from("d").into()]).into(),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn footnotes() {
+        let input = "a[^1]\n\n[^1]: \\*b [^2]";
+        let ops = parse(input);
+        let result = to_yamd(&ops, input);
+        assert_eq!(
+            result,
+            Yamd::new(
+                None,
+                vec![
+                    Paragraph::new(vec![
+                        String::from("a").into(),
+                        FootnoteReference::new("1").into(),
+                    ])
+                    .into(),
+                    FootnoteDefinition::new(
+                        "1",
+                        vec![
+                            String::from("*b ").into(),
+                            FootnoteReference::new("2").into()
+                        ]
+                    )
+                    .into(),
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn math() {
+        let input = "# $a$\n\n\\$b $\\$c$\n\n$$\n\\d\n$$";
+        let ops = parse(input);
+        let result = to_yamd(&ops, input);
+        assert_eq!(
+            result,
+            Yamd::new(
+                None,
+                vec![
+                    Heading::new(1, vec![InlineMath::new("a").into()]).into(),
+                    Paragraph::new(vec![
+                        String::from("$b ").into(),
+                        InlineMath::new("\\$c").into(),
+                    ])
+                    .into(),
+                    DisplayMath::new("\\d").into(),
+                ]
+            )
+        );
+    }
+
     #[test]
     fn empty_input() {
         let input = "";
@@ -1059,4 +1484,27 @@ end"#;
         let ops = vec![Op::new_start(Node::Heading, &[] as &[crate::lexer::Token])];
         assert_eq!(try_to_yamd(&ops, ""), Err(UnbalancedOpStream));
     }
+
+    #[test]
+    fn borrowed_text() {
+        let input = "```rust\na\n\nb\n```\n\n{{youtube|id}}\n\ntext \\_b_";
+        let ops = parse(input);
+        let yamd = to_borrowed_yamd(&ops, input);
+        assert_eq!(format!("{yamd:?}"), format!("{:?}", to_yamd(&ops, input)));
+        let [
+            YamdNodes::Code(code),
+            YamdNodes::Embed(embed),
+            YamdNodes::Paragraph(paragraph),
+        ] = &yamd.body[..]
+        else {
+            unreachable!()
+        };
+        assert!(matches!(code.lang, Cow::Borrowed("rust")));
+        assert!(matches!(code.code, Cow::Borrowed("a\n\nb")));
+        assert!(matches!(embed.args, Cow::Borrowed("id")));
+        assert!(matches!(
+            &paragraph.body[0],
+            ParagraphNodes::Text(Cow::Owned(_))
+        ));
+    }
 }
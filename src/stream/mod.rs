@@ -0,0 +1,336 @@
+//! Parsing a document that arrives in parts.
+//!
+//! [Stream] takes text in chunks of any size and returns top level blocks as soon as they are
+//! final, so rendering can start before the whole document is received. [from_reader] does the
+//! same for any [Read], including [BufRead](std::io::BufRead).
+//!
+//! A block is final when text appended after it can't change how it is parsed. Usually that is
+//! the [Terminator](crate::lexer::TokenKind::Terminator) after it, but a block that can contain
+//! terminators, like [Code](crate::nodes::Code) or [Collapsible](crate::nodes::Collapsible), is
+//! final only after its closing delimiter, and an unclosed one is final only at the end of input.
+//! Blocks are the same as [deserialize](crate::deserialize) would produce for the whole document.
+//!
+//! ```
+//! use yamd::{
+//!     nodes::{Heading, Paragraph},
+//!     stream::{Block, Stream},
+//! };
+//!
+//! let mut stream = Stream::new();
+//! assert_eq!(stream.push("# Hel"), vec![]);
+//! assert_eq!(
+//!     stream.push("lo\n\nA para"),
+//!     vec![Block::Node(Heading::new(1, vec![String::from("Hello").into()]).into())]
+//! );
+//! assert_eq!(stream.push("graph"), vec![]);
+//! assert_eq!(
+//!     stream.finish(),
+//!     vec![Block::Node(Paragraph::new(vec![String::from("A paragraph").into()]).into())]
+//! );
+//! ```
+
+use std::io::{self, Read};
+
+use crate::{
+    nodes::{MetadataDelimiter, Yamd, YamdNodes},
+    op::{parse_final, to_yamd},
+};
+
+/// Part of a document returned by [Stream] and [from_reader].
+#[derive(Debug, PartialEq, Clone)]
+pub enum Block {
+    /// [Metadata](Yamd::metadata) of the document, always returned before the first node.
+    Metadata {
+        metadata: String,
+        delimiter: MetadataDelimiter,
+    },
+    /// Top level node of the document.
+    Node(YamdNodes),
+}
+
+/// Incremental parser that takes a document in chunks, see [stream](crate::stream).
+#[derive(Debug)]
+pub struct Stream {
+    /// Text that is not parsed into final blocks yet.
+    buffer: String,
+    /// `true` until the first block is returned, metadata can only be at the start.
+    at_start: bool,
+}
+
+impl Stream {
+    pub fn new() -> Self {
+        Self {
+            buffer: String::new(),
+            at_start: true,
+        }
+    }
+
+    /// Appends `chunk` to the document and returns blocks that became final.
+    ///
+    /// Pending text is parsed again when `chunk` completes a
+    /// [Terminator](crate::lexer::TokenKind::Terminator), as no block becomes final without one.
+    pub fn push(&mut self, chunk: &str) -> Vec<Block> {
+        // a terminator can start in the previous chunk, `\r\n\r\n` is the longest one
+        let from = self.buffer.len().saturating_sub(3);
+        self.buffer.push_str(chunk);
+        let tail = &self.buffer.as_bytes()[from..];
+        let terminated =
+            tail.windows(2).any(|w| w == b"\n\n") || tail.windows(3).any(|w| w == b"\n\r\n");
+        if !terminated {
+            return vec![];
+        }
+        self.parse(false)
+    }
+
+    /// Ends the document and returns the remaining blocks.
+    pub fn finish(mut self) -> Vec<Block> {
+        self.parse(true)
+    }
+
+    fn parse(&mut self, complete: bool) -> Vec<Block> {
+        // `\r` can turn the line end before it into a terminator once `\n` arrives
+        let text = match self.buffer.strip_suffix('\r') {
+            Some(text) if !complete => text,
+            _ => &self.buffer,
+        };
+        let Some((ops, len)) = parse_final(text, self.at_start, complete) else {
+            return vec![];
+        };
+        let yamd = to_yamd(&ops, text);
+        self.buffer.drain(..len);
+        self.at_start = false;
+        let metadata = yamd.metadata.map(|metadata| Block::Metadata {
+            metadata,
+            delimiter: yamd.metadata_delimiter,
+        });
+        metadata
+            .into_iter()
+            .chain(yamd.body.into_iter().map(Block::Node))
+            .collect()
+    }
+}
+
+impl Default for Stream {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads a document from `reader` and returns an iterator over its blocks, see
+/// [stream](crate::stream). The input must be UTF-8.
+///
+/// ```
+/// use yamd::stream::{Block, from_reader};
+///
+/// let input = "---\ntitle: Yamd\n---\n\n# Hello\n\nworld";
+/// let blocks = from_reader(input.as_bytes()).collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(blocks.len(), 3);
+/// assert!(matches!(&blocks[0], Block::Metadata { metadata, .. } if metadata == "title: Yamd"));
+/// ```
+pub fn from_reader<R: Read>(reader: R) -> Blocks<R> {
+    Blocks {
+        reader,
+        stream: Some(Stream::new()),
+        bytes: Vec::new(),
+        pending: Vec::new().into_iter(),
+    }
+}
+
+/// Reads a whole document from `reader`, see [from_reader].
+///
+/// ```
+/// let yamd = yamd::stream::deserialize_reader("# Hello".as_bytes()).unwrap();
+/// assert_eq!(yamd, yamd::deserialize("# Hello"));
+/// ```
+pub fn deserialize_reader<R: Read>(reader: R) -> io::Result<Yamd> {
+    let mut yamd = Yamd::default();
+    for block in from_reader(reader) {
+        match block? {
+            Block::Metadata {
+                metadata,
+                delimiter,
+            } => {
+                yamd.metadata = Some(metadata);
+                yamd.metadata_delimiter = delimiter;
+            }
+            Block::Node(node) => yamd.body.push(node),
+        }
+    }
+    Ok(yamd)
+}
+
+/// Iterator over blocks of a document read from [Read], see [from_reader].
+///
+/// Returns an error and stops if reading fails or the input is not UTF-8.
+pub struct Blocks<R> {
+    reader: R,
+    /// `None` after the end of input or an error.
+    stream: Option<Stream>,
+    /// Bytes that are not decoded yet, the beginning of a split character.
+    bytes: Vec<u8>,
+    pending: std::vec::IntoIter<Block>,
+}
+
+impl<R: Read> Blocks<R> {
+    fn read(&mut self) -> io::Result<Vec<Block>> {
+        let Some(stream) = &mut self.stream else {
+            return Ok(vec![]);
+        };
+        let mut buf = [0; 8 * 1024];
+        let read = match self.reader.read(&mut buf) {
+            Ok(read) => read,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => return Ok(vec![]),
+            Err(error) => return Err(error),
+        };
+        if read == 0 {
+            if !self.bytes.is_empty() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "stream did not contain valid UTF-8",
+                ));
+            }
+            return Ok(self.stream.take().map(Stream::finish).unwrap_or_default());
+        }
+        self.bytes.extend_from_slice(&buf[..read]);
+        let valid = match std::str::from_utf8(&self.bytes) {
+            Ok(text) => text.len(),
+            Err(error) if error.error_len().is_none() => error.valid_up_to(),
+            Err(error) => return Err(io::Error::new(io::ErrorKind::InvalidData, error)),
+        };
+        // `valid` bytes were just checked
+        let text = std::str::from_utf8(&self.bytes[..valid]).unwrap_or_default();
+        let blocks = stream.push(text);
+        self.bytes.drain(..valid);
+        Ok(blocks)
+    }
+}
+
+impl<R: Read> Iterator for Blocks<R> {
+    type Item = io::Result<Block>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(block) = self.pending.next() {
+                return Some(Ok(block));
+            }
+            self.stream.as_ref()?;
+            match self.read() {
+                Ok(blocks) => self.pending = blocks.into_iter(),
+                Err(error) => {
+                    self.stream = None;
+                    return Some(Err(error));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        deserialize,
+        nodes::{Code, Heading, Paragraph},
+    };
+
+    fn paragraph(text: &str) -> Block {
+        Block::Node(Paragraph::new(vec![text.to_string().into()]).into())
+    }
+
+    #[test]
+    fn blocks_are_returned_when_final() {
+        let mut stream = Stream::new();
+        assert_eq!(stream.push("a\n"), vec![]);
+        assert_eq!(stream.push("\n"), vec![]);
+        assert_eq!(stream.push("b\n\n"), vec![paragraph("a")]);
+        assert_eq!(stream.push("```\nc\n\n"), vec![paragraph("b")]);
+        assert_eq!(stream.push("d\n```\n\n"), vec![]);
+        assert_eq!(
+            stream.push("e\n\n"),
+            vec![Block::Node(Code::new("", "c\n\nd").into())]
+        );
+        assert_eq!(stream.finish(), vec![paragraph("e")]);
+
+        let mut stream = Stream::new();
+        assert_eq!(stream.push("a\n\nb\n\r"), vec![paragraph("a")]);
+        assert_eq!(stream.push("\nc\n\n"), vec![paragraph("b")]);
+    }
+
+    #[test]
+    fn closing_chunk_returns_a_large_block() {
+        let mut stream = Stream::new();
+        assert_eq!(stream.push("```\n"), vec![]);
+        for _ in 0..100 {
+            assert_eq!(stream.push("let a = 1;\n\n"), vec![]);
+        }
+        let code = Code::new(String::new(), "let a = 1;\n\n".repeat(100) + "a");
+        assert_eq!(stream.push("a\n```\n\nb"), vec![Block::Node(code.into())]);
+        assert_eq!(stream.finish(), vec![paragraph("b")]);
+    }
+
+    #[test]
+    fn unclosed_block_is_final_at_the_end() {
+        let mut stream = Stream::new();
+        assert_eq!(stream.push("```\na\n\nb\n\n"), vec![]);
+        assert_eq!(stream.finish(), vec![paragraph("```\na"), paragraph("b")]);
+    }
+
+    #[test]
+    fn metadata() {
+        let mut stream = Stream::new();
+        assert_eq!(stream.push("+++\na = 1\n\n"), vec![]);
+        assert_eq!(
+            stream.push("+++\n\n# b\n\nc"),
+            vec![
+                Block::Metadata {
+                    metadata: "a = 1".to_string(),
+                    delimiter: MetadataDelimiter::Pluses
+                },
+                Block::Node(Heading::new(1, vec![String::from("b").into()]).into())
+            ]
+        );
+        assert_eq!(stream.finish(), vec![paragraph("c")]);
+
+        let mut stream = Stream::default();
+        assert_eq!(
+            stream.push("---\na: b\n---\n\nc\n\nd"),
+            vec![
+                Block::Metadata {
+                    metadata: "a: b".to_string(),
+                    delimiter: MetadataDelimiter::Dashes
+                },
+                paragraph("c")
+            ]
+        );
+
+        // metadata is only at the start of the document
+        let input = "a\n\n---\nb\n---\n\nc";
+        let mut stream = Stream::new();
+        let mut blocks = stream.push(input);
+        blocks.extend(stream.finish());
+        let nodes: Vec<Block> = deserialize(input)
+            .body
+            .into_iter()
+            .map(Block::Node)
+            .collect();
+        assert_eq!(blocks, nodes);
+    }
+
+    #[test]
+    fn reader() {
+        let input = "---\na: b\n---\n\n# 🤔\n\n{% c\n\nd\n%}\n\n";
+        let yamd = deserialize_reader(input.as_bytes()).unwrap();
+        assert_eq!(yamd, deserialize(input));
+
+        // split character
+        let reader = "🤔".as_bytes().chain("\n\na".as_bytes());
+        assert_eq!(deserialize_reader(reader).unwrap(), deserialize("🤔\n\na"));
+
+        let error = deserialize_reader(&b"a\xF0\x9F"[..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        let error = deserialize_reader(&b"\xFF"[..]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
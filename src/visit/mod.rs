@@ -0,0 +1,323 @@
+//! Traversal of the [`Yamd`] AST.
+//!
+//! [`Visit`] walks the tree by shared reference, [`VisitMut`] by mutable reference. Each trait has
+//! one `visit_*` method per node. Default implementations of container nodes call the matching
+//! `walk_*` function which visits the children, so an implementation overrides only the nodes it
+//! cares about and calls `walk_*` to keep descending.
+//!
+//! Example:
+//!
+//! ```rust
+//! use yamd::{
+//!     deserialize,
+//!     nodes::Heading,
+//!     visit::{Visit, walk_heading},
+//! };
+//!
+//! #[derive(Default)]
+//! struct Headings(usize);
+//!
+//! impl Visit for Headings {
+//!     fn visit_heading(&mut self, heading: &Heading) {
+//!         self.0 += 1;
+//!         walk_heading(self, heading);
+//!     }
+//! }
+//!
+//! let yamd = deserialize("# one\n\n{% collapsible\n## two\n%}");
+//! let mut headings = Headings::default();
+//! headings.visit_yamd(&yamd);
+//! assert_eq!(headings.0, 2);
+//! ```
+
+mod visit_mut;
+
+pub use visit_mut::*;
+
+use crate::nodes::{
+    Anchor, Bold, BoldNodes, Code, CodeSpan, Collapsible, Custom, DisplayMath, Embed, Emphasis,
+    FootnoteDefinition, FootnoteReference, Heading, HeadingNodes, Highlight, Image, Images,
+    InlineMath, Italic, List, ListItem, Paragraph, ParagraphNodes, Strikethrough, Table, TableCell,
+    TableRow, ThematicBreak, Yamd, YamdNodes,
+};
+
+/// Visits nodes by shared reference.
+pub trait Visit {
+    fn visit_yamd(&mut self, yamd: &Yamd) {
+        walk_yamd(self, yamd);
+    }
+
+    fn visit_yamd_node(&mut self, node: &YamdNodes) {
+        walk_yamd_node(self, node);
+    }
+
+    fn visit_paragraph(&mut self, paragraph: &Paragraph) {
+        walk_paragraph(self, paragraph);
+    }
+
+    fn visit_paragraph_node(&mut self, node: &ParagraphNodes) {
+        walk_paragraph_node(self, node);
+    }
+
+    fn visit_heading(&mut self, heading: &Heading) {
+        walk_heading(self, heading);
+    }
+
+    fn visit_heading_node(&mut self, node: &HeadingNodes) {
+        walk_heading_node(self, node);
+    }
+
+    fn visit_bold(&mut self, bold: &Bold) {
+        walk_bold(self, bold);
+    }
+
+    fn visit_bold_node(&mut self, node: &BoldNodes) {
+        walk_bold_node(self, node);
+    }
+
+    /// Visits plain text of [ParagraphNodes], [HeadingNodes] and [BoldNodes].
+    fn visit_text(&mut self, _text: &str) {}
+
+    fn visit_anchor(&mut self, _anchor: &Anchor) {}
+
+    fn visit_italic(&mut self, _italic: &Italic) {}
+
+    fn visit_strikethrough(&mut self, _strikethrough: &Strikethrough) {}
+
+    fn visit_emphasis(&mut self, _emphasis: &Emphasis) {}
+
+    fn visit_code_span(&mut self, _code_span: &CodeSpan) {}
+
+    fn visit_image(&mut self, _image: &Image) {}
+
+    fn visit_images(&mut self, images: &Images) {
+        walk_images(self, images);
+    }
+
+    fn visit_code(&mut self, _code: &Code) {}
+
+    fn visit_list(&mut self, list: &List) {
+        walk_list(self, list);
+    }
+
+    fn visit_list_item(&mut self, list_item: &ListItem) {
+        walk_list_item(self, list_item);
+    }
+
+    fn visit_highlight(&mut self, highlight: &Highlight) {
+        walk_highlight(self, highlight);
+    }
+
+    fn visit_thematic_break(&mut self, _thematic_break: &ThematicBreak) {}
+
+    fn visit_embed(&mut self, _embed: &Embed) {}
+
+    fn visit_custom(&mut self, custom: &Custom) {
+        walk_custom(self, custom);
+    }
+
+    fn visit_collapsible(&mut self, collapsible: &Collapsible) {
+        walk_collapsible(self, collapsible);
+    }
+
+    fn visit_table(&mut self, table: &Table) {
+        walk_table(self, table);
+    }
+
+    fn visit_table_row(&mut self, table_row: &TableRow) {
+        walk_table_row(self, table_row);
+    }
+
+    fn visit_table_cell(&mut self, table_cell: &TableCell) {
+        walk_table_cell(self, table_cell);
+    }
+
+    fn visit_footnote_reference(&mut self, _footnote_reference: &FootnoteReference) {}
+
+    fn visit_footnote_definition(&mut self, footnote_definition: &FootnoteDefinition) {
+        walk_footnote_definition(self, footnote_definition);
+    }
+
+    fn visit_inline_math(&mut self, _inline_math: &InlineMath) {}
+
+    fn visit_display_math(&mut self, _display_math: &DisplayMath) {}
+}
+
+pub fn walk_yamd<V: Visit + ?Sized>(v: &mut V, yamd: &Yamd) {
+    yamd.body.iter().for_each(|node| v.visit_yamd_node(node));
+}
+
+pub fn walk_yamd_node<V: Visit + ?Sized>(v: &mut V, node: &YamdNodes) {
+    match node {
+        YamdNodes::Paragraph(node) => v.visit_paragraph(node),
+        YamdNodes::Heading(node) => v.visit_heading(node),
+        YamdNodes::Image(node) => v.visit_image(node),
+        YamdNodes::Images(node) => v.visit_images(node),
+        YamdNodes::Code(node) => v.visit_code(node),
+        YamdNodes::List(node) => v.visit_list(node),
+        YamdNodes::Highlight(node) => v.visit_highlight(node),
+        YamdNodes::ThematicBreak(node) => v.visit_thematic_break(node),
+        YamdNodes::Embed(node) => v.visit_embed(node),
+        YamdNodes::Collapsible(node) => v.visit_collapsible(node),
+        YamdNodes::Table(node) => v.visit_table(node),
+        YamdNodes::FootnoteDefinition(node) => v.visit_footnote_definition(node),
+        YamdNodes::DisplayMath(node) => v.visit_display_math(node),
+        YamdNodes::Custom(node) => v.visit_custom(node),
+    }
+}
+
+pub fn walk_paragraph<V: Visit + ?Sized>(v: &mut V, paragraph: &Paragraph) {
+    paragraph
+        .body
+        .iter()
+        .for_each(|node| v.visit_paragraph_node(node));
+}
+
+pub fn walk_paragraph_node<V: Visit + ?Sized>(v: &mut V, node: &ParagraphNodes) {
+    match node {
+        ParagraphNodes::Anchor(node) => v.visit_anchor(node),
+        ParagraphNodes::Bold(node) => v.visit_bold(node),
+        ParagraphNodes::Italic(node) => v.visit_italic(node),
+        ParagraphNodes::Strikethrough(node) => v.visit_strikethrough(node),
+        ParagraphNodes::Text(text) => v.visit_text(text),
+        ParagraphNodes::CodeSpan(node) => v.visit_code_span(node),
+        ParagraphNodes::Emphasis(node) => v.visit_emphasis(node),
+        ParagraphNodes::FootnoteReference(node) => v.visit_footnote_reference(node),
+        ParagraphNodes::InlineMath(node) => v.visit_inline_math(node),
+    }
+}
+
+pub fn walk_heading<V: Visit + ?Sized>(v: &mut V, heading: &Heading) {
+    heading
+        .body
+        .iter()
+        .for_each(|node| v.visit_heading_node(node));
+}
+
+pub fn walk_heading_node<V: Visit + ?Sized>(v: &mut V, node: &HeadingNodes) {
+    match node {
+        HeadingNodes::Text(text) => v.visit_text(text),
+        HeadingNodes::Anchor(node) => v.visit_anchor(node),
+        HeadingNodes::InlineMath(node) => v.visit_inline_math(node),
+    }
+}
+
+pub fn walk_bold<V: Visit + ?Sized>(v: &mut V, bold: &Bold) {
+    bold.body.iter().for_each(|node| v.visit_bold_node(node));
+}
+
+pub fn walk_bold_node<V: Visit + ?Sized>(v: &mut V, node: &BoldNodes) {
+    match node {
+        BoldNodes::Italic(node) => v.visit_italic(node),
+        BoldNodes::Strikethrough(node) => v.visit_strikethrough(node),
+        BoldNodes::Text(text) => v.visit_text(text),
+    }
+}
+
+pub fn walk_images<V: Visit + ?Sized>(v: &mut V, images: &Images) {
+    images.body.iter().for_each(|node| v.visit_image(node));
+}
+
+pub fn walk_list<V: Visit + ?Sized>(v: &mut V, list: &List) {
+    list.body.iter().for_each(|node| v.visit_list_item(node));
+}
+
+pub fn walk_list_item<V: Visit + ?Sized>(v: &mut V, list_item: &ListItem) {
+    list_item
+        .text
+        .iter()
+        .for_each(|node| v.visit_paragraph_node(node));
+    if let Some(nested_list) = &list_item.nested_list {
+        v.visit_list(nested_list);
+    }
+}
+
+pub fn walk_highlight<V: Visit + ?Sized>(v: &mut V, highlight: &Highlight) {
+    highlight
+        .body
+        .iter()
+        .for_each(|node| v.visit_paragraph(node));
+}
+
+pub fn walk_collapsible<V: Visit + ?Sized>(v: &mut V, collapsible: &Collapsible) {
+    collapsible
+        .body
+        .iter()
+        .for_each(|node| v.visit_yamd_node(node));
+}
+
+pub fn walk_custom<V: Visit + ?Sized>(v: &mut V, custom: &Custom) {
+    custom.nodes.iter().for_each(|node| v.visit_yamd_node(node));
+}
+
+/// Visits the header row, then body rows.
+pub fn walk_table<V: Visit + ?Sized>(v: &mut V, table: &Table) {
+    v.visit_table_row(&table.header);
+    table.body.iter().for_each(|row| v.visit_table_row(row));
+}
+
+pub fn walk_table_row<V: Visit + ?Sized>(v: &mut V, table_row: &TableRow) {
+    table_row
+        .cells
+        .iter()
+        .for_each(|cell| v.visit_table_cell(cell));
+}
+
+pub fn walk_table_cell<V: Visit + ?Sized>(v: &mut V, table_cell: &TableCell) {
+    table_cell
+        .body
+        .iter()
+        .for_each(|node| v.visit_paragraph_node(node));
+}
+
+pub fn walk_footnote_definition<V: Visit + ?Sized>(
+    v: &mut V,
+    footnote_definition: &FootnoteDefinition,
+) {
+    footnote_definition
+        .body
+        .iter()
+        .for_each(|node| v.visit_paragraph_node(node));
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        deserialize,
+        nodes::{Anchor, Image},
+        visit::Visit,
+    };
+
+    #[derive(Default)]
+    struct Collect {
+        texts: Vec<String>,
+        urls: Vec<String>,
+    }
+
+    impl Visit for Collect {
+        fn visit_text(&mut self, text: &str) {
+            self.texts.push(text.to_string());
+        }
+
+        fn visit_anchor(&mut self, anchor: &Anchor) {
+            self.urls.push(anchor.url.clone());
+        }
+
+        fn visit_image(&mut self, image: &Image) {
+            self.urls.push(image.src.clone());
+        }
+    }
+
+    #[test]
+    fn visits_nested_nodes() {
+        let yamd = deserialize(
+            "# a [h](1)\n\n{% t\n!! title\nb **c**\n!!\n\n- d\n - [e](2)\n%}\n\n![i](3)\n![j](4)\n\n| f | [g](5) |\n| --- | --- |\n| **h** |\n\n[^k]: l [m](6)",
+        );
+        let mut collect = Collect::default();
+        collect.visit_yamd(&yamd);
+        assert_eq!(collect.texts, vec!["a ", "b ", "c", "d", "f", "h", "l "]);
+        assert_eq!(collect.urls, vec!["1", "2", "3", "4", "5", "6"]);
+    }
+}
@@ -0,0 +1,321 @@
+use crate::nodes::{
+    Anchor, Bold, BoldNodes, Code, CodeSpan, Collapsible, Custom, DisplayMath, Embed, Emphasis,
+    FootnoteDefinition, FootnoteReference, Heading, HeadingNodes, Highlight, Image, Images,
+    InlineMath, Italic, List, ListItem, Paragraph, ParagraphNodes, Strikethrough, Table, TableCell,
+    TableRow, ThematicBreak, Yamd, YamdNodes,
+};
+
+/// Visits nodes by mutable reference.
+///
+/// Example:
+///
+/// ```rust
+/// use yamd::{deserialize, nodes::Anchor, visit::VisitMut};
+///
+/// struct Https;
+///
+/// impl VisitMut for Https {
+///     fn visit_anchor_mut(&mut self, anchor: &mut Anchor) {
+///         anchor.url = anchor.url.replace("http://", "https://");
+///     }
+/// }
+///
+/// let mut yamd = deserialize("- [link](http://example.com)");
+/// Https.visit_yamd_mut(&mut yamd);
+/// assert_eq!(yamd.to_string(), "- [link](https://example.com)");
+/// ```
+pub trait VisitMut {
+    fn visit_yamd_mut(&mut self, yamd: &mut Yamd) {
+        walk_yamd_mut(self, yamd);
+    }
+
+    fn visit_yamd_node_mut(&mut self, node: &mut YamdNodes) {
+        walk_yamd_node_mut(self, node);
+    }
+
+    fn visit_paragraph_mut(&mut self, paragraph: &mut Paragraph) {
+        walk_paragraph_mut(self, paragraph);
+    }
+
+    fn visit_paragraph_node_mut(&mut self, node: &mut ParagraphNodes) {
+        walk_paragraph_node_mut(self, node);
+    }
+
+    fn visit_heading_mut(&mut self, heading: &mut Heading) {
+        walk_heading_mut(self, heading);
+    }
+
+    fn visit_heading_node_mut(&mut self, node: &mut HeadingNodes) {
+        walk_heading_node_mut(self, node);
+    }
+
+    fn visit_bold_mut(&mut self, bold: &mut Bold) {
+        walk_bold_mut(self, bold);
+    }
+
+    fn visit_bold_node_mut(&mut self, node: &mut BoldNodes) {
+        walk_bold_node_mut(self, node);
+    }
+
+    /// Visits plain text of [ParagraphNodes], [HeadingNodes] and [BoldNodes].
+    fn visit_text_mut(&mut self, _text: &mut String) {}
+
+    fn visit_anchor_mut(&mut self, _anchor: &mut Anchor) {}
+
+    fn visit_italic_mut(&mut self, _italic: &mut Italic) {}
+
+    fn visit_strikethrough_mut(&mut self, _strikethrough: &mut Strikethrough) {}
+
+    fn visit_emphasis_mut(&mut self, _emphasis: &mut Emphasis) {}
+
+    fn visit_code_span_mut(&mut self, _code_span: &mut CodeSpan) {}
+
+    fn visit_image_mut(&mut self, _image: &mut Image) {}
+
+    fn visit_images_mut(&mut self, images: &mut Images) {
+        walk_images_mut(self, images);
+    }
+
+    fn visit_code_mut(&mut self, _code: &mut Code) {}
+
+    fn visit_list_mut(&mut self, list: &mut List) {
+        walk_list_mut(self, list);
+    }
+
+    fn visit_list_item_mut(&mut self, list_item: &mut ListItem) {
+        walk_list_item_mut(self, list_item);
+    }
+
+    fn visit_highlight_mut(&mut self, highlight: &mut Highlight) {
+        walk_highlight_mut(self, highlight);
+    }
+
+    fn visit_thematic_break_mut(&mut self, _thematic_break: &mut ThematicBreak) {}
+
+    fn visit_embed_mut(&mut self, _embed: &mut Embed) {}
+
+    fn visit_custom_mut(&mut self, custom: &mut Custom) {
+        walk_custom_mut(self, custom);
+    }
+
+    fn visit_collapsible_mut(&mut self, collapsible: &mut Collapsible) {
+        walk_collapsible_mut(self, collapsible);
+    }
+
+    fn visit_table_mut(&mut self, table: &mut Table) {
+        walk_table_mut(self, table);
+    }
+
+    fn visit_table_row_mut(&mut self, table_row: &mut TableRow) {
+        walk_table_row_mut(self, table_row);
+    }
+
+    fn visit_table_cell_mut(&mut self, table_cell: &mut TableCell) {
+        walk_table_cell_mut(self, table_cell);
+    }
+
+    fn visit_footnote_reference_mut(&mut self, _footnote_reference: &mut FootnoteReference) {}
+
+    fn visit_footnote_definition_mut(&mut self, footnote_definition: &mut FootnoteDefinition) {
+        walk_footnote_definition_mut(self, footnote_definition);
+    }
+
+    fn visit_inline_math_mut(&mut self, _inline_math: &mut InlineMath) {}
+
+    fn visit_display_math_mut(&mut self, _display_math: &mut DisplayMath) {}
+}
+
+pub fn walk_yamd_mut<V: VisitMut + ?Sized>(v: &mut V, yamd: &mut Yamd) {
+    yamd.body
+        .iter_mut()
+        .for_each(|node| v.visit_yamd_node_mut(node));
+}
+
+pub fn walk_yamd_node_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut YamdNodes) {
+    match node {
+        YamdNodes::Paragraph(node) => v.visit_paragraph_mut(node),
+        YamdNodes::Heading(node) => v.visit_heading_mut(node),
+        YamdNodes::Image(node) => v.visit_image_mut(node),
+        YamdNodes::Images(node) => v.visit_images_mut(node),
+        YamdNodes::Code(node) => v.visit_code_mut(node),
+        YamdNodes::List(node) => v.visit_list_mut(node),
+        YamdNodes::Highlight(node) => v.visit_highlight_mut(node),
+        YamdNodes::ThematicBreak(node) => v.visit_thematic_break_mut(node),
+        YamdNodes::Embed(node) => v.visit_embed_mut(node),
+        YamdNodes::Collapsible(node) => v.visit_collapsible_mut(node),
+        YamdNodes::Table(node) => v.visit_table_mut(node),
+        YamdNodes::FootnoteDefinition(node) => v.visit_footnote_definition_mut(node),
+        YamdNodes::DisplayMath(node) => v.visit_display_math_mut(node),
+        YamdNodes::Custom(node) => v.visit_custom_mut(node),
+    }
+}
+
+pub fn walk_paragraph_mut<V: VisitMut + ?Sized>(v: &mut V, paragraph: &mut Paragraph) {
+    paragraph
+        .body
+        .iter_mut()
+        .for_each(|node| v.visit_paragraph_node_mut(node));
+}
+
+pub fn walk_paragraph_node_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ParagraphNodes) {
+    match node {
+        ParagraphNodes::Anchor(node) => v.visit_anchor_mut(node),
+        ParagraphNodes::Bold(node) => v.visit_bold_mut(node),
+        ParagraphNodes::Italic(node) => v.visit_italic_mut(node),
+        ParagraphNodes::Strikethrough(node) => v.visit_strikethrough_mut(node),
+        ParagraphNodes::Text(text) => v.visit_text_mut(text),
+        ParagraphNodes::CodeSpan(node) => v.visit_code_span_mut(node),
+        ParagraphNodes::Emphasis(node) => v.visit_emphasis_mut(node),
+        ParagraphNodes::FootnoteReference(node) => v.visit_footnote_reference_mut(node),
+        ParagraphNodes::InlineMath(node) => v.visit_inline_math_mut(node),
+    }
+}
+
+pub fn walk_heading_mut<V: VisitMut + ?Sized>(v: &mut V, heading: &mut Heading) {
+    heading
+        .body
+        .iter_mut()
+        .for_each(|node| v.visit_heading_node_mut(node));
+}
+
+pub fn walk_heading_node_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut HeadingNodes) {
+    match node {
+        HeadingNodes::Text(text) => v.visit_text_mut(text),
+        HeadingNodes::Anchor(node) => v.visit_anchor_mut(node),
+        HeadingNodes::InlineMath(node) => v.visit_inline_math_mut(node),
+    }
+}
+
+pub fn walk_bold_mut<V: VisitMut + ?Sized>(v: &mut V, bold: &mut Bold) {
+    bold.body
+        .iter_mut()
+        .for_each(|node| v.visit_bold_node_mut(node));
+}
+
+pub fn walk_bold_node_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut BoldNodes) {
+    match node {
+        BoldNodes::Italic(node) => v.visit_italic_mut(node),
+        BoldNodes::Strikethrough(node) => v.visit_strikethrough_mut(node),
+        BoldNodes::Text(text) => v.visit_text_mut(text),
+    }
+}
+
+pub fn walk_images_mut<V: VisitMut + ?Sized>(v: &mut V, images: &mut Images) {
+    images
+        .body
+        .iter_mut()
+        .for_each(|node| v.visit_image_mut(node));
+}
+
+pub fn walk_list_mut<V: VisitMut + ?Sized>(v: &mut V, list: &mut List) {
+    list.body
+        .iter_mut()
+        .for_each(|node| v.visit_list_item_mut(node));
+}
+
+pub fn walk_list_item_mut<V: VisitMut + ?Sized>(v: &mut V, list_item: &mut ListItem) {
+    list_item
+        .text
+        .iter_mut()
+        .for_each(|node| v.visit_paragraph_node_mut(node));
+    if let Some(nested_list) = &mut list_item.nested_list {
+        v.visit_list_mut(nested_list);
+    }
+}
+
+pub fn walk_highlight_mut<V: VisitMut + ?Sized>(v: &mut V, highlight: &mut Highlight) {
+    highlight
+        .body
+        .iter_mut()
+        .for_each(|node| v.visit_paragraph_mut(node));
+}
+
+pub fn walk_collapsible_mut<V: VisitMut + ?Sized>(v: &mut V, collapsible: &mut Collapsible) {
+    collapsible
+        .body
+        .iter_mut()
+        .for_each(|node| v.visit_yamd_node_mut(node));
+}
+
+pub fn walk_custom_mut<V: VisitMut + ?Sized>(v: &mut V, custom: &mut Custom) {
+    custom
+        .nodes
+        .iter_mut()
+        .for_each(|node| v.visit_yamd_node_mut(node));
+}
+
+/// Visits the header row, then body rows.
+pub fn walk_table_mut<V: VisitMut + ?Sized>(v: &mut V, table: &mut Table) {
+    v.visit_table_row_mut(&mut table.header);
+    table
+        .body
+        .iter_mut()
+        .for_each(|row| v.visit_table_row_mut(row));
+}
+
+pub fn walk_table_row_mut<V: VisitMut + ?Sized>(v: &mut V, table_row: &mut TableRow) {
+    table_row
+        .cells
+        .iter_mut()
+        .for_each(|cell| v.visit_table_cell_mut(cell));
+}
+
+pub fn walk_table_cell_mut<V: VisitMut + ?Sized>(v: &mut V, table_cell: &mut TableCell) {
+    table_cell
+        .body
+        .iter_mut()
+        .for_each(|node| v.visit_paragraph_node_mut(node));
+}
+
+pub fn walk_footnote_definition_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    footnote_definition: &mut FootnoteDefinition,
+) {
+    footnote_definition
+        .body
+        .iter_mut()
+        .for_each(|node| v.visit_paragraph_node_mut(node));
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        deserialize,
+        nodes::Heading,
+        visit::{VisitMut, walk_heading_mut},
+    };
+
+    struct Upper;
+
+    impl VisitMut for Upper {
+        fn visit_text_mut(&mut self, text: &mut String) {
+            *text = text.to_uppercase();
+        }
+    }
+
+    struct Demote;
+
+    impl VisitMut for Demote {
+        fn visit_heading_mut(&mut self, heading: &mut Heading) {
+            heading.level = (heading.level + 1).min(6);
+            walk_heading_mut(self, heading);
+        }
+    }
+
+    #[test]
+    fn rewrites_nested_text() {
+        let mut yamd = deserialize("# a\n\n{% t\n- b **c** _d_\n - e\n%}");
+        Upper.visit_yamd_mut(&mut yamd);
+        assert_eq!(yamd.to_string(), "# A\n\n{% t\n- B **C** _d_\n - E\n%}");
+    }
+
+    #[test]
+    fn override_can_keep_walking() {
+        let mut yamd = deserialize("# a\n\n###### b");
+        Demote.visit_yamd_mut(&mut yamd);
+        Upper.visit_yamd_mut(&mut yamd);
+        assert_eq!(yamd.to_string(), "## A\n\n###### B");
+    }
+}
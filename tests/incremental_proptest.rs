@@ -0,0 +1,73 @@
+use proptest::prelude::*;
+use yamd::deserialize_with_spans;
+use yamd::incremental::Document;
+
+fn arb_text() -> impl Strategy<Value = String> {
+    prop::collection::vec(
+        prop_oneof![
+            "[a-z ]{1,5}",
+            Just("\n".to_string()),
+            Just("\n\n".to_string()),
+            Just("\r\n".to_string()),
+            Just("\r".to_string()),
+            Just("- ".to_string()),
+            Just("\n - ".to_string()),
+            Just("# ".to_string()),
+            Just("**".to_string()),
+            Just("_".to_string()),
+            Just("`".to_string()),
+            Just("```".to_string()),
+            Just("\n```\n".to_string()),
+            Just("!! ".to_string()),
+            Just("\n!!\n".to_string()),
+            Just("{% ".to_string()),
+            Just("\n%}".to_string()),
+            Just("{{a|b}}".to_string()),
+            Just("\n| ".to_string()),
+            Just(" |".to_string()),
+            Just("![a](b)".to_string()),
+            Just("[a](b)".to_string()),
+            Just("\n[^a]: ".to_string()),
+            Just("---".to_string()),
+            Just("+++".to_string()),
+            Just("---\na: b\n---\n\n".to_string()),
+            Just("\n$$\n".to_string()),
+            Just("\\".to_string()),
+            Just("🤔".to_string()),
+        ],
+        0..20,
+    )
+    .prop_map(|parts| parts.concat())
+}
+
+/// Moves the fraction of the length of `source` to a char boundary.
+fn point(source: &str, fraction: f64) -> usize {
+    let mut point = (source.len() as f64 * fraction) as usize;
+    while !source.is_char_boundary(point) {
+        point += 1;
+    }
+    point
+}
+
+proptest! {
+    #[test]
+    fn edits_match_deserialize(
+        source in arb_text(),
+        edits in prop::collection::vec((0.0..1.0f64, 0.0..0.2f64, arb_text()), 1..6),
+    ) {
+        let mut document = Document::new(source);
+        for (start, len, text) in edits {
+            let start = point(document.source(), start);
+            let end = start + point(&document.source()[start..], len);
+            let before = document.clone();
+            let replaced = document.edit(start..end, &text);
+
+            let (yamd, spans) = deserialize_with_spans(document.source());
+            prop_assert_eq!(document.yamd(), &yamd, "Source: {:?}", document.source());
+            prop_assert_eq!(document.spans(), &spans, "Source: {:?}", document.source());
+            let kept = before.yamd().body.len() - (yamd.body.len() - replaced.end);
+            prop_assert_eq!(&yamd.body[..replaced.start], &before.yamd().body[..replaced.start]);
+            prop_assert_eq!(&yamd.body[replaced.end..], &before.yamd().body[kept..]);
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use yamd::deserialize;
 use yamd::nodes::{
     Bold, BoldNodes, Code, CodeSpan, Collapsible, Heading, HeadingNodes, Highlight, Italic, List,
-    ListItem, ListTypes, Paragraph, ParagraphNodes, ThematicBreak, Yamd, YamdNodes,
+    ListItem, ListTypes, MetadataDelimiter, Paragraph, ParagraphNodes, ThematicBreak, Yamd,
+    YamdNodes,
 };
 
 fn round_trip(yamd: &Yamd) {
@@ -144,6 +145,18 @@ fn metadata_with_triple_dash() {
     round_trip(&yamd);
 }
 
+#[test]
+fn metadata_with_pluses() {
+    let yamd = Yamd {
+        metadata_delimiter: MetadataDelimiter::Pluses,
+        ..Yamd::new(
+            Some("title = 'test'\n[params]\nkey = '---'".to_string()),
+            vec![Paragraph::new(vec![ParagraphNodes::from("body".to_string())]).into()],
+        )
+    };
+    round_trip(&yamd);
+}
+
 #[test]
 fn full_document_round_trip() {
     let yamd = Yamd::new(
@@ -1,10 +1,11 @@
 use proptest::prelude::*;
-use yamd::deserialize;
 use yamd::nodes::{
-    Anchor, Bold, BoldNodes, Code, CodeSpan, Collapsible, Embed, Emphasis, Heading, HeadingNodes,
-    Highlight, Image, Images, Italic, List, ListItem, ListTypes, Paragraph, ParagraphNodes,
-    Strikethrough, ThematicBreak, Yamd, YamdNodes,
+    Anchor, Bold, BoldNodes, Code, CodeSpan, Collapsible, DisplayMath, Embed, Emphasis,
+    FootnoteDefinition, FootnoteReference, Heading, HeadingNodes, Highlight, Image, Images,
+    InlineMath, Italic, List, ListItem, ListTypes, MetadataDelimiter, Paragraph, ParagraphNodes,
+    Strikethrough, Table, TableAlignment, TableCell, TableRow, ThematicBreak, Yamd, YamdNodes,
 };
+use yamd::{deserialize, deserialize_borrowed};
 
 fn arb_text() -> impl Strategy<Value = String> {
     prop_oneof![
@@ -260,6 +261,8 @@ proptest! {
     fn list_round_trip(
         item1 in arb_list_item_text(),
         item2 in arb_list_item_text(),
+        checked1 in proptest::option::of(any::<bool>()),
+        checked2 in proptest::option::of(any::<bool>()),
         ordered in any::<bool>()
     ) {
         let list_type = if ordered { ListTypes::Ordered } else { ListTypes::Unordered };
@@ -268,8 +271,8 @@ proptest! {
                 list_type,
                 0,
                 vec![
-                    ListItem::new(vec![ParagraphNodes::from(item1)], None),
-                    ListItem::new(vec![ParagraphNodes::from(item2)], None),
+                    ListItem { checked: checked1, text: vec![ParagraphNodes::from(item1)], nested_list: None },
+                    ListItem { checked: checked2, text: vec![ParagraphNodes::from(item2)], nested_list: None },
                 ],
             ).into(),
         ]);
@@ -294,6 +297,32 @@ proptest! {
         prop_assert_eq!(yamd, deserialized, "Serialized: {:?}", serialized);
     }
 
+    #[test]
+    fn footnote_round_trip(
+        label in arb_footnote_label(),
+        text in arb_inline_text(),
+        body in proptest::option::of(arb_paragraph_node())
+    ) {
+        let yamd = Yamd::new(None, vec![
+            Paragraph::new(vec![
+                FootnoteReference::new(label.clone()).into(),
+                ParagraphNodes::from(text),
+            ]).into(),
+            FootnoteDefinition::new(label, body.into_iter().collect()).into(),
+        ]);
+        let serialized = yamd.to_string();
+        let deserialized = deserialize(&serialized);
+        prop_assert_eq!(yamd, deserialized, "Serialized: {:?}", serialized);
+    }
+
+    #[test]
+    fn table_round_trip(table in arb_table()) {
+        let yamd = Yamd::new(None, vec![table.into()]);
+        let serialized = yamd.to_string();
+        let deserialized = deserialize(&serialized);
+        prop_assert_eq!(yamd, deserialized, "Serialized: {:?}", serialized);
+    }
+
     #[test]
     fn collapsible_round_trip_mixed_body(
         title in arb_inline_text(),
@@ -308,6 +337,10 @@ proptest! {
     }
 }
 
+fn arb_footnote_label() -> impl Strategy<Value = String> {
+    "[a-zA-Z0-9^*_:#-]{1,10}"
+}
+
 fn arb_paragraph_node() -> impl Strategy<Value = ParagraphNodes> {
     prop_oneof![
         arb_inline_text().prop_map(ParagraphNodes::from),
@@ -317,6 +350,9 @@ fn arb_paragraph_node() -> impl Strategy<Value = ParagraphNodes> {
         arb_inline_text().prop_map(|t| ParagraphNodes::from(CodeSpan::new(t))),
         (arb_inline_text(), arb_url()).prop_map(|(t, u)| ParagraphNodes::from(Anchor::new(t, u))),
         "[a-zA-Z0-9]{1,20}".prop_map(|t| ParagraphNodes::from(Bold::new(vec![BoldNodes::from(t)]))),
+        arb_footnote_label().prop_map(|l| ParagraphNodes::from(FootnoteReference::new(l))),
+        "[a-z^{}+]([a-z^{}+ ]{0,6}[a-z^{}+])?"
+            .prop_map(|t| ParagraphNodes::from(InlineMath::new(t))),
     ]
 }
 
@@ -327,6 +363,33 @@ fn arb_paragraph() -> impl Strategy<Value = Paragraph> {
     ]
 }
 
+fn arb_table_row(cells: usize) -> impl Strategy<Value = TableRow> {
+    prop::collection::vec(
+        arb_paragraph_node().prop_map(|n| TableCell::new(vec![n])),
+        cells,
+    )
+    .prop_map(TableRow::new)
+}
+
+fn arb_table() -> impl Strategy<Value = Table> {
+    (1usize..=3).prop_flat_map(|cells| {
+        (
+            arb_table_row(cells),
+            prop::collection::vec(
+                prop_oneof![
+                    Just(TableAlignment::None),
+                    Just(TableAlignment::Left),
+                    Just(TableAlignment::Center),
+                    Just(TableAlignment::Right),
+                ],
+                cells,
+            ),
+            prop::collection::vec(arb_table_row(cells), 0..=3),
+        )
+            .prop_map(|(header, alignments, body)| Table::new(header, alignments, body))
+    })
+}
+
 fn arb_yamd_node() -> impl Strategy<Value = YamdNodes> {
     prop_oneof![
         arb_paragraph().prop_map(YamdNodes::from),
@@ -344,6 +407,13 @@ fn arb_yamd_node() -> impl Strategy<Value = YamdNodes> {
         (arb_inline_text(), arb_block_text()).prop_map(|(l, c)| Code::new(l, c).into()),
         Just(ThematicBreak::new().into()),
         (arb_inline_text(), arb_block_text()).prop_map(|(k, a)| Embed::new(k, a).into()),
+        arb_table().prop_map(YamdNodes::from),
+        (
+            arb_footnote_label(),
+            proptest::option::of(arb_paragraph_node())
+        )
+            .prop_map(|(l, body)| FootnoteDefinition::new(l, body.into_iter().collect()).into()),
+        "[a-z^{}+\\\\ ]{0,7}[a-z](\n[a-z^{}+ ]{1,8})?".prop_map(|b| DisplayMath::new(b).into()),
     ]
 }
 
@@ -354,9 +424,21 @@ fn arb_yamd() -> impl Strategy<Value = Yamd> {
                 !s.trim().is_empty()
             }),
         ),
+        prop_oneof![
+            Just(MetadataDelimiter::Dashes),
+            Just(MetadataDelimiter::Pluses)
+        ],
         prop::collection::vec(arb_yamd_node(), 1..=5),
     )
-        .prop_map(|(metadata, body)| Yamd::new(metadata, body))
+        .prop_map(|(metadata, metadata_delimiter, body)| Yamd {
+            // delimiter is only written when there is metadata
+            metadata_delimiter: if metadata.is_some() {
+                metadata_delimiter
+            } else {
+                MetadataDelimiter::default()
+            },
+            ..Yamd::new(metadata, body)
+        })
 }
 
 proptest! {
@@ -366,4 +448,15 @@ proptest! {
         let deserialized = deserialize(&serialized);
         prop_assert_eq!(yamd, deserialized, "Serialized: {:?}", serialized);
     }
+
+    #[test]
+    fn borrowed_yamd_matches_owned(yamd in arb_yamd()) {
+        let serialized = yamd.to_string();
+        // `Cow<str>` has the same debug representation as `String`
+        prop_assert_eq!(
+            format!("{:?}", deserialize_borrowed(&serialized)),
+            format!("{:?}", deserialize(&serialized)),
+            "Serialized: {:?}", serialized
+        );
+    }
 }
@@ -0,0 +1,149 @@
+use proptest::prelude::*;
+use yamd::deserialize_with_spans;
+use yamd::nodes::{BoldNodes, HeadingNodes, ListItem, ParagraphNodes, YamdNodes};
+use yamd::op::SpanNode;
+
+/// Shape of a tree: number of children of every node, in pre-order.
+#[derive(Debug, PartialEq)]
+struct Shape(Vec<Shape>);
+
+fn leaf() -> Shape {
+    Shape(vec![])
+}
+
+fn paragraph_node(node: &ParagraphNodes) -> Shape {
+    match node {
+        ParagraphNodes::Bold(bold) => Shape(
+            bold.body
+                .iter()
+                .map(|n| match n {
+                    BoldNodes::Italic(_) | BoldNodes::Strikethrough(_) | BoldNodes::Text(_) => {
+                        leaf()
+                    }
+                })
+                .collect(),
+        ),
+        _ => leaf(),
+    }
+}
+
+fn list_item(item: &ListItem) -> Shape {
+    let mut children: Vec<Shape> = item.text.iter().map(paragraph_node).collect();
+    if let Some(list) = &item.nested_list {
+        children.push(Shape(list.body.iter().map(list_item).collect()));
+    }
+    Shape(children)
+}
+
+fn yamd_node(node: &YamdNodes) -> Shape {
+    match node {
+        YamdNodes::Paragraph(p) => Shape(p.body.iter().map(paragraph_node).collect()),
+        YamdNodes::Heading(h) => Shape(
+            h.body
+                .iter()
+                .map(|n| match n {
+                    HeadingNodes::Text(_)
+                    | HeadingNodes::Anchor(_)
+                    | HeadingNodes::InlineMath(_) => leaf(),
+                })
+                .collect(),
+        ),
+        YamdNodes::Images(i) => Shape(i.body.iter().map(|_| leaf()).collect()),
+        YamdNodes::List(l) => Shape(l.body.iter().map(list_item).collect()),
+        YamdNodes::Highlight(h) => Shape(
+            h.body
+                .iter()
+                .map(|p| Shape(p.body.iter().map(paragraph_node).collect()))
+                .collect(),
+        ),
+        YamdNodes::Collapsible(c) => Shape(c.body.iter().map(yamd_node).collect()),
+        YamdNodes::FootnoteDefinition(d) => Shape(d.body.iter().map(paragraph_node).collect()),
+        YamdNodes::Table(t) => Shape(
+            std::iter::once(&t.header)
+                .chain(&t.body)
+                .map(|row| {
+                    Shape(
+                        row.cells
+                            .iter()
+                            .map(|cell| Shape(cell.body.iter().map(paragraph_node).collect()))
+                            .collect(),
+                    )
+                })
+                .collect(),
+        ),
+        _ => leaf(),
+    }
+}
+
+fn span_shape(node: &SpanNode) -> Shape {
+    Shape(node.children.iter().map(span_shape).collect())
+}
+
+fn assert_nested(node: &SpanNode, source: &str) -> Result<(), TestCaseError> {
+    let range = &node.span.range;
+    prop_assert!(range.start <= range.end && range.end <= source.len());
+    prop_assert!(source.is_char_boundary(range.start) && source.is_char_boundary(range.end));
+    prop_assert_eq!(node.span.start.byte_index, range.start);
+    prop_assert_eq!(node.span.end.byte_index, range.end);
+    for child in &node.children {
+        prop_assert!(range.start <= child.span.range.start && child.span.range.end <= range.end);
+        assert_nested(child, source)?;
+    }
+    Ok(())
+}
+
+fn arb_source() -> impl Strategy<Value = String> {
+    prop::collection::vec(
+        prop_oneof![
+            "[a-z ]{1,5}",
+            Just("\n".to_string()),
+            Just("\n\n".to_string()),
+            Just("- ".to_string()),
+            Just("\n - ".to_string()),
+            Just("[x] ".to_string()),
+            Just("+ ".to_string()),
+            Just("# ".to_string()),
+            Just("**".to_string()),
+            Just("_".to_string()),
+            Just("~~".to_string()),
+            Just("*".to_string()),
+            Just("`".to_string()),
+            Just("```".to_string()),
+            Just("!! ".to_string()),
+            Just("! ".to_string()),
+            Just("{% ".to_string()),
+            Just("%}".to_string()),
+            Just("{{".to_string()),
+            Just("}}".to_string()),
+            Just("|".to_string()),
+            Just("\n| ".to_string()),
+            Just(" |".to_string()),
+            Just("![a](b)".to_string()),
+            Just("[a](b)".to_string()),
+            Just("[^a]".to_string()),
+            Just("\n[^a]: ".to_string()),
+            Just("---".to_string()),
+            Just("-----".to_string()),
+            Just("$".to_string()),
+            Just("\n$$\n".to_string()),
+            Just("\\".to_string()),
+            Just("🤔".to_string()),
+        ],
+        0..30,
+    )
+    .prop_map(|parts| parts.concat())
+}
+
+proptest! {
+    #[test]
+    fn spans_mirror_ast(source in arb_source()) {
+        let (yamd, spans) = deserialize_with_spans(&source);
+        let expected: Vec<Shape> = yamd.body.iter().map(yamd_node).collect();
+        let actual: Vec<Shape> = spans.body.iter().map(span_shape).collect();
+        prop_assert_eq!(expected, actual, "Source: {:?}", source);
+        prop_assert_eq!(yamd.metadata.is_some(), spans.metadata.is_some());
+        for node in &spans.body {
+            assert_nested(node, &source)?;
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use proptest::prelude::*;
+use yamd::deserialize;
+use yamd::nodes::Yamd;
+use yamd::stream::{Block, Stream};
+
+fn arb_source() -> impl Strategy<Value = String> {
+    let body = prop::collection::vec(
+        prop_oneof![
+            "[a-z ]{1,5}",
+            Just("\n".to_string()),
+            Just("\n\n".to_string()),
+            Just("\r\n".to_string()),
+            Just("- ".to_string()),
+            Just("\n - ".to_string()),
+            Just("+ ".to_string()),
+            Just("# ".to_string()),
+            Just("**".to_string()),
+            Just("_".to_string()),
+            Just("~~".to_string()),
+            Just("`".to_string()),
+            Just("```".to_string()),
+            Just("\n```\n".to_string()),
+            Just("!! ".to_string()),
+            Just("\n!!\n".to_string()),
+            Just("{% ".to_string()),
+            Just("\n%}".to_string()),
+            Just("{{a|b}}".to_string()),
+            Just("\n| ".to_string()),
+            Just(" |".to_string()),
+            Just("![a](b)".to_string()),
+            Just("[a](b)".to_string()),
+            Just("\n[^a]: ".to_string()),
+            Just("---".to_string()),
+            Just("+++".to_string()),
+            Just("\n$$\n".to_string()),
+            Just("\\".to_string()),
+            Just("🤔".to_string()),
+        ],
+        0..40,
+    );
+    let metadata = prop_oneof![
+        Just(""),
+        Just("---\na: b\n---\n\n"),
+        Just("+++\na = 'b'\n\n+++\n"),
+        Just("---\n"),
+    ];
+    (metadata, body).prop_map(|(metadata, body)| format!("{metadata}{}", body.concat()))
+}
+
+/// Splits `source` at the given fractions of its length, moved to char boundaries.
+fn chunks(source: &str, splits: &[f64]) -> Vec<String> {
+    let mut points: Vec<usize> = splits
+        .iter()
+        .map(|split| {
+            let mut point = (source.len() as f64 * split) as usize;
+            while !source.is_char_boundary(point) {
+                point += 1;
+            }
+            point
+        })
+        .collect();
+    points.sort();
+    points.push(source.len());
+    let mut start = 0;
+    points
+        .into_iter()
+        .map(|end| {
+            let chunk = source[start..end].to_string();
+            start = end;
+            chunk
+        })
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn stream_matches_deserialize(
+        source in arb_source(),
+        splits in prop::collection::vec(0.0..1.0f64, 0..10),
+    ) {
+        let mut stream = Stream::new();
+        let mut blocks = vec![];
+        for chunk in chunks(&source, &splits) {
+            blocks.extend(stream.push(&chunk));
+        }
+        blocks.extend(stream.finish());
+
+        let mut yamd = Yamd::default();
+        for block in blocks {
+            match block {
+                Block::Metadata { metadata, delimiter } => {
+                    prop_assert!(yamd.metadata.is_none() && yamd.body.is_empty());
+                    yamd.metadata = Some(metadata);
+                    yamd.metadata_delimiter = delimiter;
+                }
+                Block::Node(node) => yamd.body.push(node),
+            }
+        }
+        prop_assert_eq!(yamd, deserialize(&source), "Source: {:?}", source);
+    }
+}
@@ -26,12 +26,13 @@ pub fn random(length: usize, max_literal_len: usize) {
         TokenKind::RightParenthesis => 18,
         TokenKind::Underscore => 19,
         TokenKind::Pipe => 20,
-        TokenKind::Literal => 21,
+        TokenKind::Dollar => 21,
+        TokenKind::Literal => 22,
     };
 
     let mut out = String::with_capacity(length + 10);
     while out.len() < length {
-        let token = match rng.gen_range(0..=21) {
+        let token = match rng.gen_range(0..=22) {
             0 => "\n\n",
             1 => "\n",
             2 => "{",
@@ -53,6 +54,7 @@ pub fn random(length: usize, max_literal_len: usize) {
             18 => ")",
             19 => "_",
             20 => "|",
+            21 => "$",
             _ => &random_string(rng.gen_range(3..max_literal_len), &mut rng),
         };
         out.push_str(token);